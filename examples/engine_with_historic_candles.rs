@@ -1,4 +1,5 @@
 use barter::{
+    clock::SharedClock,
     data::historical,
     engine::{trader::Trader, Engine},
     event::{Event, EventTx},
@@ -14,7 +15,10 @@ use barter::{
         trading::{Config as StatisticConfig, TradingSummary},
         Initialiser,
     },
-    strategy::example::{Config as StrategyConfig, RSIStrategy},
+    strategy::{
+        example::{Config as StrategyConfig, RSIStrategy},
+        StrategyId,
+    },
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_data::subscription::candle::Candle;
@@ -42,6 +46,10 @@ async fn main() {
     // Create the Market(s) to be traded on (1-to-1 relationship with a Trader)
     let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
 
+    // Create a Clock driven by the MarketEvent exchange_time, shared by every component so
+    // backtest fills & Position durations reflect market time
+    let clock = SharedClock::historical();
+
     // Build global shared-state MetaPortfolio (1-to-1 relationship with an Engine)
     let portfolio = Arc::new(Mutex::new(
         MetaPortfolio::builder()
@@ -58,6 +66,7 @@ async fn main() {
                 trading_days_per_year: 365,
                 risk_free_return: 0.0,
             })
+            .clock(clock.clone())
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
     ));
@@ -79,13 +88,19 @@ async fn main() {
                 load_json_market_event_candles().into_iter(),
             ))
             .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
-            .execution(SimulatedExecution::new(ExecutionConfig {
-                simulated_fees_pct: Fees {
-                    exchange: 0.1,
-                    slippage: 0.05,
-                    network: 0.0,
-                },
-            }))
+            .strategy_id(StrategyId::from("rsi_14"))
+            .execution(
+                SimulatedExecution::new(ExecutionConfig {
+                    simulated_fees_pct: Fees {
+                        exchange: 0.1,
+                        slippage: 0.05,
+                        network: 0.0,
+                    },
+                    partial_fills: false,
+                })
+                .with_clock(clock.clone()),
+            )
+            .clock(clock.clone())
             .build()
             .expect("failed to build trader"),
     );
@@ -105,6 +120,7 @@ async fn main() {
             trading_days_per_year: 365,
             risk_free_return: 0.0,
         }))
+        .clock(clock)
         .build()
         .expect("failed to build engine");
 
@@ -146,6 +162,9 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
             Event::SignalPositionExit(_) => {
                 // SignalPositionExit Event occurred in Engine
             }
+            Event::SignalPositionAmend(_) => {
+                // SignalPositionAmend Event occurred in Engine
+            }
             Event::SignalForceExit(_) => {
                 // SignalForceExit Event occurred in Engine
             }
@@ -156,8 +175,9 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // OrderNew Event occurred in Engine
                 println!("{new_order:?}");
             }
-            Event::OrderUpdate => {
+            Event::OrderUpdate(order_update) => {
                 // OrderUpdate Event occurred in Engine
+                println!("{order_update:?}");
             }
             Event::Fill(fill_event) => {
                 // Fill Event occurred in Engine
@@ -179,6 +199,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::DataQuality(issue) => {
+                // MarketEvent failed a data quality check
+                println!("{issue:?}");
+            }
         }
     }
 }
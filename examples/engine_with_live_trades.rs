@@ -30,6 +30,7 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 const ENGINE_RUN_TIMEOUT: Duration = Duration::from_secs(5);
+const MARKET_FEED_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[tokio::main]
 async fn main() {
@@ -79,7 +80,10 @@ async fn main() {
             .command_rx(trader_command_rx)
             .event_tx(event_tx.clone())
             .portfolio(Arc::clone(&portfolio))
-            .data(live::MarketFeed::new(stream_market_event_trades().await))
+            .data(live::MarketFeed::with_heartbeat_timeout(
+                stream_market_event_trades().await,
+                MARKET_FEED_HEARTBEAT_TIMEOUT,
+            ))
             .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
@@ -87,6 +91,7 @@ async fn main() {
                     slippage: 0.05,
                     network: 0.0,
                 },
+                partial_fills: false,
             }))
             .build()
             .expect("failed to build trader"),
@@ -110,10 +115,10 @@ async fn main() {
         .build()
         .expect("failed to build engine");
 
-    // Run Engine trading & listen to Events it produces
+    // Run Engine trading with each Trader as a tokio task & listen to Events it produces
     tokio::spawn(listen_to_engine_events(event_rx));
 
-    let _ = tokio::time::timeout(ENGINE_RUN_TIMEOUT, engine.run()).await;
+    let _ = tokio::time::timeout(ENGINE_RUN_TIMEOUT, engine.run_async()).await;
 }
 
 async fn stream_market_event_trades() -> mpsc::UnboundedReceiver<MarketEvent<DataKind>> {
@@ -173,6 +178,9 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
             Event::SignalPositionExit(_) => {
                 // SignalPositionExit Event occurred in Engine
             }
+            Event::SignalPositionAmend(_) => {
+                // SignalPositionAmend Event occurred in Engine
+            }
             Event::SignalForceExit(_) => {
                 // SignalForceExit Event occurred in Engine
             }
@@ -183,8 +191,9 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // OrderNew Event occurred in Engine
                 println!("{new_order:?}");
             }
-            Event::OrderUpdate => {
+            Event::OrderUpdate(order_update) => {
                 // OrderUpdate Event occurred in Engine
+                println!("{order_update:?}");
             }
             Event::Fill(fill_event) => {
                 // Fill Event occurred in Engine
@@ -206,6 +215,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::DataQuality(issue) => {
+                // MarketEvent failed a data quality check
+                println!("{issue:?}");
+            }
         }
     }
 }
@@ -0,0 +1,88 @@
+use barter::{
+    backtest::{GridSearch, ParamGrid, RankBy},
+    execution::{simulated::Config as ExecutionConfig, Fees},
+    portfolio::{allocator::DefaultAllocator, risk::DefaultRisk},
+    statistic::summary::trading::Config as StatisticConfig,
+    strategy::example::{Config as StrategyConfig, RSIStrategy},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_data::subscription::candle::Candle;
+use barter_integration::model::{Exchange, Instrument, InstrumentKind, Market};
+use chrono::Utc;
+use std::fs;
+
+const DATA_HISTORIC_CANDLES_1H: &str = "examples/data/candles_1h.json";
+
+fn main() {
+    // Create the Market(s) to be backtested & load their historical MarketEvents
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+    let markets = vec![(market, load_json_market_event_candles())];
+
+    // Define the grid of configurations to sweep, every combination is backtested
+    let grid = ParamGrid {
+        strategy: [7, 14, 21]
+            .into_iter()
+            .map(|rsi_period| StrategyConfig { rsi_period })
+            .collect(),
+        allocator: [100.0, 500.0]
+            .into_iter()
+            .map(|default_order_value| DefaultAllocator {
+                default_order_value,
+            })
+            .collect(),
+        risk: vec![DefaultRisk {}],
+        execution: vec![ExecutionConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.1,
+                slippage: 0.05,
+                network: 0.0,
+            },
+            partial_fills: false,
+        }],
+    };
+
+    // Build GridSearch that backtests each combination with it's own in-memory Engine
+    let grid_search = GridSearch::builder()
+        .grid(grid)
+        .markets(markets)
+        .strategy(|config: &StrategyConfig| RSIStrategy::new(*config))
+        .starting_cash(10_000.0)
+        .statistic_config(StatisticConfig {
+            starting_equity: 10_000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        })
+        .rank_by(RankBy::SharpeRatio)
+        .build()
+        .expect("failed to build GridSearch");
+
+    // Run every backtest in parallel & print the ranked results
+    let results = grid_search.run().expect("failed to run GridSearch");
+    results.table().printstd();
+
+    // Export the ranked results as CSV & JSON
+    results
+        .write_csv(std::io::stdout())
+        .expect("failed to write CSV results");
+    results
+        .write_json(std::io::stdout())
+        .expect("failed to write JSON results");
+}
+
+fn load_json_market_event_candles() -> Vec<MarketEvent<DataKind>> {
+    let candles = fs::read_to_string(DATA_HISTORIC_CANDLES_1H).expect("failed to read file");
+
+    let candles =
+        serde_json::from_str::<Vec<Candle>>(&candles).expect("failed to parse candles String");
+
+    candles
+        .into_iter()
+        .map(|candle| MarketEvent {
+            exchange_time: candle.close_time,
+            received_time: Utc::now(),
+            exchange: Exchange::from("binance"),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            kind: DataKind::Candle(candle),
+        })
+        .collect()
+}
@@ -0,0 +1,33 @@
+use crate::{
+    engine::error::EngineError, portfolio::error::PortfolioError,
+    portfolio::repository::error::RepositoryError,
+};
+use thiserror::Error;
+
+/// All errors generated in the barter::backtest module.
+#[derive(Error, Debug)]
+pub enum BacktestError {
+    #[error("Failed to build struct due to missing attributes: {0}")]
+    BuilderIncomplete(&'static str),
+
+    #[error("Parameter grid contains no combinations to backtest")]
+    EmptyGrid,
+
+    #[error("Failed to build backtest runtime: {0}")]
+    Runtime(#[from] std::io::Error),
+
+    #[error("Failed to build backtest Portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    #[error("Failed to build backtest Engine: {0}")]
+    Engine(#[from] EngineError),
+
+    #[error("Failed to read backtest results from the repository: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Failed to export backtest results as CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Failed to export backtest results as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,759 @@
+use self::error::BacktestError;
+use crate::{
+    clock::SharedClock,
+    data::historical::sync::SynchronisedReplay,
+    engine::{trader::Trader, Engine},
+    event::EventTx,
+    execution::simulated::{Config as ExecutionConfig, SimulatedExecution},
+    portfolio::{
+        allocator::OrderAllocator,
+        portfolio::MetaPortfolio,
+        repository::{in_memory::InMemoryRepository, PositionHandler},
+        risk::OrderEvaluator,
+    },
+    statistic::{
+        metric::ratio::Ratio,
+        summary::{
+            trading::{Config as StatisticConfig, TradingSummary},
+            Initialiser, PositionSummariser, TableBuilder,
+        },
+    },
+    strategy::SignalGenerator,
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::Market;
+use parking_lot::Mutex;
+use prettytable::{Cell, Table};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Barter backtest module specific errors.
+pub mod error;
+
+/// In-memory Portfolio repository used by every backtest run.
+type Repository = InMemoryRepository<TradingSummary>;
+
+/// Grid of strategy, allocator, risk & execution configurations. A [`GridSearch`] backtests
+/// every combination.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ParamGrid<StrategyConfig, Allocator, Risk> {
+    pub strategy: Vec<StrategyConfig>,
+    pub allocator: Vec<Allocator>,
+    pub risk: Vec<Risk>,
+    pub execution: Vec<ExecutionConfig>,
+}
+
+impl<StrategyConfig, Allocator, Risk> ParamGrid<StrategyConfig, Allocator, Risk>
+where
+    StrategyConfig: Clone,
+    Allocator: Clone,
+    Risk: Clone,
+{
+    /// Returns every combination of [`Params`] in the grid. The execution configuration varies
+    /// fastest, followed by the risk, allocator & strategy configurations.
+    pub fn combinations(&self) -> Vec<Params<StrategyConfig, Allocator, Risk>> {
+        let mut combinations = Vec::with_capacity(
+            self.strategy.len() * self.allocator.len() * self.risk.len() * self.execution.len(),
+        );
+
+        for strategy in &self.strategy {
+            for allocator in &self.allocator {
+                for risk in &self.risk {
+                    for execution in &self.execution {
+                        combinations.push(Params {
+                            strategy: strategy.clone(),
+                            allocator: allocator.clone(),
+                            risk: risk.clone(),
+                            execution: *execution,
+                        });
+                    }
+                }
+            }
+        }
+
+        combinations
+    }
+}
+
+/// Single combination of a [`ParamGrid`], backtested by it's own in-memory [`Engine`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Params<StrategyConfig, Allocator, Risk> {
+    pub strategy: StrategyConfig,
+    pub allocator: Allocator,
+    pub risk: Risk,
+    pub execution: ExecutionConfig,
+}
+
+/// [`TradingSummary`] metric the runs of a [`GridSearch`] are ranked by, best first.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum RankBy {
+    /// Daily Sharpe Ratio.
+    #[default]
+    SharpeRatio,
+    /// Daily Sortino Ratio.
+    SortinoRatio,
+    /// Daily Calmar Ratio.
+    CalmarRatio,
+    /// Sum of the PnL returns of every exited [`Position`](crate::portfolio::position::Position).
+    TotalReturn,
+    /// Maximum drawdown, shallowest first.
+    MaxDrawdown,
+}
+
+impl RankBy {
+    /// Returns the score of the provided [`TradingSummary`], where a higher score ranks better, or
+    /// None if the metric is not finite (eg/ a ratio of a run with too few trades to define it).
+    pub fn score(&self, summary: &TradingSummary) -> Option<f64> {
+        let score = match self {
+            RankBy::SharpeRatio => summary.tear_sheet.sharpe_ratio.daily(),
+            RankBy::SortinoRatio => summary.tear_sheet.sortino_ratio.daily(),
+            RankBy::CalmarRatio => summary.tear_sheet.calmar_ratio.daily(),
+            RankBy::TotalReturn => summary.pnl_returns.total.sum,
+            // Drawdowns are -ve, so the shallowest is the largest
+            RankBy::MaxDrawdown => summary.drawdown.max_drawdown.drawdown.drawdown,
+        };
+
+        score.is_finite().then_some(score)
+    }
+}
+
+/// Outcome of backtesting a single combination of [`Params`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RunResult<StrategyConfig, Allocator, Risk> {
+    /// Position of this run once ranked, starting at 1 for the best run.
+    pub rank: usize,
+    /// Score of the [`TradingSummary`] according to the [`RankBy`] metric, or None if it is not
+    /// finite. Runs without a score are ranked last.
+    pub score: Option<f64>,
+    pub params: Params<StrategyConfig, Allocator, Risk>,
+    pub summary: TradingSummary,
+}
+
+/// Ranked [`RunResult`]s of a [`GridSearch`], best first. Exportable as a table, CSV or JSON.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct GridSearchResults<StrategyConfig, Allocator, Risk> {
+    pub rank_by: RankBy,
+    pub runs: Vec<RunResult<StrategyConfig, Allocator, Risk>>,
+}
+
+impl<StrategyConfig, Allocator, Risk> GridSearchResults<StrategyConfig, Allocator, Risk>
+where
+    StrategyConfig: Serialize,
+    Allocator: Serialize,
+    Risk: Serialize,
+{
+    /// Returns the best [`RunResult`], if any.
+    pub fn best(&self) -> Option<&RunResult<StrategyConfig, Allocator, Risk>> {
+        self.runs.first()
+    }
+
+    /// Generates a table with a row for each ranked run, detailing it's [`Params`] as JSON
+    /// followed by it's [`TradingSummary`].
+    pub fn table(&self) -> Table {
+        let mut table = Table::new();
+
+        for (index, run) in self.runs.iter().enumerate() {
+            if index == 0 {
+                let mut titles = run.summary.titles();
+                titles.insert_cell(0, Cell::new("Params"));
+                titles.insert_cell(0, Cell::new("Score"));
+                titles.insert_cell(0, Cell::new("Rank"));
+                table.set_titles(titles);
+            }
+
+            let mut row = run.summary.row();
+            row.insert_cell(
+                0,
+                Cell::new(&serde_json::to_string(&run.params).unwrap_or_default()),
+            );
+            row.insert_cell(
+                0,
+                Cell::new(
+                    &run.score
+                        .map_or("-".to_owned(), |score| format!("{:.3}", score)),
+                ),
+            );
+            row.insert_cell(0, Cell::new(&run.rank.to_string()));
+            table.add_row(row);
+        }
+
+        table
+    }
+
+    /// Writes a CSV record for each ranked run to the provided writer. Each configuration of the
+    /// run [`Params`] is written as a JSON column, followed by the key [`TradingSummary`] metrics.
+    pub fn write_csv<W>(&self, writer: W) -> Result<(), BacktestError>
+    where
+        W: std::io::Write,
+    {
+        let mut writer = csv::Writer::from_writer(writer);
+
+        for run in &self.runs {
+            writer.serialize(CsvRecord {
+                rank: run.rank,
+                score: run.score,
+                strategy: serde_json::to_string(&run.params.strategy)?,
+                allocator: serde_json::to_string(&run.params.allocator)?,
+                risk: serde_json::to_string(&run.params.risk)?,
+                execution: serde_json::to_string(&run.params.execution)?,
+                trades: run.summary.pnl_returns.total.count,
+                losses: run.summary.pnl_returns.losses.count,
+                total_return: run.summary.pnl_returns.total.sum,
+                mean_return: run.summary.pnl_returns.total.mean,
+                sharpe_ratio: run.summary.tear_sheet.sharpe_ratio.daily(),
+                sortino_ratio: run.summary.tear_sheet.sortino_ratio.daily(),
+                calmar_ratio: run.summary.tear_sheet.calmar_ratio.daily(),
+                max_drawdown: run.summary.drawdown.max_drawdown.drawdown.drawdown,
+            })?;
+        }
+
+        writer.flush().map_err(csv::Error::from)?;
+        Ok(())
+    }
+
+    /// Writes the ranked runs as pretty printed JSON to the provided writer.
+    pub fn write_json<W>(&self, writer: W) -> Result<(), BacktestError>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer_pretty(writer, self).map_err(BacktestError::Json)
+    }
+}
+
+/// Flattened CSV representation of a [`RunResult`].
+#[derive(Debug, Serialize)]
+struct CsvRecord {
+    rank: usize,
+    score: Option<f64>,
+    strategy: String,
+    allocator: String,
+    risk: String,
+    execution: String,
+    trades: u64,
+    losses: u64,
+    total_return: f64,
+    mean_return: f64,
+    sharpe_ratio: f64,
+    sortino_ratio: f64,
+    calmar_ratio: f64,
+    max_drawdown: f64,
+}
+
+/// Parameter sweep backtest runner. Backtests every combination of a [`ParamGrid`] over the same
+/// historical [`MarketEvent`]s, each with an independent in-memory [`Engine`] (using a
+/// [`MetaPortfolio`], [`SimulatedExecution`] & a [`SynchronisedReplay`] of the markets), and
+/// ranks the resulting [`TradingSummary`]s.
+///
+/// Runs are distributed across a pool of worker threads, each driving one [`Engine`] at a time
+/// on it's own single threaded runtime.
+pub struct GridSearch<StrategyConfig, Allocator, Risk, Strategy> {
+    grid: ParamGrid<StrategyConfig, Allocator, Risk>,
+    markets: Vec<(Market, Vec<MarketEvent<DataKind>>)>,
+    strategy: Box<dyn Fn(&StrategyConfig) -> Strategy + Send + Sync>,
+    starting_cash: f64,
+    statistic_config: StatisticConfig,
+    rank_by: RankBy,
+    threads: usize,
+}
+
+impl<StrategyConfig, Allocator, Risk, Strategy> std::fmt::Debug
+    for GridSearch<StrategyConfig, Allocator, Risk, Strategy>
+where
+    StrategyConfig: std::fmt::Debug,
+    Allocator: std::fmt::Debug,
+    Risk: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridSearch")
+            .field("grid", &self.grid)
+            .field(
+                "markets",
+                &self
+                    .markets
+                    .iter()
+                    .map(|(market, _)| market)
+                    .collect::<Vec<_>>(),
+            )
+            .field("starting_cash", &self.starting_cash)
+            .field("statistic_config", &self.statistic_config)
+            .field("rank_by", &self.rank_by)
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+impl<StrategyConfig, Allocator, Risk, Strategy>
+    GridSearch<StrategyConfig, Allocator, Risk, Strategy>
+where
+    StrategyConfig: Clone + Send + Sync,
+    Allocator: OrderAllocator<Repository> + Clone + Send + Sync + 'static,
+    Risk: OrderEvaluator<Repository> + Clone + Send + Sync + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+{
+    /// Returns a [`GridSearchBuilder`] instance.
+    pub fn builder() -> GridSearchBuilder<StrategyConfig, Allocator, Risk, Strategy> {
+        GridSearchBuilder::new()
+    }
+
+    /// Backtests every combination of the [`ParamGrid`] in parallel & returns the ranked
+    /// [`GridSearchResults`]. Runs with equal scores retain their [`ParamGrid::combinations`]
+    /// order.
+    pub fn run(&self) -> Result<GridSearchResults<StrategyConfig, Allocator, Risk>, BacktestError> {
+        let combinations = self.grid.combinations();
+        if combinations.is_empty() {
+            return Err(BacktestError::EmptyGrid);
+        }
+
+        // Each worker thread backtests the next combination until none remain
+        let next = AtomicUsize::new(0);
+        let summaries = Mutex::new(Vec::with_capacity(combinations.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(combinations.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(params) = combinations.get(index) else {
+                        break;
+                    };
+
+                    let summary = self.backtest(params);
+                    summaries.lock().push((index, summary));
+                });
+            }
+        });
+
+        let mut summaries = summaries.into_inner();
+        summaries.sort_by_key(|(index, _)| *index);
+
+        let mut runs = combinations
+            .into_iter()
+            .zip(summaries)
+            .map(|(params, (_, summary))| {
+                summary.map(|summary| RunResult {
+                    rank: 0,
+                    score: self.rank_by.score(&summary),
+                    params,
+                    summary,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Rank best score first, with runs without a score ranked last
+        let rank_score = |score: Option<f64>| score.unwrap_or(f64::NEG_INFINITY);
+        runs.sort_by(|a, b| rank_score(b.score).total_cmp(&rank_score(a.score)));
+        runs.iter_mut()
+            .enumerate()
+            .for_each(|(index, run)| run.rank = index + 1);
+
+        Ok(GridSearchResults {
+            rank_by: self.rank_by,
+            runs,
+        })
+    }
+
+    /// Backtests a single combination of [`Params`] with an independent in-memory [`Engine`] on
+    /// the current thread, returning the [`TradingSummary`] of it's exited
+    /// [`Position`](crate::portfolio::position::Position)s.
+    fn backtest(
+        &self,
+        params: &Params<StrategyConfig, Allocator, Risk>,
+    ) -> Result<TradingSummary, BacktestError> {
+        let engine_id = Uuid::new_v4();
+        let clock = SharedClock::historical();
+
+        let portfolio = Arc::new(Mutex::new(
+            MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(
+                    self.markets
+                        .iter()
+                        .map(|(market, _)| market.clone())
+                        .collect(),
+                )
+                .starting_cash(self.starting_cash)
+                .repository(Repository::new())
+                .allocation_manager(params.allocator.clone())
+                .risk_manager(params.risk.clone())
+                .statistic_config(self.statistic_config)
+                .clock(clock.clone())
+                .build_and_init()?,
+        ));
+
+        // Events are not consumed by backtest runs
+        let (event_tx, _) = mpsc::unbounded_channel();
+        let event_tx = EventTx::new(event_tx);
+
+        let replay = self
+            .markets
+            .iter()
+            .fold(SynchronisedReplay::new(), |replay, (market, events)| {
+                replay.source(market.clone(), events.clone())
+            });
+
+        let mut traders = Vec::with_capacity(self.markets.len());
+        let mut trader_command_txs = HashMap::with_capacity(self.markets.len());
+        for (market, feed) in replay.into_feeds() {
+            let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+
+            traders.push(
+                Trader::builder()
+                    .engine_id(engine_id)
+                    .market(market.clone())
+                    .command_rx(trader_command_rx)
+                    .event_tx(event_tx.clone())
+                    .portfolio(Arc::clone(&portfolio))
+                    .data(feed)
+                    .strategy((self.strategy)(&params.strategy))
+                    .execution(SimulatedExecution::new(params.execution).with_clock(clock.clone()))
+                    .clock(clock.clone())
+                    .build()?,
+            );
+            trader_command_txs.insert(market, trader_command_tx);
+        }
+
+        // Engine terminates if the Command transmitter is dropped, so hold it until it's finished
+        let (_command_tx, command_rx) = mpsc::channel(1);
+
+        let engine = Engine::builder()
+            .engine_id(engine_id)
+            .command_rx(command_rx)
+            .portfolio(Arc::clone(&portfolio))
+            .traders(traders)
+            .trader_command_txs(trader_command_txs)
+            .statistics_summary(TradingSummary::init(self.statistic_config))
+            .clock(clock)
+            .print_summary(false)
+            .build()?;
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(engine.run());
+
+        let exited_positions = portfolio.lock().get_exited_positions(engine_id)?;
+        let mut summary = TradingSummary::init(self.statistic_config);
+        summary.generate_summary(&exited_positions);
+
+        Ok(summary)
+    }
+}
+
+/// Builder to construct [`GridSearch`] instances.
+pub struct GridSearchBuilder<StrategyConfig, Allocator, Risk, Strategy> {
+    grid: Option<ParamGrid<StrategyConfig, Allocator, Risk>>,
+    markets: Option<Vec<(Market, Vec<MarketEvent<DataKind>>)>>,
+    strategy: Option<Box<dyn Fn(&StrategyConfig) -> Strategy + Send + Sync>>,
+    starting_cash: Option<f64>,
+    statistic_config: Option<StatisticConfig>,
+    rank_by: Option<RankBy>,
+    threads: Option<usize>,
+}
+
+impl<StrategyConfig, Allocator, Risk, Strategy> std::fmt::Debug
+    for GridSearchBuilder<StrategyConfig, Allocator, Risk, Strategy>
+where
+    StrategyConfig: std::fmt::Debug,
+    Allocator: std::fmt::Debug,
+    Risk: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridSearchBuilder")
+            .field("grid", &self.grid)
+            .field("starting_cash", &self.starting_cash)
+            .field("statistic_config", &self.statistic_config)
+            .field("rank_by", &self.rank_by)
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+impl<StrategyConfig, Allocator, Risk, Strategy>
+    GridSearchBuilder<StrategyConfig, Allocator, Risk, Strategy>
+{
+    fn new() -> Self {
+        Self {
+            grid: None,
+            markets: None,
+            strategy: None,
+            starting_cash: None,
+            statistic_config: None,
+            rank_by: None,
+            threads: None,
+        }
+    }
+
+    pub fn grid(self, value: ParamGrid<StrategyConfig, Allocator, Risk>) -> Self {
+        Self {
+            grid: Some(value),
+            ..self
+        }
+    }
+
+    /// Historical [`MarketEvent`]s of each [`Market`] to backtest, ordered by `exchange_time`.
+    pub fn markets(self, value: Vec<(Market, Vec<MarketEvent<DataKind>>)>) -> Self {
+        Self {
+            markets: Some(value),
+            ..self
+        }
+    }
+
+    /// Constructs the strategy of each [`Trader`] from the strategy configuration of a run.
+    pub fn strategy<F>(self, value: F) -> Self
+    where
+        F: Fn(&StrategyConfig) -> Strategy + Send + Sync + 'static,
+    {
+        Self {
+            strategy: Some(Box::new(value)),
+            ..self
+        }
+    }
+
+    pub fn starting_cash(self, value: f64) -> Self {
+        Self {
+            starting_cash: Some(value),
+            ..self
+        }
+    }
+
+    pub fn statistic_config(self, value: StatisticConfig) -> Self {
+        Self {
+            statistic_config: Some(value),
+            ..self
+        }
+    }
+
+    pub fn rank_by(self, value: RankBy) -> Self {
+        Self {
+            rank_by: Some(value),
+            ..self
+        }
+    }
+
+    /// Maximum number of runs backtested in parallel. Defaults to the available parallelism.
+    pub fn threads(self, value: usize) -> Self {
+        Self {
+            threads: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<GridSearch<StrategyConfig, Allocator, Risk, Strategy>, BacktestError> {
+        Ok(GridSearch {
+            grid: self.grid.ok_or(BacktestError::BuilderIncomplete("grid"))?,
+            markets: self
+                .markets
+                .ok_or(BacktestError::BuilderIncomplete("markets"))?,
+            strategy: self
+                .strategy
+                .ok_or(BacktestError::BuilderIncomplete("strategy"))?,
+            starting_cash: self
+                .starting_cash
+                .ok_or(BacktestError::BuilderIncomplete("starting_cash"))?,
+            statistic_config: self
+                .statistic_config
+                .ok_or(BacktestError::BuilderIncomplete("statistic_config"))?,
+            rank_by: self.rank_by.unwrap_or_default(),
+            threads: self
+                .threads
+                .unwrap_or_else(|| {
+                    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+                })
+                .max(1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::synthetic::{PriceProcess, SyntheticConfig, SyntheticFeed},
+        execution::Fees,
+        portfolio::{allocator::DefaultAllocator, risk::DefaultRisk},
+        strategy::{
+            example::{Config as StrategyConfig, RSIStrategy},
+            Signal,
+        },
+    };
+    use barter_integration::model::InstrumentKind;
+
+    fn grid_search() -> GridSearch<StrategyConfig, DefaultAllocator, DefaultRisk, RSIStrategy> {
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let events = SyntheticFeed::new(SyntheticConfig {
+            events: Some(300),
+            seed: 7,
+            ..SyntheticConfig::candles(
+                market.exchange.clone(),
+                market.instrument.clone(),
+                PriceProcess::OrnsteinUhlenbeck {
+                    mean: 1000.0,
+                    reversion: 500.0,
+                    volatility: 2.0,
+                },
+            )
+        })
+        .collect();
+
+        GridSearch::builder()
+            .grid(ParamGrid {
+                strategy: vec![
+                    StrategyConfig { rsi_period: 7 },
+                    StrategyConfig { rsi_period: 14 },
+                ],
+                allocator: vec![
+                    DefaultAllocator {
+                        default_order_value: 100.0,
+                    },
+                    DefaultAllocator {
+                        default_order_value: 500.0,
+                    },
+                ],
+                risk: vec![DefaultRisk {}],
+                execution: vec![ExecutionConfig {
+                    simulated_fees_pct: Fees::default(),
+                    partial_fills: false,
+                }],
+            })
+            .markets(vec![(market, events)])
+            .strategy(|config: &StrategyConfig| RSIStrategy::new(*config))
+            .starting_cash(10_000.0)
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .rank_by(RankBy::TotalReturn)
+            .threads(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn run_backtests_every_combination_ranked_by_score() {
+        let results = grid_search().run().unwrap();
+
+        assert_eq!(results.runs.len(), 4);
+        assert_eq!(
+            results.runs.iter().map(|run| run.rank).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert!(results
+            .runs
+            .windows(2)
+            .all(|runs| runs[0].score >= runs[1].score));
+        assert!(results
+            .runs
+            .iter()
+            .all(|run| run.score == Some(run.summary.pnl_returns.total.sum)));
+        assert!(results
+            .runs
+            .iter()
+            .any(|run| run.summary.pnl_returns.total.count > 0));
+    }
+
+    #[test]
+    fn results_export_as_csv_and_json() {
+        let results = grid_search().run().unwrap();
+
+        let mut csv = Vec::new();
+        results.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 1 + results.runs.len());
+        assert!(csv.starts_with("rank,score,strategy,allocator,risk,execution,trades"));
+
+        let mut json = Vec::new();
+        results.write_json(&mut json).unwrap();
+        let parsed = serde_json::from_slice::<
+            GridSearchResults<StrategyConfig, DefaultAllocator, DefaultRisk>,
+        >(&json)
+        .unwrap();
+        assert_eq!(parsed.runs.len(), results.runs.len());
+        assert_eq!(parsed.runs[0].params, results.runs[0].params);
+    }
+
+    /// [`RSIStrategy`] that never trades if configured without an RSI period.
+    struct OptionalRsi(Option<RSIStrategy>);
+
+    impl SignalGenerator for OptionalRsi {
+        fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+            self.0.as_mut()?.generate_signal(market)
+        }
+    }
+
+    #[test]
+    fn results_ranked_by_sharpe_ratio_round_trip_json_with_no_trade_run() {
+        let (market, events) = grid_search().markets.remove(0);
+        let results = GridSearch::builder()
+            .grid(ParamGrid {
+                strategy: vec![None, Some(StrategyConfig { rsi_period: 14 })],
+                allocator: vec![DefaultAllocator {
+                    default_order_value: 100.0,
+                }],
+                risk: vec![DefaultRisk {}],
+                execution: vec![ExecutionConfig::default()],
+            })
+            .markets(vec![(market, events)])
+            .strategy(|config: &Option<StrategyConfig>| OptionalRsi(config.map(RSIStrategy::new)))
+            .starting_cash(10_000.0)
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .rank_by(RankBy::SharpeRatio)
+            .threads(2)
+            .build()
+            .unwrap()
+            .run()
+            .unwrap();
+
+        assert!(results
+            .runs
+            .iter()
+            .any(|run| run.params.strategy.is_none() && run.summary.pnl_returns.total.count == 0));
+
+        let mut json = Vec::new();
+        results.write_json(&mut json).unwrap();
+        let parsed = serde_json::from_slice::<
+            GridSearchResults<Option<StrategyConfig>, DefaultAllocator, DefaultRisk>,
+        >(&json)
+        .unwrap();
+        assert_eq!(parsed.rank_by, RankBy::SharpeRatio);
+        assert_eq!(parsed.runs.len(), results.runs.len());
+        for (parsed, run) in parsed.runs.iter().zip(&results.runs) {
+            assert_eq!(parsed.rank, run.rank);
+            assert_eq!(parsed.params, run.params);
+            assert_eq!(parsed.score.is_some(), run.score.is_some());
+            assert!(parsed
+                .score
+                .zip(run.score)
+                .is_none_or(|(parsed, score)| (parsed - score).abs() < 1e-9));
+            assert_eq!(
+                parsed.summary.pnl_returns.total.count,
+                run.summary.pnl_returns.total.count
+            );
+        }
+    }
+
+    #[test]
+    fn non_finite_scores_are_none() {
+        let mut summary = TradingSummary::init(StatisticConfig {
+            starting_equity: 10_000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        });
+        summary.tear_sheet.sharpe_ratio.trades_per_day = f64::NAN;
+
+        assert_eq!(RankBy::SharpeRatio.score(&summary), None);
+        assert_eq!(RankBy::TotalReturn.score(&summary), Some(0.0));
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+
+/// Source of the current time for the [`Engine`](crate::engine::Engine), Portfolio &
+/// [`ExecutionClient`](crate::execution::ExecutionClient) components.
+///
+/// Live trading uses the [`LiveClock`] wall-clock, whereas backtests use a [`HistoricalClock`]
+/// driven by the `exchange_time` of each [`MarketEvent`](barter_data::event::MarketEvent), so
+/// fills, [`Position`](crate::portfolio::position::Position) durations & statistics reflect
+/// market time.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn time(&self) -> DateTime<Utc>;
+
+    /// Advances the clock to the `exchange_time` of the
+    /// [`MarketEvent`](barter_data::event::MarketEvent) being processed. No-op by default.
+    fn advance(&self, _market_time: DateTime<Utc>) {}
+}
+
+/// Wall-clock [`Clock`] for live & dry trading.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct LiveClock;
+
+impl Clock for LiveClock {
+    fn time(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Backtest [`Clock`] driven by the `exchange_time` of the
+/// [`MarketEvent`](barter_data::event::MarketEvent)s processed. Never moves backwards, so it can
+/// be shared between [`Trader`](crate::engine::trader::Trader)s.
+///
+/// Defaults to the Unix epoch until the first [`MarketEvent`](barter_data::event::MarketEvent)
+/// is processed.
+#[derive(Debug, Default)]
+pub struct HistoricalClock {
+    nanos: AtomicI64,
+}
+
+impl HistoricalClock {
+    /// Constructs a new [`HistoricalClock`] starting at the provided time.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            nanos: AtomicI64::new(start.timestamp_nanos_opt().unwrap_or_default()),
+        }
+    }
+}
+
+impl Clock for HistoricalClock {
+    fn time(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_nanos(self.nanos.load(Ordering::Acquire))
+    }
+
+    fn advance(&self, market_time: DateTime<Utc>) {
+        if let Some(nanos) = market_time.timestamp_nanos_opt() {
+            self.nanos.fetch_max(nanos, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Cheaply cloneable handle to a [`Clock`] shared between components. Defaults to a
+/// [`LiveClock`].
+///
+/// Handles are equal if they share the same underlying [`Clock`].
+#[derive(Clone, Debug)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    /// Constructs a new [`SharedClock`] handle to the provided [`Clock`].
+    pub fn new<C: Clock + 'static>(clock: C) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    /// Constructs a new [`SharedClock`] handle to a [`HistoricalClock`] starting at the Unix
+    /// epoch.
+    pub fn historical() -> Self {
+        Self::new(HistoricalClock::default())
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self::new(LiveClock)
+    }
+}
+
+impl PartialEq for SharedClock {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Clock for SharedClock {
+    fn time(&self) -> DateTime<Utc> {
+        self.0.time()
+    }
+
+    fn advance(&self, market_time: DateTime<Utc>) {
+        self.0.advance(market_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn historical_clock_only_advances_forwards_across_handles() {
+        let start = Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap();
+        let clock = SharedClock::new(HistoricalClock::new(start));
+        let other = clock.clone();
+        assert_eq!(clock, other);
+        assert_ne!(clock, SharedClock::historical());
+
+        other.advance(start + Duration::minutes(1));
+        assert_eq!(clock.time(), start + Duration::minutes(1));
+
+        clock.advance(start);
+        assert_eq!(other.time(), start + Duration::minutes(1));
+    }
+}
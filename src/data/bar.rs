@@ -0,0 +1,463 @@
+use crate::data::{AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Determines when a [`BarBuilder`] completes a bar.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum BarKind {
+    /// Bar spanning a fixed interval, aligned to multiples of the interval since the Unix epoch
+    /// (eg/ 5m bars close at :00, :05, :10, etc).
+    Time(Duration),
+    /// Bar completed after the provided number of trades.
+    Tick(u64),
+    /// Bar completed once the traded amount reaches the provided volume.
+    Volume(f64),
+    /// Bar completed once the traded value (price * amount) reaches the provided value.
+    Dollar(f64),
+}
+
+/// [`MarketGenerator`] adapter that aggregates the [`DataKind::Trade`] [`MarketEvent`]s of a
+/// single market into [`DataKind::Candle`] bars, so candle strategies (eg/
+/// [`RSIStrategy`](crate::strategy::example::RSIStrategy)) can consume live trade feeds
+/// unchanged.
+///
+/// A [`BarKind::Time`] bar is yielded once the first trade of the next interval arrives, with a
+/// close_time at the end of it's interval. Every other [`BarKind`] is yielded with the trade
+/// that completes it, with a close_time of that trade. Non-trade [`MarketEvent`]s are discarded,
+/// as is any incomplete bar once the underlying [`Feed`] is finished.
+#[derive(Debug)]
+pub struct BarBuilder<Data> {
+    data: Data,
+    kind: BarKind,
+    bar: Option<Bar>,
+}
+
+impl<Data> BarBuilder<Data> {
+    /// Constructs a new [`BarBuilder`] aggregating the trades yielded by the provided market data
+    /// handler into bars of the provided [`BarKind`].
+    pub fn new(data: Data, kind: BarKind) -> Self {
+        Self {
+            data,
+            kind,
+            bar: None,
+        }
+    }
+
+    /// Aggregates the [`MarketEvent`], returning the bar it completes (if any).
+    fn aggregate(&mut self, event: MarketEvent<DataKind>) -> Option<MarketEvent<DataKind>> {
+        let trade = match &event.kind {
+            DataKind::Trade(trade) => trade,
+            _ => return None,
+        };
+
+        if let BarKind::Time(interval) = self.kind {
+            let bucket = bucket(event.exchange_time, interval);
+            let completed = match &self.bar {
+                Some(bar) if bar.bucket != bucket => self.bar.take(),
+                _ => None,
+            };
+            match &mut self.bar {
+                Some(bar) => bar.add_trade(&event, trade),
+                None => self.bar = Some(Bar::from_trade(&event, trade, bucket)),
+            }
+            return completed.map(|bar| {
+                let close_time = bucket_start(bar.bucket + 1, interval);
+                bar.into_event(close_time)
+            });
+        }
+
+        let bar = match &mut self.bar {
+            Some(bar) => {
+                bar.add_trade(&event, trade);
+                bar
+            }
+            None => self.bar.insert(Bar::from_trade(&event, trade, 0)),
+        };
+
+        let complete = match self.kind {
+            BarKind::Tick(ticks) => bar.candle.trade_count >= ticks,
+            BarKind::Volume(volume) => bar.candle.volume >= volume,
+            BarKind::Dollar(value) => bar.value >= value,
+            BarKind::Time(_) => unreachable!("time bars are completed by interval"),
+        };
+
+        complete.then(|| self.bar.take()).flatten().map(|bar| {
+            let close_time = bar.candle.close_time;
+            bar.into_event(close_time)
+        })
+    }
+}
+
+impl<Data> MarketGenerator<MarketEvent<DataKind>> for BarBuilder<Data>
+where
+    Data: MarketGenerator<MarketEvent<DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<DataKind>> {
+        loop {
+            let feed = self.data.next();
+            if let Some(feed) = next_bar(feed, |event| self.aggregate(event)) {
+                break feed;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<Data> AsyncMarketGenerator<MarketEvent<DataKind>> for BarBuilder<Data>
+where
+    Data: AsyncMarketGenerator<MarketEvent<DataKind>> + Send,
+{
+    async fn next_async(&mut self) -> Feed<MarketEvent<DataKind>> {
+        loop {
+            let feed = self.data.next_async().await;
+            if let Some(feed) = next_bar(feed, |event| self.aggregate(event)) {
+                break feed;
+            }
+        }
+    }
+}
+
+/// [`MarketGenerator`] adapter that resamples the [`DataKind::Candle`] [`MarketEvent`]s of a
+/// single market into coarser candles (eg/ 1h candles into 4h candles), aligned to multiples of
+/// the interval since the Unix epoch.
+///
+/// A resampled candle is yielded once the first candle of the next interval arrives, with the
+/// close_time of the last candle it contains. Non-candle [`MarketEvent`]s are discarded, as is
+/// any incomplete candle once the underlying [`Feed`] is finished.
+#[derive(Debug)]
+pub struct CandleResampler<Data> {
+    data: Data,
+    interval: Duration,
+    bar: Option<Bar>,
+}
+
+impl<Data> CandleResampler<Data> {
+    /// Constructs a new [`CandleResampler`] resampling the candles yielded by the provided market
+    /// data handler into candles of the provided interval.
+    pub fn new(data: Data, interval: Duration) -> Self {
+        Self {
+            data,
+            interval,
+            bar: None,
+        }
+    }
+
+    /// Aggregates the [`MarketEvent`], returning the resampled candle it completes (if any).
+    fn aggregate(&mut self, event: MarketEvent<DataKind>) -> Option<MarketEvent<DataKind>> {
+        let candle = match &event.kind {
+            DataKind::Candle(candle) => candle,
+            _ => return None,
+        };
+
+        // Candles closing exactly on an interval boundary belong to the interval they close
+        let bucket = bucket(
+            candle.close_time - chrono::Duration::nanoseconds(1),
+            self.interval,
+        );
+        let completed = match &self.bar {
+            Some(bar) if bar.bucket != bucket => self.bar.take(),
+            _ => None,
+        };
+        match &mut self.bar {
+            Some(bar) => bar.add_candle(&event, candle),
+            None => self.bar = Some(Bar::from_candle(&event, candle, bucket)),
+        }
+
+        completed.map(|bar| {
+            let close_time = bar.candle.close_time;
+            bar.into_event(close_time)
+        })
+    }
+}
+
+impl<Data> MarketGenerator<MarketEvent<DataKind>> for CandleResampler<Data>
+where
+    Data: MarketGenerator<MarketEvent<DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<DataKind>> {
+        loop {
+            let feed = self.data.next();
+            if let Some(feed) = next_bar(feed, |event| self.aggregate(event)) {
+                break feed;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<Data> AsyncMarketGenerator<MarketEvent<DataKind>> for CandleResampler<Data>
+where
+    Data: AsyncMarketGenerator<MarketEvent<DataKind>> + Send,
+{
+    async fn next_async(&mut self) -> Feed<MarketEvent<DataKind>> {
+        loop {
+            let feed = self.data.next_async().await;
+            if let Some(feed) = next_bar(feed, |event| self.aggregate(event)) {
+                break feed;
+            }
+        }
+    }
+}
+
+/// Handles the next [`Feed`] of the underlying market data handler, returning the [`Feed`] to
+/// yield, or None if another [`MarketEvent`] is required to complete the next bar.
+fn next_bar<Aggregate>(
+    feed: Feed<MarketEvent<DataKind>>,
+    aggregate: Aggregate,
+) -> Option<Feed<MarketEvent<DataKind>>>
+where
+    Aggregate: FnOnce(MarketEvent<DataKind>) -> Option<MarketEvent<DataKind>>,
+{
+    match feed {
+        Feed::Next(event) => aggregate(event).map(Feed::Next),
+        Feed::Unhealthy => Some(Feed::Unhealthy),
+        Feed::Finished => Some(Feed::Finished),
+    }
+}
+
+/// Bar in the process of being aggregated.
+#[derive(Clone, PartialEq, Debug)]
+struct Bar {
+    exchange: Exchange,
+    instrument: Instrument,
+    received_time: DateTime<Utc>,
+    /// Interval since the Unix epoch the bar belongs to, if aggregating by time.
+    bucket: i64,
+    /// Traded value (price * amount) aggregated so far.
+    value: f64,
+    candle: Candle,
+}
+
+impl Bar {
+    fn from_trade(event: &MarketEvent<DataKind>, trade: &PublicTrade, bucket: i64) -> Self {
+        Self {
+            exchange: event.exchange.clone(),
+            instrument: event.instrument.clone(),
+            received_time: event.received_time,
+            bucket,
+            value: trade.price * trade.amount,
+            candle: Candle {
+                close_time: event.exchange_time,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: trade.amount,
+                trade_count: 1,
+            },
+        }
+    }
+
+    fn add_trade(&mut self, event: &MarketEvent<DataKind>, trade: &PublicTrade) {
+        self.received_time = event.received_time;
+        self.value += trade.price * trade.amount;
+        self.candle.close_time = event.exchange_time;
+        self.candle.high = self.candle.high.max(trade.price);
+        self.candle.low = self.candle.low.min(trade.price);
+        self.candle.close = trade.price;
+        self.candle.volume += trade.amount;
+        self.candle.trade_count += 1;
+    }
+
+    fn from_candle(event: &MarketEvent<DataKind>, candle: &Candle, bucket: i64) -> Self {
+        Self {
+            exchange: event.exchange.clone(),
+            instrument: event.instrument.clone(),
+            received_time: event.received_time,
+            bucket,
+            value: 0.0,
+            candle: *candle,
+        }
+    }
+
+    fn add_candle(&mut self, event: &MarketEvent<DataKind>, candle: &Candle) {
+        self.received_time = event.received_time;
+        self.candle.close_time = candle.close_time;
+        self.candle.high = self.candle.high.max(candle.high);
+        self.candle.low = self.candle.low.min(candle.low);
+        self.candle.close = candle.close;
+        self.candle.volume += candle.volume;
+        self.candle.trade_count += candle.trade_count;
+    }
+
+    fn into_event(self, close_time: DateTime<Utc>) -> MarketEvent<DataKind> {
+        MarketEvent {
+            exchange_time: close_time,
+            received_time: self.received_time,
+            exchange: self.exchange,
+            instrument: self.instrument,
+            kind: DataKind::Candle(Candle {
+                close_time,
+                ..self.candle
+            }),
+        }
+    }
+}
+
+/// Number of whole intervals between the Unix epoch & the provided time.
+pub(crate) fn bucket(time: DateTime<Utc>, interval: Duration) -> i64 {
+    time.timestamp_nanos_opt()
+        .unwrap_or_default()
+        .div_euclid(interval_nanos(interval))
+}
+
+/// Start time of the provided bucket.
+pub(crate) fn bucket_start(bucket: i64, interval: Duration) -> DateTime<Utc> {
+    DateTime::from_timestamp_nanos(bucket.saturating_mul(interval_nanos(interval)))
+}
+
+fn interval_nanos(interval: Duration) -> i64 {
+    i64::try_from(interval.as_nanos())
+        .unwrap_or(i64::MAX)
+        .max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::historical,
+        test_util::{market_event_candle, market_event_trade},
+    };
+    use barter_integration::model::Side;
+    use chrono::TimeZone;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap()
+    }
+
+    fn trade(seconds: i64, price: f64, amount: f64) -> MarketEvent<DataKind> {
+        let mut event = market_event_trade(Side::Buy);
+        event.exchange_time = start() + chrono::Duration::seconds(seconds);
+        if let DataKind::Trade(trade) = &mut event.kind {
+            trade.price = price;
+            trade.amount = amount;
+        }
+        event
+    }
+
+    fn candles<Data: MarketGenerator<MarketEvent<DataKind>>>(mut data: Data) -> Vec<Candle> {
+        std::iter::from_fn(|| match data.next() {
+            Feed::Next(MarketEvent {
+                kind: DataKind::Candle(candle),
+                ..
+            }) => Some(candle),
+            _ => None,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn should_aggregate_trades_into_time_bars_aligned_to_the_interval() {
+        let trades = [
+            trade(10, 100.0, 1.0),
+            trade(70, 105.0, 2.0),
+            trade(299, 95.0, 1.0),
+            trade(300, 101.0, 1.0),
+            trade(900, 110.0, 1.0),
+        ];
+        let bars = candles(BarBuilder::new(
+            historical::MarketFeed::new(trades),
+            BarKind::Time(Duration::from_secs(300)),
+        ));
+
+        // Bar containing the trade at 900s is incomplete, so is discarded
+        assert_eq!(
+            bars,
+            vec![
+                Candle {
+                    close_time: start() + chrono::Duration::minutes(5),
+                    open: 100.0,
+                    high: 105.0,
+                    low: 95.0,
+                    close: 95.0,
+                    volume: 4.0,
+                    trade_count: 3,
+                },
+                Candle {
+                    close_time: start() + chrono::Duration::minutes(10),
+                    open: 101.0,
+                    high: 101.0,
+                    low: 101.0,
+                    close: 101.0,
+                    volume: 1.0,
+                    trade_count: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_complete_tick_volume_and_dollar_bars_with_the_completing_trade() {
+        let trades = || {
+            historical::MarketFeed::new([
+                trade(1, 10.0, 1.0),
+                trade(2, 20.0, 2.0),
+                trade(3, 30.0, 3.0),
+                trade(4, 40.0, 4.0),
+            ])
+        };
+
+        let closes = |kind| {
+            candles(BarBuilder::new(trades(), kind))
+                .into_iter()
+                .map(|candle| (candle.close, candle.trade_count))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(closes(BarKind::Tick(2)), vec![(20.0, 2), (40.0, 2)]);
+        assert_eq!(
+            closes(BarKind::Volume(3.0)),
+            vec![(20.0, 2), (30.0, 1), (40.0, 1)]
+        );
+        assert_eq!(closes(BarKind::Dollar(100.0)), vec![(30.0, 3), (40.0, 1)]);
+    }
+
+    #[test]
+    fn should_resample_candles_into_coarser_intervals() {
+        let candle = |minutes: i64, close: f64| {
+            let mut event = market_event_candle();
+            if let DataKind::Candle(candle) = &mut event.kind {
+                candle.close_time = start() + chrono::Duration::minutes(minutes);
+                candle.open = close - 1.0;
+                candle.high = close + 1.0;
+                candle.low = close - 2.0;
+                candle.close = close;
+                candle.volume = 10.0;
+                candle.trade_count = 5;
+            }
+            event
+        };
+
+        let resampled = candles(CandleResampler::new(
+            historical::MarketFeed::new([
+                candle(60, 100.0),
+                candle(120, 110.0),
+                candle(180, 90.0),
+                candle(240, 95.0),
+            ]),
+            Duration::from_secs(2 * 60 * 60),
+        ));
+
+        // 21:00 & 22:00 candles close within the 20:00-22:00 interval
+        assert_eq!(
+            resampled,
+            vec![Candle {
+                close_time: start() + chrono::Duration::minutes(120),
+                open: 99.0,
+                high: 111.0,
+                low: 98.0,
+                close: 110.0,
+                volume: 20.0,
+                trade_count: 10,
+            }]
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use crate::data::{Feed, MarketGenerator};
+use crate::data::{AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
+
+/// Time-synchronised replay of many historical markets, yielding [`MarketEvent`](barter_data::event::MarketEvent)s
+/// to each [`Trader`](crate::engine::trader::Trader) in global time order.
+pub mod sync;
 
 /// Historical [`Feed`] of market events.
 #[derive(Debug)]
@@ -20,6 +25,17 @@ where
     }
 }
 
+#[async_trait]
+impl<Iter, Event> AsyncMarketGenerator<Event> for MarketFeed<Iter, Event>
+where
+    Iter: Iterator<Item = Event> + Send,
+    Event: Send,
+{
+    async fn next_async(&mut self) -> Feed<Event> {
+        MarketGenerator::next(self)
+    }
+}
+
 impl<Iter, Event> MarketFeed<Iter, Event>
 where
     Iter: Iterator<Item = Event>,
@@ -0,0 +1,275 @@
+use crate::data::{Feed, MarketGenerator};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::Market;
+use parking_lot::{Condvar, Mutex};
+use std::sync::Arc;
+
+/// Boxed historical source of [`MarketEvent`]s for a single [`Market`].
+type Source = Box<dyn Iterator<Item = MarketEvent<DataKind>> + Send>;
+
+/// Time-synchronised historical replay of many [`Market`]s.
+///
+/// Merges several per-market sources (each ordered by `exchange_time`) & hands out one
+/// [`SynchronisedFeed`] per source, each used as the [`MarketGenerator`] of the
+/// [`Trader`](crate::engine::trader::Trader) of that [`Market`]. Every [`MarketEvent`] is only
+/// yielded once the [`Trader`](crate::engine::trader::Trader) that received the previous
+/// [`MarketEvent`] in global time order has finished processing it (ie/ has requested it's next
+/// [`MarketEvent`]). Backtests of spread & portfolio strategies therefore see a consistent
+/// clock, and the shared Portfolio is updated in causal order.
+///
+/// [`MarketEvent`]s with equal `exchange_time`s are yielded in the order their sources were
+/// added. Dropping a [`SynchronisedFeed`] (eg/ when it's
+/// [`Trader`](crate::engine::trader::Trader) stops) discards the remainder of it's source so the
+/// other [`Market`]s can continue.
+///
+/// Since waiting for it's turn blocks the calling thread, [`SynchronisedFeed`]s must be used with
+/// [`Engine::run`](crate::engine::Engine::run) rather than
+/// [`Engine::run_async`](crate::engine::Engine::run_async).
+pub struct SynchronisedReplay {
+    sources: Vec<(Market, Source)>,
+}
+
+impl std::fmt::Debug for SynchronisedReplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SynchronisedReplay")
+            .field(
+                "markets",
+                &self
+                    .sources
+                    .iter()
+                    .map(|(market, _)| market)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Default for SynchronisedReplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SynchronisedReplay {
+    /// Constructs a new [`SynchronisedReplay`] without any sources.
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    /// Adds the historical [`MarketEvent`]s of a [`Market`], ordered by `exchange_time`.
+    pub fn source<Events>(mut self, market: Market, events: Events) -> Self
+    where
+        Events: IntoIterator<Item = MarketEvent<DataKind>>,
+        Events::IntoIter: Send + 'static,
+    {
+        self.sources.push((market, Box::new(events.into_iter())));
+        self
+    }
+
+    /// Constructs the [`SynchronisedFeed`] of every source, in the order they were added.
+    pub fn into_feeds(self) -> Vec<(Market, SynchronisedFeed)> {
+        let (markets, sources): (Vec<_>, Vec<_>) = self.sources.into_iter().unzip();
+
+        let mut cursor = ReplayCursor {
+            heads: Vec::with_capacity(sources.len()),
+            sources,
+            turn: None,
+        };
+        for index in 0..cursor.sources.len() {
+            let head = cursor.sources[index].next();
+            cursor.heads.push(head);
+        }
+
+        let shared = Arc::new(Shared {
+            cursor: Mutex::new(cursor),
+            turn_changed: Condvar::new(),
+        });
+
+        markets
+            .into_iter()
+            .enumerate()
+            .map(|(source, market)| {
+                (
+                    market,
+                    SynchronisedFeed {
+                        source,
+                        shared: Arc::clone(&shared),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// State shared between the [`SynchronisedFeed`]s of a [`SynchronisedReplay`].
+struct Shared {
+    cursor: Mutex<ReplayCursor>,
+    turn_changed: Condvar,
+}
+
+/// Global replay position of a [`SynchronisedReplay`], shared by every [`SynchronisedFeed`].
+struct ReplayCursor {
+    sources: Vec<Source>,
+    /// Next [`MarketEvent`] of each source, or None if the source is exhausted or detached.
+    heads: Vec<Option<MarketEvent<DataKind>>>,
+    /// Source whose latest [`MarketEvent`] is still being processed.
+    turn: Option<usize>,
+}
+
+impl ReplayCursor {
+    /// Source holding the earliest pending [`MarketEvent`], with ties resolved by source order.
+    fn earliest(&self) -> Option<usize> {
+        self.heads
+            .iter()
+            .enumerate()
+            .filter_map(|(source, head)| head.as_ref().map(|event| (event.exchange_time, source)))
+            .min()
+            .map(|(_, source)| source)
+    }
+}
+
+/// [`MarketGenerator`] of a single [`Market`] in a [`SynchronisedReplay`].
+pub struct SynchronisedFeed {
+    source: usize,
+    shared: Arc<Shared>,
+}
+
+impl std::fmt::Debug for SynchronisedFeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SynchronisedFeed")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl MarketGenerator<MarketEvent<DataKind>> for SynchronisedFeed {
+    fn next(&mut self) -> Feed<MarketEvent<DataKind>> {
+        let mut cursor = self.shared.cursor.lock();
+
+        // Requesting the next MarketEvent signals the previous one has been fully processed
+        if cursor.turn == Some(self.source) {
+            cursor.turn = None;
+            self.shared.turn_changed.notify_all();
+        }
+
+        loop {
+            if cursor.heads[self.source].is_none() {
+                return Feed::Finished;
+            }
+
+            if cursor.turn.is_none() && cursor.earliest() == Some(self.source) {
+                let next = cursor.sources[self.source].next();
+                let event = std::mem::replace(&mut cursor.heads[self.source], next);
+                cursor.turn = Some(self.source);
+                return event.map_or(Feed::Finished, Feed::Next);
+            }
+
+            self.shared.turn_changed.wait(&mut cursor);
+        }
+    }
+}
+
+impl Drop for SynchronisedFeed {
+    fn drop(&mut self) {
+        let mut cursor = self.shared.cursor.lock();
+        cursor.heads[self.source] = None;
+        if cursor.turn == Some(self.source) {
+            cursor.turn = None;
+        }
+        self.shared.turn_changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_trade;
+    use barter_integration::model::{InstrumentKind, Side};
+    use chrono::{Duration, TimeZone, Utc};
+    use std::thread;
+
+    fn events(market: &Market, seconds: &[i64]) -> Vec<MarketEvent<DataKind>> {
+        let start = Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap();
+        seconds
+            .iter()
+            .map(|second| MarketEvent {
+                exchange_time: start + Duration::seconds(*second),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                ..market_event_trade(Side::Buy)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_yield_events_across_threads_in_global_time_order() {
+        let btc = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let eth = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let feeds = SynchronisedReplay::new()
+            .source(btc.clone(), events(&btc, &[0, 3, 4, 9]))
+            .source(eth.clone(), events(&eth, &[1, 2, 4, 5]))
+            .into_feeds();
+
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let handles = feeds
+            .into_iter()
+            .map(|(market, mut feed)| {
+                let processed = Arc::clone(&processed);
+                thread::spawn(move || {
+                    while let Feed::Next(event) = feed.next() {
+                        // Simulate processing taking longer for the earlier source
+                        if market.instrument.base.to_string() == "btc" {
+                            thread::sleep(std::time::Duration::from_millis(5));
+                        }
+                        processed.lock().push((
+                            event.exchange_time.timestamp() % 60,
+                            market.instrument.base.to_string(),
+                        ));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .for_each(|handle| handle.join().unwrap());
+
+        let expected = [
+            (0, "btc"),
+            (1, "eth"),
+            (2, "eth"),
+            (3, "btc"),
+            (4, "btc"),
+            (4, "eth"),
+            (5, "eth"),
+            (9, "btc"),
+        ]
+        .map(|(second, base)| (second, base.to_owned()));
+        assert_eq!(*processed.lock(), expected);
+    }
+
+    #[test]
+    fn should_continue_remaining_markets_once_a_feed_is_dropped() {
+        let btc = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let eth = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let mut feeds = SynchronisedReplay::new()
+            .source(btc.clone(), events(&btc, &[0, 2]))
+            .source(eth.clone(), events(&eth, &[1, 3]))
+            .into_feeds();
+
+        let (_, mut eth_feed) = feeds.pop().unwrap();
+        let (_, mut btc_feed) = feeds.pop().unwrap();
+
+        assert!(matches!(btc_feed.next(), Feed::Next(event) if event.instrument == btc.instrument));
+        drop(btc_feed);
+
+        let remaining = std::iter::from_fn(|| match eth_feed.next() {
+            Feed::Next(event) => Some(event.exchange_time.timestamp() % 60),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(remaining, vec![1, 3]);
+    }
+}
@@ -1,24 +1,70 @@
-use super::{Feed, MarketGenerator};
+use super::{AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
+use std::{
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
 
 /// Live [`Feed`] of market events.
+///
+/// Waiting for the next market event parks the calling thread (or yields to the runtime when
+/// used as an [`AsyncMarketGenerator`]) rather than busy-spinning. If a heartbeat timeout is
+/// configured, [`Feed::Unhealthy`] is yielded whenever no market event arrives within it, giving
+/// the [`Trader`](crate::engine::trader::Trader) an opportunity to action remote
+/// [`Command`](crate::engine::Command)s.
 #[derive(Debug)]
 pub struct MarketFeed<Event> {
     pub market_rx: mpsc::UnboundedReceiver<Event>,
+    pub heartbeat_timeout: Option<Duration>,
 }
 
 impl<Event> MarketGenerator<Event> for MarketFeed<Event> {
     fn next(&mut self) -> Feed<Event> {
+        let deadline = self
+            .heartbeat_timeout
+            .map(|timeout| Instant::now() + timeout);
+
+        // Unpark this thread whenever the market_rx is ready to be polled again
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+
         loop {
-            match self.market_rx.try_recv() {
-                Ok(event) => break Feed::Next(event),
-                Err(mpsc::error::TryRecvError::Empty) => continue,
-                Err(mpsc::error::TryRecvError::Disconnected) => break Feed::Finished,
+            match self.market_rx.poll_recv(&mut context) {
+                Poll::Ready(Some(event)) => break Feed::Next(event),
+                Poll::Ready(None) => break Feed::Finished,
+                Poll::Pending => match deadline {
+                    None => thread::park(),
+                    Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                        Some(remaining) if !remaining.is_zero() => thread::park_timeout(remaining),
+                        _ => break Feed::Unhealthy,
+                    },
+                },
             }
         }
     }
 }
 
+#[async_trait]
+impl<Event> AsyncMarketGenerator<Event> for MarketFeed<Event>
+where
+    Event: Send,
+{
+    async fn next_async(&mut self) -> Feed<Event> {
+        let next = match self.heartbeat_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, self.market_rx.recv()).await {
+                Ok(next) => next,
+                Err(_) => return Feed::Unhealthy,
+            },
+            None => self.market_rx.recv().await,
+        };
+
+        next.map_or(Feed::Finished, Feed::Next)
+    }
+}
+
 impl<Event> MarketFeed<Event> {
     /// Initialises a live [`MarketFeed`] that yields market `Event`s from the provided
     /// [`mpsc::UnboundedReceiver`].
@@ -31,6 +77,77 @@ impl<Event> MarketFeed<Event> {
     ///     [`mpsc::UnboundedReceiver`] streams into a unified [`mpsc::UnboundedReceiver`].
     ///  3. Construct [`Self`] with the unified [`mpsc::UnboundedReceiver`].
     pub fn new(market_rx: mpsc::UnboundedReceiver<Event>) -> Self {
-        Self { market_rx }
+        Self {
+            market_rx,
+            heartbeat_timeout: None,
+        }
+    }
+
+    /// Initialises a live [`MarketFeed`] that yields [`Feed::Unhealthy`] if no market `Event` is
+    /// received from the provided [`mpsc::UnboundedReceiver`] within the heartbeat timeout.
+    pub fn with_heartbeat_timeout(
+        market_rx: mpsc::UnboundedReceiver<Event>,
+        heartbeat_timeout: Duration,
+    ) -> Self {
+        Self {
+            market_rx,
+            heartbeat_timeout: Some(heartbeat_timeout),
+        }
+    }
+}
+
+/// [`Wake`] implementation that unparks the [`Thread`] blocked waiting for the next market event.
+#[derive(Debug)]
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_blocks_until_event_received_from_another_thread() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut feed = MarketFeed::with_heartbeat_timeout(rx, Duration::from_secs(5));
+
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx.send(1).unwrap();
+        });
+
+        assert_eq!(feed.next(), Feed::Next(1));
+        sender.join().unwrap();
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn next_yields_unhealthy_once_heartbeat_timeout_elapses() {
+        let (_tx, rx) = mpsc::unbounded_channel::<u64>();
+        let mut feed = MarketFeed::with_heartbeat_timeout(rx, Duration::from_millis(10));
+
+        let start = Instant::now();
+        assert_eq!(feed.next(), Feed::Unhealthy);
+        assert!(start.elapsed() >= Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn next_async_yields_unhealthy_once_heartbeat_timeout_elapses() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut feed = MarketFeed::with_heartbeat_timeout(rx, Duration::from_millis(10));
+
+        assert_eq!(feed.next_async().await, Feed::Unhealthy);
+        tx.send(1).unwrap();
+        assert_eq!(feed.next_async().await, Feed::Next(1));
+        drop(tx);
+        assert_eq!(feed.next_async().await, Feed::Finished);
     }
 }
@@ -0,0 +1,480 @@
+//! Simple binary columnar format storing records in row groups, so a loader only needs to hold a
+//! single row group in memory at a time.
+//!
+//! Layout (all integers little endian):
+//!  1. Header: [`MAGIC`] bytes, `u32` column count, then for each column a `u16` name length,
+//!     the UTF-8 name & a `u8` [`ColumnType`] tag.
+//!  2. Row groups until EOF: `u32` row count, then for each column every row's value. Floats are
+//!     `f64`, ints are `i64`, times are `i64` nanoseconds since the Unix epoch & text is a `u32`
+//!     byte length followed by the UTF-8 bytes.
+
+use super::{error::LoaderError, CandleColumns, Field, LoaderConfig, Record, TradeColumns};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::Side;
+use chrono::{TimeZone, Utc};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// Magic bytes identifying a columnar file.
+pub const MAGIC: &[u8; 8] = b"BARTCOL1";
+
+/// Kind of [`MarketEvent`] records stored by a [`ColumnarWriter`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RecordKind {
+    Candle,
+    Trade,
+}
+
+impl RecordKind {
+    /// Columns written for the [`RecordKind`], named as per the default [`CandleColumns`] &
+    /// [`TradeColumns`] so the default [`LoaderConfig`]s can read them back.
+    fn columns(&self) -> Vec<(String, ColumnType)> {
+        match self {
+            RecordKind::Candle => {
+                let columns = CandleColumns::default();
+                vec![
+                    (columns.close_time, ColumnType::Time),
+                    (columns.open, ColumnType::Float),
+                    (columns.high, ColumnType::Float),
+                    (columns.low, ColumnType::Float),
+                    (columns.close, ColumnType::Float),
+                    (columns.volume, ColumnType::Float),
+                    (columns.trade_count.unwrap_or_default(), ColumnType::Int),
+                ]
+            }
+            RecordKind::Trade => {
+                let columns = TradeColumns::default();
+                vec![
+                    (columns.time, ColumnType::Time),
+                    (columns.id.unwrap_or_default(), ColumnType::Text),
+                    (columns.price, ColumnType::Float),
+                    (columns.amount, ColumnType::Float),
+                    (columns.side, ColumnType::Text),
+                ]
+            }
+        }
+    }
+
+    /// Values of each column for the provided [`MarketEvent`].
+    fn row(&self, event: &MarketEvent<DataKind>) -> Result<Vec<Field>, LoaderError> {
+        match (self, &event.kind) {
+            (RecordKind::Candle, DataKind::Candle(candle)) => Ok(vec![
+                Field::Time(candle.close_time),
+                Field::Float(candle.open),
+                Field::Float(candle.high),
+                Field::Float(candle.low),
+                Field::Float(candle.close),
+                Field::Float(candle.volume),
+                Field::Int(candle.trade_count as i64),
+            ]),
+            (RecordKind::Trade, DataKind::Trade(trade)) => Ok(vec![
+                Field::Time(event.exchange_time),
+                Field::Text(trade.id.clone()),
+                Field::Float(trade.price),
+                Field::Float(trade.amount),
+                Field::Text(
+                    match trade.side {
+                        Side::Buy => "buy",
+                        Side::Sell => "sell",
+                    }
+                    .to_owned(),
+                ),
+            ]),
+            (kind, data) => Err(LoaderError::InvalidColumnar(format!(
+                "cannot write {data:?} to {kind:?} columnar file"
+            ))),
+        }
+    }
+}
+
+/// Type of the values stored in a column.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum ColumnType {
+    Float = 0,
+    Int = 1,
+    Text = 2,
+    Time = 3,
+}
+
+impl TryFrom<u8> for ColumnType {
+    type Error = LoaderError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        match tag {
+            0 => Ok(ColumnType::Float),
+            1 => Ok(ColumnType::Int),
+            2 => Ok(ColumnType::Text),
+            3 => Ok(ColumnType::Time),
+            other => Err(LoaderError::InvalidColumnar(format!(
+                "unknown column type tag {other}"
+            ))),
+        }
+    }
+}
+
+/// Writes [`MarketEvent`]s to the columnar format, buffering a row group in memory before
+/// flushing it to the underlying writer. Useful for converting CSV & JSON lines files into a
+/// more compact format that is faster to load.
+#[derive(Debug)]
+pub struct ColumnarWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    kind: RecordKind,
+    row_group_size: usize,
+    rows: Vec<Vec<Field>>,
+}
+
+impl<W> ColumnarWriter<W>
+where
+    W: Write,
+{
+    /// Constructs a new [`ColumnarWriter`], writing the header for the [`RecordKind`] to the
+    /// provided writer.
+    pub fn new(
+        mut writer: W,
+        kind: RecordKind,
+        row_group_size: usize,
+    ) -> Result<Self, LoaderError> {
+        let columns = kind.columns();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(columns.len() as u32).to_le_bytes())?;
+        for (name, column_type) in columns {
+            writer.write_all(&(name.len() as u16).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&[column_type as u8])?;
+        }
+
+        Ok(Self {
+            writer,
+            kind,
+            row_group_size: row_group_size.max(1),
+            rows: Vec::with_capacity(row_group_size),
+        })
+    }
+
+    /// Appends the [`MarketEvent`], flushing the row group if it is full.
+    pub fn append(&mut self, event: &MarketEvent<DataKind>) -> Result<(), LoaderError> {
+        self.rows.push(self.kind.row(event)?);
+        if self.rows.len() >= self.row_group_size {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Flushes any buffered rows & returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, LoaderError> {
+        self.write_row_group()?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_row_group(&mut self) -> Result<(), LoaderError> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+
+        self.writer
+            .write_all(&(self.rows.len() as u32).to_le_bytes())?;
+        for column in 0..self.rows[0].len() {
+            for row in &self.rows {
+                match &row[column] {
+                    Field::Float(value) => self.writer.write_all(&value.to_le_bytes())?,
+                    Field::Int(value) => self.writer.write_all(&value.to_le_bytes())?,
+                    Field::Time(time) => {
+                        let nanos = time.timestamp_nanos_opt().ok_or_else(|| {
+                            LoaderError::InvalidColumnar(format!("{time} is out of range"))
+                        })?;
+                        self.writer.write_all(&nanos.to_le_bytes())?
+                    }
+                    Field::Text(text) => {
+                        self.writer.write_all(&(text.len() as u32).to_le_bytes())?;
+                        self.writer.write_all(text.as_bytes())?
+                    }
+                }
+            }
+        }
+
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+/// Lazily loads [`MarketEvent<DataKind>`]s from a columnar file, reading one row group at a time.
+#[derive(Debug)]
+pub struct ColumnarLoader<R>
+where
+    R: BufRead,
+{
+    config: LoaderConfig,
+    reader: R,
+    columns: Vec<(String, ColumnType)>,
+    /// Values of the current row group, stored per column.
+    group: Vec<Vec<Field>>,
+    row: usize,
+    /// Set once an error has been encountered, since the remaining bytes cannot be trusted.
+    finished: bool,
+}
+
+impl ColumnarLoader<BufReader<File>> {
+    /// Opens the columnar file at the provided path.
+    pub fn open<P: AsRef<Path>>(path: P, config: LoaderConfig) -> Result<Self, LoaderError> {
+        Self::new(BufReader::new(File::open(path)?), config)
+    }
+}
+
+impl<R> ColumnarLoader<R>
+where
+    R: BufRead,
+{
+    /// Constructs a new [`ColumnarLoader`], reading the header from the provided reader.
+    pub fn new(mut reader: R, config: LoaderConfig) -> Result<Self, LoaderError> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(LoaderError::InvalidColumnar(
+                "missing columnar magic bytes".to_owned(),
+            ));
+        }
+
+        let columns = (0..read_u32(&mut reader)?)
+            .map(|_| {
+                let mut length = [0; 2];
+                reader.read_exact(&mut length)?;
+                let name = read_bytes(&mut reader, u16::from_le_bytes(length) as usize)?;
+                let mut tag = [0; 1];
+                reader.read_exact(&mut tag)?;
+                Ok((utf8(name)?, ColumnType::try_from(tag[0])?))
+            })
+            .collect::<Result<Vec<_>, LoaderError>>()?;
+
+        Ok(Self {
+            config,
+            reader,
+            columns,
+            group: Vec::new(),
+            row: 0,
+            finished: false,
+        })
+    }
+
+    /// Reads the next row group. A file ending exactly on a row group boundary is finished,
+    /// whereas a truncated row group yields an error.
+    fn next_row_group(&mut self) -> Option<Result<(), LoaderError>> {
+        match self.reader.fill_buf() {
+            Ok([]) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error.into())),
+        }
+
+        Some(self.read_row_group())
+    }
+
+    fn read_row_group(&mut self) -> Result<(), LoaderError> {
+        let rows = read_u32(&mut self.reader)? as usize;
+
+        self.group = self
+            .columns
+            .iter()
+            .map(|(_, column_type)| {
+                (0..rows)
+                    .map(|_| read_value(&mut self.reader, *column_type))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.row = 0;
+
+        Ok(())
+    }
+}
+
+impl<R> Iterator for ColumnarLoader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<MarketEvent<DataKind>, LoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished && self.group.first().map_or(0, Vec::len) <= self.row {
+            match self.next_row_group()? {
+                Ok(()) => {}
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        if self.finished {
+            return None;
+        }
+
+        let event = self.config.parse(&ColumnarRow {
+            columns: &self.columns,
+            group: &self.group,
+            row: self.row,
+        });
+        self.row += 1;
+
+        Some(event)
+    }
+}
+
+/// Row of a columnar row group with it's fields looked up by column name.
+struct ColumnarRow<'a> {
+    columns: &'a [(String, ColumnType)],
+    group: &'a [Vec<Field>],
+    row: usize,
+}
+
+impl Record for ColumnarRow<'_> {
+    fn field(&self, column: &str) -> Option<Field> {
+        self.columns
+            .iter()
+            .position(|(name, _)| name == column)
+            .map(|index| self.group[index][self.row].clone())
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, LoaderError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Reads the provided number of bytes incrementally, so a corrupt length prefix yields an error
+/// at the end of the data rather than allocating for it up front.
+fn read_bytes<R: Read>(reader: &mut R, length: usize) -> Result<Vec<u8>, LoaderError> {
+    let mut bytes = Vec::new();
+    reader
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut bytes)?;
+    match bytes.len() == length {
+        true => Ok(bytes),
+        false => Err(std::io::Error::from(ErrorKind::UnexpectedEof).into()),
+    }
+}
+
+fn read_value<R: Read>(reader: &mut R, column_type: ColumnType) -> Result<Field, LoaderError> {
+    let mut bytes = [0; 8];
+    match column_type {
+        ColumnType::Float => {
+            reader.read_exact(&mut bytes)?;
+            Ok(Field::Float(f64::from_le_bytes(bytes)))
+        }
+        ColumnType::Int => {
+            reader.read_exact(&mut bytes)?;
+            Ok(Field::Int(i64::from_le_bytes(bytes)))
+        }
+        ColumnType::Time => {
+            reader.read_exact(&mut bytes)?;
+            Ok(Field::Time(Utc.timestamp_nanos(i64::from_le_bytes(bytes))))
+        }
+        ColumnType::Text => {
+            let length = read_u32(reader)? as usize;
+            read_bytes(reader, length).and_then(utf8).map(Field::Text)
+        }
+    }
+}
+
+fn utf8(bytes: Vec<u8>) -> Result<String, LoaderError> {
+    String::from_utf8(bytes).map_err(|error| LoaderError::InvalidColumnar(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{market_event_candle, market_event_trade};
+    use barter_integration::model::InstrumentKind;
+    use std::io::Cursor;
+
+    #[test]
+    fn columnar_events_round_trip_across_row_groups() {
+        let instrument = ("btc", "usdt", InstrumentKind::Spot);
+        let cases = [
+            (
+                RecordKind::Candle,
+                LoaderConfig::candles("binance", instrument),
+                (0..5).map(|_| market_event_candle()).collect::<Vec<_>>(),
+            ),
+            (
+                RecordKind::Trade,
+                LoaderConfig::trades("binance", instrument),
+                vec![
+                    market_event_trade(Side::Buy),
+                    market_event_trade(Side::Sell),
+                ],
+            ),
+        ];
+
+        for (kind, config, mut events) in cases {
+            // Loaded events are timestamped with the record time
+            for event in &mut events {
+                event.exchange = config.exchange.clone();
+                event.instrument = config.instrument.clone();
+                if let DataKind::Candle(candle) = &event.kind {
+                    event.exchange_time = candle.close_time;
+                }
+                event.received_time = event.exchange_time;
+            }
+
+            let mut writer = ColumnarWriter::new(Vec::new(), kind, 2).unwrap();
+            for event in &events {
+                writer.append(event).unwrap();
+            }
+            let bytes = writer.finish().unwrap();
+
+            let loaded = ColumnarLoader::new(Cursor::new(bytes), config)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            assert_eq!(loaded, events, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn truncated_columnar_file_yields_error_after_complete_row_groups() {
+        let mut writer = ColumnarWriter::new(Vec::new(), RecordKind::Candle, 1).unwrap();
+        writer.append(&market_event_candle()).unwrap();
+        writer.append(&market_event_candle()).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        bytes.truncate(bytes.len() - 3);
+
+        let config = LoaderConfig::candles("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let mut loader = ColumnarLoader::new(Cursor::new(bytes), config).unwrap();
+
+        assert!(loader.next().unwrap().is_ok());
+        assert!(matches!(loader.next(), Some(Err(LoaderError::Io(_)))));
+        assert!(loader.next().is_none());
+        assert!(matches!(
+            ColumnarWriter::new(Vec::new(), RecordKind::Trade, 1)
+                .unwrap()
+                .append(&market_event_candle()),
+            Err(LoaderError::InvalidColumnar(_))
+        ));
+    }
+
+    #[test]
+    fn corrupt_text_length_yields_error_without_allocating() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(1_u32.to_le_bytes());
+        bytes.extend(2_u16.to_le_bytes());
+        bytes.extend(b"id");
+        bytes.push(ColumnType::Text as u8);
+        bytes.extend(1_u32.to_le_bytes());
+        bytes.extend(u32::MAX.to_le_bytes());
+        bytes.extend(b"abc");
+
+        let config = LoaderConfig::trades("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let mut loader = ColumnarLoader::new(Cursor::new(bytes), config).unwrap();
+
+        assert!(matches!(loader.next(), Some(Err(LoaderError::Io(_)))));
+        assert!(loader.next().is_none());
+    }
+}
@@ -0,0 +1,173 @@
+use super::{error::LoaderError, Field, LoaderConfig, Record};
+use barter_data::event::{DataKind, MarketEvent};
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+/// Lazily loads [`MarketEvent<DataKind>`]s from a CSV file with a header row, reading one record
+/// at a time so files larger than memory can be streamed.
+#[derive(Debug)]
+pub struct CsvLoader<R>
+where
+    R: Read,
+{
+    config: LoaderConfig,
+    reader: csv::Reader<R>,
+    headers: HashMap<String, usize>,
+    record: csv::StringRecord,
+}
+
+impl CsvLoader<File> {
+    /// Opens the CSV file at the provided path.
+    pub fn open<P: AsRef<Path>>(path: P, config: LoaderConfig) -> Result<Self, LoaderError> {
+        Self::from_csv_reader(csv::Reader::from_path(path)?, config)
+    }
+}
+
+impl<R> CsvLoader<R>
+where
+    R: Read,
+{
+    /// Constructs a new [`CsvLoader`] that reads comma delimited records from the provided reader.
+    pub fn new(reader: R, config: LoaderConfig) -> Result<Self, LoaderError> {
+        Self::from_csv_reader(csv::Reader::from_reader(reader), config)
+    }
+
+    /// Constructs a new [`CsvLoader`] from a pre-configured [`csv::Reader`] (eg/ with a custom
+    /// delimiter). The reader must be configured to read a header row.
+    pub fn from_csv_reader(
+        mut reader: csv::Reader<R>,
+        config: LoaderConfig,
+    ) -> Result<Self, LoaderError> {
+        let headers = reader
+            .headers()?
+            .iter()
+            .enumerate()
+            .map(|(index, header)| (header.trim().to_owned(), index))
+            .collect();
+
+        Ok(Self {
+            config,
+            reader,
+            headers,
+            record: csv::StringRecord::new(),
+        })
+    }
+}
+
+impl<R> Iterator for CsvLoader<R>
+where
+    R: Read,
+{
+    type Item = Result<MarketEvent<DataKind>, LoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_record(&mut self.record) {
+            Ok(false) => None,
+            Ok(true) => Some(self.config.parse(&CsvRecord {
+                headers: &self.headers,
+                record: &self.record,
+            })),
+            Err(error) => Some(Err(error.into())),
+        }
+    }
+}
+
+/// CSV [`Record`] with it's fields looked up by header name.
+struct CsvRecord<'a> {
+    headers: &'a HashMap<String, usize>,
+    record: &'a csv::StringRecord,
+}
+
+impl Record for CsvRecord<'_> {
+    fn field(&self, column: &str) -> Option<Field> {
+        self.headers
+            .get(column)
+            .and_then(|index| self.record.get(*index))
+            .map(|value| Field::Text(value.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::loader::{TimeFormat, TradeColumns, UnixUnit};
+    use barter_data::subscription::trade::PublicTrade;
+    use barter_integration::model::{InstrumentKind, Side};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn csv_trades_are_loaded_using_column_mapping() {
+        let csv = "\
+timestamp,trade_id,px,qty,is_sell
+1649188800000,1,1000.5,0.25,buy
+1649188801000,2,999.0,1.5,sell
+1649188802000,3,not_a_price,1.0,buy
+";
+        let config = LoaderConfig {
+            columns: super::super::Columns::Trade(TradeColumns {
+                time: "timestamp".to_owned(),
+                id: Some("trade_id".to_owned()),
+                price: "px".to_owned(),
+                amount: "qty".to_owned(),
+                side: "is_sell".to_owned(),
+            }),
+            time_format: TimeFormat::Unix(UnixUnit::Millis),
+            ..LoaderConfig::trades("binance", ("btc", "usdt", InstrumentKind::Spot))
+        };
+
+        let mut loader = CsvLoader::new(csv.as_bytes(), config).unwrap();
+
+        let first = loader.next().unwrap().unwrap();
+        assert_eq!(
+            first.exchange_time,
+            Utc.timestamp_millis_opt(1649188800000).unwrap()
+        );
+        assert_eq!(
+            first.kind,
+            DataKind::Trade(PublicTrade {
+                id: "1".to_owned(),
+                price: 1000.5,
+                amount: 0.25,
+                side: Side::Buy,
+            })
+        );
+        assert!(matches!(
+            loader.next().unwrap().unwrap().kind,
+            DataKind::Trade(PublicTrade {
+                side: Side::Sell,
+                ..
+            })
+        ));
+        assert!(matches!(
+            loader.next(),
+            Some(Err(LoaderError::InvalidField(column, _))) if column == "px"
+        ));
+        assert!(loader.next().is_none());
+    }
+
+    #[test]
+    fn csv_nanosecond_timestamps_are_loaded_without_precision_loss() {
+        let csv = "\
+time,id,price,amount,side
+1649188800123456789,1,1000.0,1.0,buy
+1649188800.5,2,1000.0,1.0,sell
+";
+        let config = LoaderConfig {
+            time_format: TimeFormat::Unix(UnixUnit::Nanos),
+            ..LoaderConfig::trades("binance", ("btc", "usdt", InstrumentKind::Spot))
+        };
+
+        let mut loader = CsvLoader::new(csv.as_bytes(), config).unwrap();
+
+        assert_eq!(
+            loader.next().unwrap().unwrap().exchange_time,
+            Utc.timestamp_nanos(1649188800123456789)
+        );
+
+        // Fractional timestamps fall back to floating point scaling
+        assert_eq!(
+            loader.next().unwrap().unwrap().exchange_time,
+            Utc.timestamp_nanos(1649188800)
+        );
+        assert!(loader.next().is_none());
+    }
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+/// All errors generated when loading historical data files.
+#[derive(Error, Debug)]
+pub enum LoaderError {
+    #[error("Failed to read historical data: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse CSV record: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Failed to parse JSON record: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Historical data is missing column: {0}")]
+    MissingColumn(String),
+
+    #[error("Invalid value for column {0}: {1}")]
+    InvalidField(String, String),
+
+    #[error("Invalid columnar historical data: {0}")]
+    InvalidColumnar(String),
+}
@@ -0,0 +1,135 @@
+use super::{error::LoaderError, Field, LoaderConfig, Record};
+use barter_data::event::{DataKind, MarketEvent};
+use serde_json::{Map, Value};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Lazily loads [`MarketEvent<DataKind>`]s from a JSON lines file, where each line is a JSON
+/// object record. Reads one line at a time so files larger than memory can be streamed.
+#[derive(Debug)]
+pub struct JsonLinesLoader<R>
+where
+    R: BufRead,
+{
+    config: LoaderConfig,
+    reader: R,
+    line: String,
+}
+
+impl JsonLinesLoader<BufReader<File>> {
+    /// Opens the JSON lines file at the provided path.
+    pub fn open<P: AsRef<Path>>(path: P, config: LoaderConfig) -> Result<Self, LoaderError> {
+        Ok(Self::new(BufReader::new(File::open(path)?), config))
+    }
+}
+
+impl<R> JsonLinesLoader<R>
+where
+    R: BufRead,
+{
+    /// Constructs a new [`JsonLinesLoader`] that reads JSON object records from the provided
+    /// reader.
+    pub fn new(reader: R, config: LoaderConfig) -> Self {
+        Self {
+            config,
+            reader,
+            line: String::new(),
+        }
+    }
+}
+
+impl<R> Iterator for JsonLinesLoader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<MarketEvent<DataKind>, LoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) if self.line.trim().is_empty() => continue,
+                Ok(_) => {
+                    return Some(
+                        serde_json::from_str::<Map<String, Value>>(&self.line)
+                            .map_err(LoaderError::from)
+                            .and_then(|record| self.config.parse(&record)),
+                    )
+                }
+                Err(error) => return Some(Err(error.into())),
+            }
+        }
+    }
+}
+
+impl Record for Map<String, Value> {
+    fn field(&self, column: &str) -> Option<Field> {
+        match self.get(column)? {
+            Value::Number(number) => Some(
+                number
+                    .as_i64()
+                    .map(Field::Int)
+                    .unwrap_or_else(|| Field::Float(number.as_f64().unwrap_or(f64::NAN))),
+            ),
+            Value::String(text) => Some(Field::Text(text.clone())),
+            Value::Null => None,
+            other => Some(Field::Text(other.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::loader::{CandleColumns, Columns, TimeFormat};
+    use barter_data::subscription::candle::Candle;
+    use barter_integration::model::InstrumentKind;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn json_lines_candles_are_loaded_with_utc_offset() {
+        let json = r#"
+{"t":"2022-04-05 22:00:00","o":1000,"h":1100.5,"l":950,"c":1050,"v":"12.5"}
+
+{"t":"2022-04-05 22:01:00","o":1050,"h":1060,"l":1040,"c":1055,"v":3}
+"#;
+        let config = LoaderConfig {
+            columns: Columns::Candle(CandleColumns {
+                close_time: "t".to_owned(),
+                open: "o".to_owned(),
+                high: "h".to_owned(),
+                low: "l".to_owned(),
+                close: "c".to_owned(),
+                volume: "v".to_owned(),
+                trade_count: None,
+            }),
+            time_format: TimeFormat::Custom("%Y-%m-%d %H:%M:%S".to_owned()),
+            utc_offset_secs: 2 * 3600,
+            ..LoaderConfig::candles("binance", ("btc", "usdt", InstrumentKind::Spot))
+        };
+
+        let events = JsonLinesLoader::new(json.as_bytes(), config)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        let close_time = Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap();
+        assert_eq!(events[0].exchange_time, close_time);
+        assert_eq!(
+            events[0].kind,
+            DataKind::Candle(Candle {
+                close_time,
+                open: 1000.0,
+                high: 1100.5,
+                low: 950.0,
+                close: 1050.0,
+                volume: 12.5,
+                trade_count: 0,
+            })
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use crate::data::loader::error::LoaderError;
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_integration::model::{Exchange, Instrument, Side};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Historical data loader specific errors.
+pub mod error;
+
+/// Lazy loader of CSV files.
+pub mod csv;
+
+/// Lazy loader of JSON lines files.
+pub mod json;
+
+/// Simple binary columnar format, with a lazy loader & a writer to convert other formats into it.
+pub mod columnar;
+
+/// Configures how the records of a historical data file are mapped into
+/// [`MarketEvent<DataKind>`]s.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct LoaderConfig {
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    /// Kind of record contained in the file & the columns it's fields are read from.
+    pub columns: Columns,
+    /// Format of the time column.
+    pub time_format: TimeFormat,
+    /// Offset from UTC (in seconds) of timestamps that do not specify their own time zone.
+    pub utc_offset_secs: i32,
+}
+
+impl LoaderConfig {
+    /// Constructs a [`LoaderConfig`] for candle records using the default [`CandleColumns`],
+    /// with [`TimeFormat::Rfc3339`] timestamps.
+    pub fn candles<E, I>(exchange: E, instrument: I) -> Self
+    where
+        E: Into<Exchange>,
+        I: Into<Instrument>,
+    {
+        Self {
+            exchange: exchange.into(),
+            instrument: instrument.into(),
+            columns: Columns::Candle(CandleColumns::default()),
+            time_format: TimeFormat::default(),
+            utc_offset_secs: 0,
+        }
+    }
+
+    /// Constructs a [`LoaderConfig`] for trade records using the default [`TradeColumns`], with
+    /// [`TimeFormat::Rfc3339`] timestamps.
+    pub fn trades<E, I>(exchange: E, instrument: I) -> Self
+    where
+        E: Into<Exchange>,
+        I: Into<Instrument>,
+    {
+        Self {
+            exchange: exchange.into(),
+            instrument: instrument.into(),
+            columns: Columns::Trade(TradeColumns::default()),
+            time_format: TimeFormat::default(),
+            utc_offset_secs: 0,
+        }
+    }
+
+    /// Maps a record into a [`MarketEvent<DataKind>`], timestamped with the record time.
+    fn parse<R: Record>(&self, record: &R) -> Result<MarketEvent<DataKind>, LoaderError> {
+        let (time, kind) = match &self.columns {
+            Columns::Candle(columns) => {
+                let close_time = self.time(record, &columns.close_time)?;
+                let candle = Candle {
+                    close_time,
+                    open: float(record, &columns.open)?,
+                    high: float(record, &columns.high)?,
+                    low: float(record, &columns.low)?,
+                    close: float(record, &columns.close)?,
+                    volume: float(record, &columns.volume)?,
+                    trade_count: match &columns.trade_count {
+                        Some(column) => float(record, column)? as u64,
+                        None => 0,
+                    },
+                };
+                (close_time, DataKind::Candle(candle))
+            }
+            Columns::Trade(columns) => {
+                let time = self.time(record, &columns.time)?;
+                let trade = PublicTrade {
+                    id: match &columns.id {
+                        Some(column) => field(record, column)?.to_string(),
+                        None => String::new(),
+                    },
+                    price: float(record, &columns.price)?,
+                    amount: float(record, &columns.amount)?,
+                    side: side(record, &columns.side)?,
+                };
+                (time, DataKind::Trade(trade))
+            }
+        };
+
+        Ok(MarketEvent {
+            exchange_time: time,
+            received_time: time,
+            exchange: self.exchange.clone(),
+            instrument: self.instrument.clone(),
+            kind,
+        })
+    }
+
+    /// Parses the time column of the record using the configured [`TimeFormat`].
+    fn time<R: Record>(&self, record: &R, column: &str) -> Result<DateTime<Utc>, LoaderError> {
+        let invalid = |reason: String| LoaderError::InvalidField(column.to_owned(), reason);
+
+        match (field(record, column)?, &self.time_format) {
+            (Field::Time(time), _) => Ok(time),
+            (Field::Text(text), TimeFormat::Rfc3339) => DateTime::parse_from_rfc3339(text.trim())
+                .map(|time| time.with_timezone(&Utc))
+                .map_err(|error| invalid(error.to_string())),
+            (Field::Text(text), TimeFormat::Custom(format)) => {
+                // Formats that specify their own offset take precedence over the utc_offset_secs
+                if let Ok(time) = DateTime::parse_from_str(text.trim(), format) {
+                    return Ok(time.with_timezone(&Utc));
+                }
+
+                let naive = NaiveDateTime::parse_from_str(text.trim(), format)
+                    .map_err(|error| invalid(error.to_string()))?;
+
+                FixedOffset::east_opt(self.utc_offset_secs)
+                    .and_then(|offset| offset.from_local_datetime(&naive).single())
+                    .map(|time| time.with_timezone(&Utc))
+                    .ok_or_else(|| invalid(format!("invalid utc offset {}", self.utc_offset_secs)))
+            }
+            (field, TimeFormat::Unix(unit)) => {
+                // Integer timestamps (including integer text) are scaled exactly, since large
+                // millisecond, microsecond & nanosecond values exceed the precision of an f64
+                let integer = match &field {
+                    Field::Int(value) => Some(*value),
+                    Field::Text(text) => text.trim().parse::<i64>().ok(),
+                    _ => None,
+                };
+
+                match integer {
+                    Some(value) => value
+                        .checked_mul(unit.nanos())
+                        .map(|nanos| Utc.timestamp_nanos(nanos))
+                        .ok_or_else(|| invalid(format!("{value} is out of range"))),
+                    None => {
+                        let value = field.to_f64().map_err(invalid)?;
+                        Ok(Utc.timestamp_nanos((value * unit.nanos() as f64) as i64))
+                    }
+                }
+            }
+            (field, format) => Err(invalid(format!("{field} does not match {format:?}"))),
+        }
+    }
+}
+
+/// Kind of record contained in a historical data file, along with the columns each field of the
+/// record is read from.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Columns {
+    Candle(CandleColumns),
+    Trade(TradeColumns),
+}
+
+/// Columns the fields of a [`Candle`] are read from.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CandleColumns {
+    pub close_time: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    /// Trade count defaults to zero if no column is provided.
+    pub trade_count: Option<String>,
+}
+
+impl Default for CandleColumns {
+    fn default() -> Self {
+        Self {
+            close_time: "close_time".to_owned(),
+            open: "open".to_owned(),
+            high: "high".to_owned(),
+            low: "low".to_owned(),
+            close: "close".to_owned(),
+            volume: "volume".to_owned(),
+            trade_count: Some("trade_count".to_owned()),
+        }
+    }
+}
+
+/// Columns the fields of a [`PublicTrade`] are read from.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TradeColumns {
+    pub time: String,
+    /// Trade id defaults to an empty string if no column is provided.
+    pub id: Option<String>,
+    pub price: String,
+    pub amount: String,
+    /// Side column containing "buy" / "sell" (or "b" / "s", "bid" / "ask"), or a signed number.
+    pub side: String,
+}
+
+impl Default for TradeColumns {
+    fn default() -> Self {
+        Self {
+            time: "time".to_owned(),
+            id: Some("id".to_owned()),
+            price: "price".to_owned(),
+            amount: "amount".to_owned(),
+            side: "side".to_owned(),
+        }
+    }
+}
+
+/// Format of the time column of a historical data file.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum TimeFormat {
+    /// RFC 3339 / ISO 8601 timestamp including an offset, eg/ "2022-04-05T20:00:00Z".
+    #[default]
+    Rfc3339,
+    /// Number of [`UnixUnit`]s since the Unix epoch.
+    Unix(UnixUnit),
+    /// [`chrono::format::strftime`] format string, eg/ "%Y-%m-%d %H:%M:%S". Timestamps without an
+    /// offset are interpreted using the [`LoaderConfig::utc_offset_secs`].
+    Custom(String),
+}
+
+/// Unit of a Unix timestamp.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub enum UnixUnit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl UnixUnit {
+    /// Number of nanoseconds in one [`UnixUnit`].
+    fn nanos(&self) -> i64 {
+        match self {
+            UnixUnit::Seconds => 1_000_000_000,
+            UnixUnit::Millis => 1_000_000,
+            UnixUnit::Micros => 1_000,
+            UnixUnit::Nanos => 1,
+        }
+    }
+}
+
+/// Value of a record field.
+#[derive(Clone, PartialEq, Debug)]
+enum Field {
+    Float(f64),
+    Int(i64),
+    Text(String),
+    Time(DateTime<Utc>),
+}
+
+impl Field {
+    fn to_f64(&self) -> Result<f64, String> {
+        match self {
+            Field::Float(value) => Ok(*value),
+            Field::Int(value) => Ok(*value as f64),
+            Field::Text(text) => text
+                .trim()
+                .parse()
+                .map_err(|_| format!("{text:?} is not a number")),
+            Field::Time(time) => Err(format!("{time} is not a number")),
+        }
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::Float(value) => write!(f, "{value}"),
+            Field::Int(value) => write!(f, "{value}"),
+            Field::Text(text) => write!(f, "{text}"),
+            Field::Time(time) => write!(f, "{time}"),
+        }
+    }
+}
+
+/// Record of a historical data file, providing access to it's fields by column name.
+trait Record {
+    fn field(&self, column: &str) -> Option<Field>;
+}
+
+fn field<R: Record>(record: &R, column: &str) -> Result<Field, LoaderError> {
+    record
+        .field(column)
+        .ok_or_else(|| LoaderError::MissingColumn(column.to_owned()))
+}
+
+fn float<R: Record>(record: &R, column: &str) -> Result<f64, LoaderError> {
+    field(record, column)?
+        .to_f64()
+        .map_err(|reason| LoaderError::InvalidField(column.to_owned(), reason))
+}
+
+fn side<R: Record>(record: &R, column: &str) -> Result<Side, LoaderError> {
+    match field(record, column)? {
+        Field::Text(text) => match text.trim().to_lowercase().as_str() {
+            "buy" | "b" | "bid" => Ok(Side::Buy),
+            "sell" | "s" | "ask" => Ok(Side::Sell),
+            other => other.parse::<f64>().map(signed_side).map_err(|_| {
+                LoaderError::InvalidField(column.to_owned(), format!("{text:?} is not a Side"))
+            }),
+        },
+        Field::Float(value) => Ok(signed_side(value)),
+        Field::Int(value) => Ok(signed_side(value as f64)),
+        Field::Time(time) => Err(LoaderError::InvalidField(
+            column.to_owned(),
+            format!("{time} is not a Side"),
+        )),
+    }
+}
+
+fn signed_side(value: f64) -> Side {
+    if value.is_sign_negative() {
+        Side::Sell
+    } else {
+        Side::Buy
+    }
+}
+
+/// Discards any records a loader fails to read, logging the error, so the remaining
+/// [`MarketEvent`]s can drive a historical [`MarketFeed`](super::historical::MarketFeed).
+pub fn skip_invalid<Records>(records: Records) -> impl Iterator<Item = MarketEvent<DataKind>>
+where
+    Records: IntoIterator<Item = Result<MarketEvent<DataKind>, LoaderError>>,
+{
+    records.into_iter().filter_map(|record| match record {
+        Ok(event) => Some(event),
+        Err(error) => {
+            warn!(%error, action = "skipping record", "failed to load historical record");
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    impl Record for HashMap<&str, Field> {
+        fn field(&self, column: &str) -> Option<Field> {
+            self.get(column).cloned()
+        }
+    }
+
+    #[test]
+    fn time_column_is_parsed_using_time_format_and_utc_offset() {
+        let expected = Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap();
+        let mut config = LoaderConfig::trades(
+            "binance",
+            (
+                "btc",
+                "usdt",
+                barter_integration::model::InstrumentKind::Spot,
+            ),
+        );
+
+        let cases = [
+            (
+                TimeFormat::Rfc3339,
+                Field::Text("2022-04-05T22:00:00+02:00".to_owned()),
+                0,
+            ),
+            (
+                TimeFormat::Unix(UnixUnit::Millis),
+                Field::Int(1649188800000),
+                0,
+            ),
+            (
+                TimeFormat::Unix(UnixUnit::Seconds),
+                Field::Text("1649188800".to_owned()),
+                0,
+            ),
+            (
+                TimeFormat::Custom("%Y-%m-%d %H:%M:%S".to_owned()),
+                Field::Text("2022-04-05 15:00:00".to_owned()),
+                -5 * 3600,
+            ),
+            (
+                TimeFormat::Custom("%Y-%m-%d %H:%M:%S%.f UTC".to_owned()),
+                Field::Text("2022-04-05 20:00:00.000000000 UTC".to_owned()),
+                0,
+            ),
+            (TimeFormat::Rfc3339, Field::Time(expected), 0),
+        ];
+
+        for (format, value, utc_offset_secs) in cases {
+            config.time_format = format.clone();
+            config.utc_offset_secs = utc_offset_secs;
+            let record = HashMap::from([("time", value)]);
+            assert_eq!(
+                config.time(&record, "time").unwrap(),
+                expected,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn side_column_accepts_names_and_signed_numbers() {
+        let cases = [
+            (Field::Text("BUY".to_owned()), Side::Buy),
+            (Field::Text("s".to_owned()), Side::Sell),
+            (Field::Text("-1".to_owned()), Side::Sell),
+            (Field::Float(2.0), Side::Buy),
+        ];
+
+        for (value, expected) in cases {
+            let record = HashMap::from([("side", value)]);
+            assert_eq!(side(&record, "side").unwrap(), expected);
+        }
+
+        let record = HashMap::from([("side", Field::Text("sideways".to_owned()))]);
+        assert!(matches!(
+            side(&record, "side"),
+            Err(LoaderError::InvalidField(_, _))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -10,12 +11,37 @@ pub mod live;
 /// Historical market event feed for backtesting.
 pub mod historical;
 
+/// [`MarketGenerator`] adapters aggregating trades into time, tick, volume & dollar bars, and
+/// resampling candles into coarser intervals.
+pub mod bar;
+
+/// Lazy loaders streaming historical candles & trades from CSV, JSON lines & binary columnar
+/// files on disk.
+pub mod loader;
+
+/// [`MarketGenerator`] adapter guarding against out-of-order, duplicate, mispriced, stale & gapped
+/// market data.
+pub mod quality;
+
+/// Seeded, deterministic [`MarketGenerator`]s yielding synthetic trades & candles with known price
+/// dynamics (eg/ geometric Brownian motion, mean reversion, regime switching & jumps).
+pub mod synthetic;
+
 /// Generates the next `Event`. Acts as the system heartbeat.
 pub trait MarketGenerator<Event> {
     /// Return the next market `Event`.
     fn next(&mut self) -> Feed<Event>;
 }
 
+/// Asynchronously generates the next `Event`, allowing a
+/// [`Trader`](crate::engine::trader::Trader) to run as a task on the tokio runtime rather than
+/// on it's own thread.
+#[async_trait]
+pub trait AsyncMarketGenerator<Event> {
+    /// Return the next market `Event`, yielding to the runtime until it is available.
+    async fn next_async(&mut self) -> Feed<Event>;
+}
+
 /// Communicates the state of the [`Feed`] as well as the next event.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub enum Feed<Event> {
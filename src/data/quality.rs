@@ -0,0 +1,473 @@
+use crate::{
+    data::{AsyncMarketGenerator, Feed, MarketGenerator},
+    event::{Event, MessageTransmitter},
+    portfolio::protection::PriceRange,
+};
+use async_trait::async_trait;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, time::Duration};
+use tracing::warn;
+
+/// Configurable data quality rules checked by a [`DataQualityGuard`]. MarketEvents with a
+/// non-positive or non-finite price are always rejected.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct QualityConfig {
+    /// Reject [`MarketEvent`]s with an `exchange_time` earlier than the last accepted
+    /// [`MarketEvent`].
+    pub monotonic_time: bool,
+    /// Reject trades with the same id as one of this many most recently accepted trades.
+    pub duplicate_trade_window: Option<usize>,
+    /// Reject [`MarketEvent`]s priced further than this fraction from the last accepted price
+    /// (eg/ 0.1 rejects moves of more than 10%).
+    pub max_price_deviation: Option<f64>,
+    /// Accept a deviating price, re-anchoring the last accepted price to it, once this many
+    /// consecutive [`MarketEvent`]s deviate to prices within `max_price_deviation` of each other
+    /// (eg/ the market genuinely moved on a gap open or news spike).
+    #[serde(default = "QualityConfig::default_reanchor_after")]
+    pub reanchor_after: usize,
+    /// Reject [`MarketEvent`]s received more than this long after their `exchange_time`
+    /// (eg/ stale order books).
+    pub max_staleness: Option<Duration>,
+    /// Report a gap if more than this long elapses between the `exchange_time`s of consecutive
+    /// accepted [`MarketEvent`]s.
+    pub max_gap: Option<Duration>,
+}
+
+impl QualityConfig {
+    const DEFAULT_REANCHOR_AFTER: usize = 3;
+
+    fn default_reanchor_after() -> usize {
+        Self::DEFAULT_REANCHOR_AFTER
+    }
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            monotonic_time: false,
+            duplicate_trade_window: None,
+            max_price_deviation: None,
+            reanchor_after: Self::DEFAULT_REANCHOR_AFTER,
+            max_staleness: None,
+            max_gap: None,
+        }
+    }
+}
+
+/// Data quality violation detected by a [`DataQualityGuard`], sent downstream as an
+/// [`Event::DataQuality`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct DataQualityIssue {
+    /// `exchange_time` of the offending [`MarketEvent`].
+    pub time: DateTime<Utc>,
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub violation: Violation,
+}
+
+/// Data quality rule violated by a [`MarketEvent`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Violation {
+    /// Price is zero, negative or non-finite. The [`MarketEvent`] is dropped.
+    InvalidPrice(f64),
+    /// `exchange_time` precedes that of the last accepted [`MarketEvent`]. The [`MarketEvent`]
+    /// is dropped.
+    OutOfOrder { last: DateTime<Utc> },
+    /// Trade id was recently seen. The [`MarketEvent`] is dropped.
+    DuplicateTrade(String),
+    /// Price deviates too far from the last accepted price. The [`MarketEvent`] is dropped.
+    PriceDeviation { last: f64, price: f64 },
+    /// [`MarketEvent`] was received too long after it's `exchange_time`. The [`MarketEvent`] is
+    /// dropped.
+    Stale { received_time: DateTime<Utc> },
+    /// Too long elapsed since the last accepted [`MarketEvent`]. The [`MarketEvent`] is still
+    /// yielded, after a [`Feed::Unhealthy`].
+    Gap { last: DateTime<Utc> },
+}
+
+/// [`MarketGenerator`] adapter that validates the [`MarketEvent`]s of a single market against
+/// it's [`QualityConfig`] rules.
+///
+/// Each violation yields a [`Feed::Unhealthy`] & sends an [`Event::DataQuality`] via the
+/// provided [`MessageTransmitter`]. Invalid [`MarketEvent`]s are dropped, whereas a
+/// [`MarketEvent`] following a gap is yielded by the next call.
+#[derive(Debug)]
+pub struct DataQualityGuard<Data, EventTx>
+where
+    EventTx: MessageTransmitter<Event>,
+{
+    data: Data,
+    config: QualityConfig,
+    event_tx: EventTx,
+    last_time: Option<DateTime<Utc>>,
+    last_price: Option<f64>,
+    /// Latest deviating price & the number of consecutive deviations agreeing with it.
+    deviation: Option<(f64, usize)>,
+    recent_trades: VecDeque<String>,
+    pending: Option<MarketEvent<DataKind>>,
+}
+
+impl<Data, EventTx> DataQualityGuard<Data, EventTx>
+where
+    EventTx: MessageTransmitter<Event>,
+{
+    /// Constructs a new [`DataQualityGuard`] validating the [`MarketEvent`]s of the provided
+    /// market data handler.
+    pub fn new(data: Data, config: QualityConfig, event_tx: EventTx) -> Self {
+        Self {
+            data,
+            config,
+            event_tx,
+            last_time: None,
+            last_price: None,
+            deviation: None,
+            recent_trades: VecDeque::new(),
+            pending: None,
+        }
+    }
+
+    /// Determines the [`Violation`] that should cause the provided [`MarketEvent`] to be
+    /// dropped, if any.
+    fn rejection(&self, event: &MarketEvent<DataKind>) -> Option<Violation> {
+        let range = PriceRange::from_market(event);
+
+        if let Some(range) = range {
+            if let Some(price) = [range.open, range.high, range.low, range.close]
+                .into_iter()
+                .find(|price| !price.is_finite() || *price <= 0.0)
+            {
+                return Some(Violation::InvalidPrice(price));
+            }
+        }
+
+        if let Some(last) = self.last_time {
+            if self.config.monotonic_time && event.exchange_time < last {
+                return Some(Violation::OutOfOrder { last });
+            }
+        }
+
+        if let Some(max_staleness) = self.config.max_staleness {
+            let latency = event.received_time - event.exchange_time;
+            if latency
+                .to_std()
+                .is_ok_and(|latency| latency > max_staleness)
+            {
+                return Some(Violation::Stale {
+                    received_time: event.received_time,
+                });
+            }
+        }
+
+        if let DataKind::Trade(trade) = &event.kind {
+            if self.recent_trades.contains(&trade.id) {
+                return Some(Violation::DuplicateTrade(trade.id.clone()));
+            }
+        }
+
+        if let (Some(max_deviation), Some(last), Some(range)) =
+            (self.config.max_price_deviation, self.last_price, range)
+        {
+            if ((range.close - last) / last).abs() > max_deviation {
+                return Some(Violation::PriceDeviation {
+                    last,
+                    price: range.close,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Tracks consecutive [`Violation::PriceDeviation`]s that agree with each other, returning
+    /// true once enough of them indicate the market has moved to a new price level.
+    fn reanchor(&mut self, violation: &Violation) -> bool {
+        let (Violation::PriceDeviation { price, .. }, Some(max_deviation)) =
+            (violation, self.config.max_price_deviation)
+        else {
+            return false;
+        };
+
+        let count = match self.deviation {
+            Some((previous, count)) if ((price - previous) / previous).abs() <= max_deviation => {
+                count + 1
+            }
+            _ => 1,
+        };
+
+        if count >= self.config.reanchor_after {
+            self.deviation = None;
+            true
+        } else {
+            self.deviation = Some((*price, count));
+            false
+        }
+    }
+
+    /// Records the accepted [`MarketEvent`], returning the gap [`Violation`] it ends, if any.
+    fn accept(&mut self, event: &MarketEvent<DataKind>) -> Option<Violation> {
+        let gap = match (self.config.max_gap, self.last_time) {
+            (Some(max_gap), Some(last)) => (event.exchange_time - last)
+                .to_std()
+                .ok()
+                .filter(|elapsed| *elapsed > max_gap)
+                .map(|_| Violation::Gap { last }),
+            _ => None,
+        };
+
+        self.last_time = Some(
+            self.last_time
+                .map_or(event.exchange_time, |last| last.max(event.exchange_time)),
+        );
+
+        if let Some(range) = PriceRange::from_market(event) {
+            self.last_price = Some(range.close);
+            self.deviation = None;
+        }
+
+        if let (Some(window), DataKind::Trade(trade)) =
+            (self.config.duplicate_trade_window, &event.kind)
+        {
+            if window > 0 {
+                if self.recent_trades.len() == window {
+                    self.recent_trades.pop_front();
+                }
+                self.recent_trades.push_back(trade.id.clone());
+            }
+        }
+
+        gap
+    }
+
+    /// Logs & sends the [`Violation`] of the provided [`MarketEvent`] downstream.
+    fn report(&mut self, event: &MarketEvent<DataKind>, violation: Violation) {
+        warn!(
+            exchange = %event.exchange,
+            instrument = %event.instrument,
+            ?violation,
+            "data quality violation"
+        );
+
+        self.event_tx.send(Event::DataQuality(DataQualityIssue {
+            time: event.exchange_time,
+            exchange: event.exchange.clone(),
+            instrument: event.instrument.clone(),
+            violation,
+        }));
+    }
+
+    /// Validates the next [`Feed`] of the underlying market data handler.
+    fn validate(&mut self, feed: Feed<MarketEvent<DataKind>>) -> Feed<MarketEvent<DataKind>> {
+        let event = match feed {
+            Feed::Next(event) => event,
+            other => return other,
+        };
+
+        if let Some(violation) = self.rejection(&event) {
+            if !self.reanchor(&violation) {
+                self.report(&event, violation);
+                return Feed::Unhealthy;
+            }
+        }
+
+        match self.accept(&event) {
+            Some(gap) => {
+                self.report(&event, gap);
+                self.pending = Some(event);
+                Feed::Unhealthy
+            }
+            None => Feed::Next(event),
+        }
+    }
+}
+
+impl<Data, EventTx> MarketGenerator<MarketEvent<DataKind>> for DataQualityGuard<Data, EventTx>
+where
+    Data: MarketGenerator<MarketEvent<DataKind>>,
+    EventTx: MessageTransmitter<Event>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<DataKind>> {
+        if let Some(event) = self.pending.take() {
+            return Feed::Next(event);
+        }
+
+        let feed = self.data.next();
+        self.validate(feed)
+    }
+}
+
+#[async_trait]
+impl<Data, EventTx> AsyncMarketGenerator<MarketEvent<DataKind>> for DataQualityGuard<Data, EventTx>
+where
+    Data: AsyncMarketGenerator<MarketEvent<DataKind>> + Send,
+    EventTx: MessageTransmitter<Event> + Send,
+{
+    async fn next_async(&mut self) -> Feed<MarketEvent<DataKind>> {
+        if let Some(event) = self.pending.take() {
+            return Feed::Next(event);
+        }
+
+        let feed = self.data.next_async().await;
+        self.validate(feed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical::MarketFeed, event::EventTx, test_util::market_event_trade};
+    use barter_data::subscription::trade::PublicTrade;
+    use barter_integration::model::Side;
+    use chrono::TimeZone;
+    use tokio::sync::mpsc;
+
+    fn trade(id: &str, second: i64, price: f64) -> MarketEvent<DataKind> {
+        let time =
+            Utc.with_ymd_and_hms(2022, 4, 5, 20, 0, 0).unwrap() + chrono::Duration::seconds(second);
+        MarketEvent {
+            exchange_time: time,
+            received_time: time,
+            kind: DataKind::Trade(PublicTrade {
+                id: id.to_owned(),
+                price,
+                amount: 1.0,
+                side: Side::Buy,
+            }),
+            ..market_event_trade(Side::Buy)
+        }
+    }
+
+    fn collect<Data>(guard: &mut DataQualityGuard<Data, EventTx>) -> Vec<Feed<String>>
+    where
+        Data: MarketGenerator<MarketEvent<DataKind>>,
+    {
+        std::iter::from_fn(|| match guard.next() {
+            Feed::Finished => None,
+            Feed::Unhealthy => Some(Feed::Unhealthy),
+            Feed::Next(event) => match event.kind {
+                DataKind::Trade(trade) => Some(Feed::Next(trade.id)),
+                _ => unreachable!(),
+            },
+        })
+        .collect()
+    }
+
+    fn violations(event_rx: &mut mpsc::UnboundedReceiver<Event>) -> Vec<Violation> {
+        std::iter::from_fn(|| event_rx.try_recv().ok())
+            .map(|event| match event {
+                Event::DataQuality(issue) => issue.violation,
+                other => panic!("unexpected Event: {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_drop_invalid_events_and_report_violations() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let config = QualityConfig {
+            monotonic_time: true,
+            duplicate_trade_window: Some(2),
+            max_price_deviation: Some(0.1),
+            ..QualityConfig::default()
+        };
+        let data = MarketFeed::new(vec![
+            trade("1", 0, 1000.0),
+            trade("2", 1, 0.0),
+            trade("3", 2, f64::NAN),
+            trade("1", 3, 1000.0),
+            trade("4", -1, 1000.0),
+            trade("5", 4, 1500.0),
+            trade("6", 5, 1050.0),
+        ]);
+        let mut guard = DataQualityGuard::new(data, config, EventTx::new(event_tx));
+
+        assert_eq!(
+            collect(&mut guard),
+            vec![
+                Feed::Next("1".to_owned()),
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Next("6".to_owned()),
+            ]
+        );
+
+        let violations = violations(&mut event_rx);
+        assert_eq!(violations.len(), 5);
+        assert_eq!(violations[0], Violation::InvalidPrice(0.0));
+        assert!(matches!(violations[1], Violation::InvalidPrice(price) if price.is_nan()));
+        assert_eq!(violations[2], Violation::DuplicateTrade("1".to_owned()));
+        assert!(matches!(violations[3], Violation::OutOfOrder { .. }));
+        assert_eq!(
+            violations[4],
+            Violation::PriceDeviation {
+                last: 1000.0,
+                price: 1500.0
+            }
+        );
+    }
+
+    #[test]
+    fn should_reanchor_after_consecutive_agreeing_price_deviations() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let config = QualityConfig {
+            max_price_deviation: Some(0.1),
+            reanchor_after: 3,
+            ..QualityConfig::default()
+        };
+        let data = MarketFeed::new(vec![
+            trade("1", 0, 1000.0),
+            trade("2", 1, 1500.0),
+            trade("3", 2, 1510.0),
+            trade("4", 3, 1505.0),
+            trade("5", 4, 1520.0),
+            trade("6", 5, 1000.0),
+        ]);
+        let mut guard = DataQualityGuard::new(data, config, EventTx::new(event_tx));
+
+        assert_eq!(
+            collect(&mut guard),
+            vec![
+                Feed::Next("1".to_owned()),
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Next("4".to_owned()),
+                Feed::Next("5".to_owned()),
+                Feed::Unhealthy,
+            ]
+        );
+        assert_eq!(violations(&mut event_rx).len(), 3);
+    }
+
+    #[test]
+    fn should_report_stale_events_and_yield_events_after_gaps() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let config = QualityConfig {
+            max_staleness: Some(Duration::from_secs(1)),
+            max_gap: Some(Duration::from_secs(10)),
+            ..QualityConfig::default()
+        };
+        let mut stale = trade("2", 1, 1000.0);
+        stale.received_time = stale.exchange_time + chrono::Duration::seconds(5);
+        let data = MarketFeed::new(vec![trade("1", 0, 1000.0), stale, trade("3", 60, 1000.0)]);
+        let mut guard = DataQualityGuard::new(data, config, EventTx::new(event_tx));
+
+        assert_eq!(
+            collect(&mut guard),
+            vec![
+                Feed::Next("1".to_owned()),
+                Feed::Unhealthy,
+                Feed::Unhealthy,
+                Feed::Next("3".to_owned()),
+            ]
+        );
+
+        let violations = violations(&mut event_rx);
+        assert!(matches!(violations[0], Violation::Stale { .. }));
+        assert!(matches!(violations[1], Violation::Gap { .. }));
+    }
+}
@@ -0,0 +1,489 @@
+use crate::data::{AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_integration::model::{Exchange, Instrument, Side};
+use chrono::{DateTime, Utc};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Number of seconds in a year, used to scale the annualised [`PriceProcess`] parameters to each
+/// step.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+/// Stochastic process driving the price of a [`SyntheticFeed`]. Every parameter is annualised
+/// (eg/ a volatility of 0.8 is 80% per year).
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum PriceProcess {
+    /// Geometric Brownian motion with constant drift & volatility.
+    GeometricBrownian { drift: f64, volatility: f64 },
+    /// Ornstein-Uhlenbeck process on the log price, reverting towards the provided mean price at
+    /// the provided rate.
+    OrnsteinUhlenbeck {
+        mean: f64,
+        reversion: f64,
+        volatility: f64,
+    },
+    /// Geometric Brownian motion switching to a different (uniformly chosen) [`Regime`] with the
+    /// provided probability every step, starting in the first [`Regime`].
+    RegimeSwitching {
+        regimes: Vec<Regime>,
+        switch_probability: f64,
+    },
+    /// Merton jump diffusion, ie/ geometric Brownian motion with log-normal jumps arriving at the
+    /// provided intensity (expected jumps per year).
+    JumpDiffusion {
+        drift: f64,
+        volatility: f64,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+    },
+}
+
+/// Drift & volatility of a [`PriceProcess::RegimeSwitching`] regime (eg/ bull, bear, crash).
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Regime {
+    pub drift: f64,
+    pub volatility: f64,
+}
+
+/// Determines the [`DataKind`] of the [`MarketEvent`]s yielded by a [`SyntheticFeed`].
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum SyntheticOutput {
+    /// One [`DataKind::Trade`] per step.
+    Trades,
+    /// One [`DataKind::Candle`] per provided number of steps.
+    Candles { steps_per_candle: u32 },
+}
+
+/// Configuration for constructing a [`SyntheticFeed`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SyntheticConfig {
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub process: PriceProcess,
+    pub output: SyntheticOutput,
+    pub initial_price: f64,
+    /// Time of the initial price. The first [`MarketEvent`] occurs one step later.
+    pub start: DateTime<Utc>,
+    /// Time between consecutive samples of the [`PriceProcess`].
+    pub step: Duration,
+    /// Number of [`MarketEvent`]s to yield before the [`Feed`] is finished, or None to never
+    /// finish.
+    pub events: Option<u64>,
+    /// Seed of the random number generator. The same [`SyntheticConfig`] always yields the same
+    /// [`MarketEvent`]s.
+    pub seed: u64,
+}
+
+impl SyntheticConfig {
+    /// Constructs a [`SyntheticConfig`] yielding one minute trades starting at a price of 1000.0
+    /// at the Unix epoch, with a seed of 0 & no event limit.
+    pub fn trades<E, I>(exchange: E, instrument: I, process: PriceProcess) -> Self
+    where
+        E: Into<Exchange>,
+        I: Into<Instrument>,
+    {
+        Self {
+            exchange: exchange.into(),
+            instrument: instrument.into(),
+            process,
+            output: SyntheticOutput::Trades,
+            initial_price: 1000.0,
+            start: DateTime::<Utc>::default(),
+            step: Duration::from_secs(60),
+            events: None,
+            seed: 0,
+        }
+    }
+
+    /// Constructs a [`SyntheticConfig`] yielding one hour candles (of sixty one minute steps)
+    /// starting at a price of 1000.0 at the Unix epoch, with a seed of 0 & no event limit.
+    pub fn candles<E, I>(exchange: E, instrument: I, process: PriceProcess) -> Self
+    where
+        E: Into<Exchange>,
+        I: Into<Instrument>,
+    {
+        Self {
+            output: SyntheticOutput::Candles {
+                steps_per_candle: 60,
+            },
+            ..Self::trades(exchange, instrument, process)
+        }
+    }
+}
+
+/// Seeded, deterministic [`MarketGenerator`] yielding trades or candles of a single market with
+/// known price dynamics. Useful for testing strategies & risk rules without large fixture files.
+///
+/// Also implements [`Iterator`], so it can be used as the source of a
+/// [`MarketFeed`](crate::data::historical::MarketFeed) or
+/// [`SynchronisedReplay`](crate::data::historical::sync::SynchronisedReplay).
+#[derive(Debug)]
+pub struct SyntheticFeed {
+    config: SyntheticConfig,
+    /// Fixed algorithm rng, so the same seed yields the same series across rand releases.
+    rng: ChaCha8Rng,
+    /// Step duration in years.
+    dt: f64,
+    step: chrono::Duration,
+    time: DateTime<Utc>,
+    price: f64,
+    regime: usize,
+    yielded: u64,
+}
+
+impl SyntheticFeed {
+    /// Constructs a new [`SyntheticFeed`] from the provided [`SyntheticConfig`].
+    pub fn new(config: SyntheticConfig) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(config.seed),
+            dt: config.step.as_secs_f64() / SECONDS_PER_YEAR,
+            step: chrono::Duration::from_std(config.step)
+                .unwrap_or_else(|_| chrono::Duration::zero()),
+            time: config.start,
+            price: config.initial_price,
+            regime: 0,
+            yielded: 0,
+            config,
+        }
+    }
+
+    /// Index of the current [`Regime`] of a [`PriceProcess::RegimeSwitching`] process.
+    pub fn regime(&self) -> usize {
+        self.regime
+    }
+
+    /// Samples the next price of the [`PriceProcess`], advancing time by one step.
+    fn step(&mut self) -> f64 {
+        let dt = self.dt;
+        let shock = self.standard_normal();
+
+        let log_return = match &self.config.process {
+            PriceProcess::GeometricBrownian { drift, volatility } => {
+                gbm_log_return(*drift, *volatility, dt, shock)
+            }
+            PriceProcess::OrnsteinUhlenbeck {
+                mean,
+                reversion,
+                volatility,
+            } => {
+                let (log_price, log_mean) = (self.price.ln(), mean.ln());
+                let decay = (-reversion * dt).exp();
+                let deviation = match *reversion > 0.0 {
+                    true => volatility * ((1.0 - decay * decay) / (2.0 * reversion)).sqrt(),
+                    false => volatility * dt.sqrt(),
+                };
+                log_mean + (log_price - log_mean) * decay + deviation * shock - log_price
+            }
+            PriceProcess::RegimeSwitching {
+                regimes,
+                switch_probability,
+            } => {
+                let switch_probability = switch_probability.clamp(0.0, 1.0);
+                if regimes.len() > 1 && self.rng.gen_bool(switch_probability) {
+                    // Choose uniformly between every other regime
+                    let next = self.rng.gen_range(0..regimes.len() - 1);
+                    self.regime = if next >= self.regime { next + 1 } else { next };
+                }
+                regimes
+                    .get(self.regime)
+                    .map(|regime| gbm_log_return(regime.drift, regime.volatility, dt, shock))
+                    .unwrap_or_default()
+            }
+            PriceProcess::JumpDiffusion {
+                drift,
+                volatility,
+                jump_intensity,
+                jump_mean,
+                jump_volatility,
+            } => {
+                let (drift, volatility) = (*drift, *volatility);
+                let (jump_intensity, jump_mean, jump_volatility) =
+                    (*jump_intensity, *jump_mean, *jump_volatility);
+
+                // Compensate the drift so the expected return is unaffected by jumps
+                let compensator = jump_intensity
+                    * ((jump_mean + 0.5 * jump_volatility * jump_volatility).exp() - 1.0);
+
+                let jumps = (0..self.poisson(jump_intensity * dt))
+                    .map(|_| jump_mean + jump_volatility * self.standard_normal())
+                    .sum::<f64>();
+
+                gbm_log_return(drift - compensator, volatility, dt, shock) + jumps
+            }
+        };
+
+        self.time += self.step;
+        self.price *= log_return.exp();
+        self.price
+    }
+
+    /// Samples a standard normal variate using the Box-Muller transform.
+    fn standard_normal(&mut self) -> f64 {
+        let uniform = 1.0 - self.rng.gen::<f64>();
+        let angle = self.rng.gen::<f64>() * std::f64::consts::TAU;
+        (-2.0 * uniform.ln()).sqrt() * angle.cos()
+    }
+
+    /// Samples a Poisson variate with the provided mean (assumed small, as is the case for the
+    /// number of jumps per step).
+    fn poisson(&mut self, mean: f64) -> u64 {
+        let threshold = (-mean.max(0.0)).exp();
+        let mut count = 0;
+        let mut product = self.rng.gen::<f64>();
+        while product > threshold {
+            count += 1;
+            product *= self.rng.gen::<f64>();
+        }
+        count
+    }
+
+    /// Samples an exponentially distributed trade amount with a mean of 1.0.
+    fn amount(&mut self) -> f64 {
+        -(1.0 - self.rng.gen::<f64>()).ln()
+    }
+
+    fn next_trade(&mut self) -> MarketEvent<DataKind> {
+        let previous = self.price;
+        let price = self.step();
+        let amount = self.amount();
+
+        self.market_event(DataKind::Trade(PublicTrade {
+            id: self.yielded.to_string(),
+            price,
+            amount,
+            side: if price >= previous {
+                Side::Buy
+            } else {
+                Side::Sell
+            },
+        }))
+    }
+
+    fn next_candle(&mut self, steps: u32) -> MarketEvent<DataKind> {
+        let open = self.step();
+        let mut candle = Candle {
+            close_time: self.time,
+            open,
+            high: open,
+            low: open,
+            close: open,
+            volume: self.amount(),
+            trade_count: 1,
+        };
+
+        for _ in 1..steps {
+            let price = self.step();
+            candle.high = candle.high.max(price);
+            candle.low = candle.low.min(price);
+            candle.close = price;
+            candle.volume += self.amount();
+            candle.trade_count += 1;
+        }
+        candle.close_time = self.time;
+
+        self.market_event(DataKind::Candle(candle))
+    }
+
+    fn market_event(&self, kind: DataKind) -> MarketEvent<DataKind> {
+        MarketEvent {
+            exchange_time: self.time,
+            received_time: self.time,
+            exchange: self.config.exchange.clone(),
+            instrument: self.config.instrument.clone(),
+            kind,
+        }
+    }
+}
+
+/// Log return of geometric Brownian motion over a step of dt years, given a standard normal
+/// shock.
+fn gbm_log_return(drift: f64, volatility: f64, dt: f64, shock: f64) -> f64 {
+    (drift - 0.5 * volatility * volatility) * dt + volatility * dt.sqrt() * shock
+}
+
+impl Iterator for SyntheticFeed {
+    type Item = MarketEvent<DataKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self
+            .config
+            .events
+            .is_some_and(|events| self.yielded >= events)
+        {
+            return None;
+        }
+
+        let event = match self.config.output {
+            SyntheticOutput::Trades => self.next_trade(),
+            SyntheticOutput::Candles { steps_per_candle } => {
+                self.next_candle(steps_per_candle.max(1))
+            }
+        };
+        self.yielded += 1;
+
+        Some(event)
+    }
+}
+
+impl MarketGenerator<MarketEvent<DataKind>> for SyntheticFeed {
+    fn next(&mut self) -> Feed<MarketEvent<DataKind>> {
+        Iterator::next(self).map_or(Feed::Finished, Feed::Next)
+    }
+}
+
+#[async_trait]
+impl AsyncMarketGenerator<MarketEvent<DataKind>> for SyntheticFeed {
+    async fn next_async(&mut self) -> Feed<MarketEvent<DataKind>> {
+        Iterator::next(self).map_or(Feed::Finished, Feed::Next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    fn config(process: PriceProcess) -> SyntheticConfig {
+        SyntheticConfig::trades("binance", ("btc", "usdt", InstrumentKind::Spot), process)
+    }
+
+    #[test]
+    fn should_yield_the_same_events_for_the_same_seed() {
+        let gbm = PriceProcess::GeometricBrownian {
+            drift: 0.1,
+            volatility: 0.8,
+        };
+        let seeded = |seed| SyntheticConfig {
+            events: Some(50),
+            seed,
+            ..config(gbm.clone())
+        };
+
+        let mut feed = SyntheticFeed::new(seeded(7));
+        let first = std::iter::from_fn(|| match MarketGenerator::next(&mut feed) {
+            Feed::Next(event) => Some(event),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(first.len(), 50);
+        assert_eq!(first, SyntheticFeed::new(seeded(7)).collect::<Vec<_>>());
+        assert_ne!(first, SyntheticFeed::new(seeded(8)).collect::<Vec<_>>());
+        assert_eq!(
+            first[1].exchange_time - first[0].exchange_time,
+            chrono::Duration::minutes(1)
+        );
+    }
+
+    #[test]
+    fn should_yield_consistent_candles_for_regime_switching_and_jump_processes() {
+        let processes = [
+            PriceProcess::RegimeSwitching {
+                regimes: vec![
+                    Regime {
+                        drift: 0.5,
+                        volatility: 0.3,
+                    },
+                    Regime {
+                        drift: -0.8,
+                        volatility: 1.2,
+                    },
+                ],
+                switch_probability: 0.05,
+            },
+            PriceProcess::JumpDiffusion {
+                drift: 0.0,
+                volatility: 0.5,
+                jump_intensity: 1000.0,
+                jump_mean: -0.01,
+                jump_volatility: 0.02,
+            },
+        ];
+
+        for process in processes {
+            let config = SyntheticConfig {
+                events: Some(100),
+                ..SyntheticConfig::candles(
+                    "binance",
+                    ("btc", "usdt", InstrumentKind::Spot),
+                    process,
+                )
+            };
+
+            let candles = SyntheticFeed::new(config)
+                .map(|event| match event.kind {
+                    DataKind::Candle(candle) => candle,
+                    _ => panic!("expected candle"),
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(candles.len(), 100);
+            for (index, candle) in candles.iter().enumerate() {
+                assert!(candle.low > 0.0);
+                assert!(candle.low <= candle.open.min(candle.close));
+                assert!(candle.high >= candle.open.max(candle.close));
+                assert_eq!(candle.trade_count, 60);
+                assert_eq!(
+                    candle.close_time,
+                    DateTime::<Utc>::default() + chrono::Duration::hours(index as i64 + 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ornstein_uhlenbeck_should_revert_towards_mean_price() {
+        let config = SyntheticConfig {
+            initial_price: 2000.0,
+            ..config(PriceProcess::OrnsteinUhlenbeck {
+                mean: 1000.0,
+                reversion: 5000.0,
+                volatility: 0.5,
+            })
+        };
+
+        let prices = SyntheticFeed::new(config)
+            .take(2000)
+            .map(|event| match event.kind {
+                DataKind::Trade(trade) => trade.price,
+                _ => panic!("expected trade"),
+            })
+            .collect::<Vec<_>>();
+
+        let settled = &prices[1000..];
+        let average = settled.iter().sum::<f64>() / settled.len() as f64;
+        assert!((average - 1000.0).abs() < 50.0, "average: {average}");
+    }
+
+    #[test]
+    fn should_yield_golden_prices_for_a_pinned_seed() {
+        let feed = SyntheticFeed::new(SyntheticConfig {
+            events: Some(3),
+            seed: 7,
+            ..config(PriceProcess::GeometricBrownian {
+                drift: 0.1,
+                volatility: 0.8,
+            })
+        });
+
+        let prices = feed
+            .map(|event| match event.kind {
+                DataKind::Trade(trade) => trade.price,
+                kind => panic!("expected trade, found {kind:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        // Changes if the rng algorithm (or the sampling of the PriceProcess) ever changes
+        assert_eq!(
+            prices,
+            vec![1000.3182087259635, 998.8891485899195, 999.1568231030163]
+        );
+    }
+}
@@ -1,5 +1,14 @@
-use crate::portfolio::repository::error::RepositoryError;
+use crate::{
+    execution::{
+        error::ExecutionError,
+        order::{OrderId, OrderState},
+    },
+    portfolio::{error::PortfolioError, repository::error::RepositoryError},
+    strategy::error::StrategyError,
+};
+use barter_integration::model::Market;
 use thiserror::Error;
+use uuid::Uuid;
 
 /// All errors generated in barter-engine.
 #[derive(Error, Debug)]
@@ -9,4 +18,31 @@ pub enum EngineError {
 
     #[error("Failed to interact with repository")]
     RepositoryInteractionError(#[from] RepositoryError),
+
+    #[error("Failed to interact with Portfolio: {0}")]
+    PortfolioInteractionError(#[from] PortfolioError),
+
+    #[error("Failed to interact with ExecutionClient: {0}")]
+    ExecutionInteractionError(#[from] ExecutionError),
+
+    #[error("Failed to interact with Strategy: {0}")]
+    StrategyInteractionError(#[from] StrategyError),
+
+    #[error("Engine has no Trader associated with Market: {0:?}")]
+    MarketNotFound(Market),
+
+    #[error("Trader associated with Market is no longer receiving Commands: {0:?}")]
+    TraderUnavailable(Market),
+
+    #[error("No open Position entered by signal_id: {0}")]
+    PositionNotFound(Uuid),
+
+    #[error("Trader has not received a MarketEvent to price the exit of Position: {0}")]
+    NoMarketPrice(Uuid),
+
+    #[error("Exit order {0} reached terminal state {1:?} without being filled")]
+    ExitOrderNotFilled(OrderId, OrderState),
+
+    #[error("Exit request superseded by a later request before its exit orders were confirmed")]
+    ExitSuperseded,
 }
@@ -1,11 +1,21 @@
 use crate::{
-    data::MarketGenerator,
-    engine::{error::EngineError, trader::Trader},
+    clock::{Clock, SharedClock},
+    data::{AsyncMarketGenerator, MarketGenerator},
+    engine::{
+        error::EngineError,
+        shutdown::{
+            ShutdownConfig, ShutdownEscalation, ShutdownReason, ShutdownReport, UnconfirmedExit,
+        },
+        trader::Trader,
+    },
     event::{Event, MessageTransmitter},
-    execution::ExecutionClient,
+    execution::{
+        order::{OrderId, OrderUpdate},
+        AsyncExecutionClient, ExecutionClient,
+    },
     portfolio::{
         position::Position,
-        repository::{PositionHandler, StatisticHandler},
+        repository::{determine_strategy_market_id, PositionHandler, StatisticHandler},
         FillUpdater, MarketUpdater, OrderGenerator,
     },
     statistic::summary::{PositionSummariser, TableBuilder},
@@ -16,7 +26,12 @@ use barter_integration::model::{Market, MarketId};
 use parking_lot::Mutex;
 use prettytable::Table;
 use serde::Serialize;
-use std::{collections::HashMap, fmt::Debug, sync::Arc, thread};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Debug,
+    sync::Arc,
+    thread,
+};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -29,6 +44,9 @@ pub mod error;
 /// Portfolio instance.
 pub mod trader;
 
+/// Graceful shutdown configuration & the [`ShutdownReport`] produced when an [`Engine`] stops.
+pub mod shutdown;
+
 /// Commands that can be actioned by an [`Engine`] and it's associated [`Trader`]s.
 #[derive(Debug)]
 pub enum Command {
@@ -36,15 +54,111 @@ pub enum Command {
     /// `oneshot::Sender`. Involves the [`Engine`] only.
     FetchOpenPositions(oneshot::Sender<Result<Vec<Position>, EngineError>>),
 
-    /// Terminate every running [`Trader`] associated with this [`Engine`]. Involves all [`Trader`]s.
-    Terminate(String),
+    /// Terminate every running [`Trader`] associated with this [`Engine`], after waiting for
+    /// them to confirm every open [`Position`] has been exited (see [`ShutdownConfig`]).
+    /// Acknowledges once every [`Trader`] has been terminated. Involves all [`Trader`]s.
+    Terminate(String, oneshot::Sender<Result<(), EngineError>>),
 
-    /// Exit every open [`Position`] associated with this [`Engine`]. Involves all [`Trader`]s.
-    ExitAllPositions,
+    /// Exit every open [`Position`] associated with this [`Engine`]. Acknowledges once every
+    /// [`Trader`] has generated it's exit [`OrderEvent`](crate::portfolio::OrderEvent)s, or with
+    /// the first error encountered. Involves all [`Trader`]s.
+    ExitAllPositions(oneshot::Sender<Result<(), EngineError>>),
 
     /// Exit a [`Position`]. Uses the [`Market`] provided to route this [`Command`] to the relevant
-    /// [`Trader`] instance. Involves one [`Trader`].
-    ExitPosition(Market),
+    /// [`Trader`] instance, which acknowledges once the exit
+    /// [`OrderEvent`](crate::portfolio::OrderEvent)s have been generated. Involves one
+    /// [`Trader`].
+    ExitPosition(Market, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Exit the open [`Position`] entered by the provided signal_id. Uses the [`Market`] provided
+    /// to route this [`Command`] to the relevant [`Trader`] instance, which acknowledges once the
+    /// exit [`OrderEvent`](crate::portfolio::OrderEvent) has been generated. Involves one
+    /// [`Trader`].
+    ExitPositionBySignal(Market, Uuid, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Cancel the working order with the provided [`OrderId`]. Uses the [`Market`] provided to
+    /// route this [`Command`] to the relevant [`Trader`] instance, which acknowledges with the
+    /// resulting [`OrderUpdate`]s. Involves one [`Trader`].
+    CancelOrder(
+        Market,
+        OrderId,
+        oneshot::Sender<Result<Vec<OrderUpdate>, EngineError>>,
+    ),
+
+    /// Cancel every working order. Uses the [`Market`] provided to route this [`Command`] to the
+    /// relevant [`Trader`] instance, which acknowledges with the resulting [`OrderUpdate`]s.
+    /// Involves one [`Trader`].
+    CancelAllOrders(
+        Market,
+        oneshot::Sender<Result<Vec<OrderUpdate>, EngineError>>,
+    ),
+
+    /// Pause the [`Trader`] associated with the provided [`Market`] from generating new
+    /// [`Signal`](crate::strategy::Signal)s. Open [`Position`]s, protective exits & working orders
+    /// continue to be managed. Involves one [`Trader`].
+    PauseTrader(Market, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Resume [`Signal`](crate::strategy::Signal) generation of the paused [`Trader`] associated
+    /// with the provided [`Market`]. Involves one [`Trader`].
+    ResumeTrader(Market, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Exit every open [`Position`] of the provided [`Market`]. The [`Trader`] acknowledges once
+    /// every exit order has been filled, or with an error if one is not. Used by the [`Engine`]
+    /// during shutdown. Involves one [`Trader`].
+    ExitPositionsAndConfirm(Market, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Update the parameters of the strategy run by the [`Trader`] associated with the provided
+    /// [`Market`]. See [`SignalGenerator::update_params`]. Involves one [`Trader`].
+    UpdateStrategyParams(
+        Market,
+        serde_json::Value,
+        oneshot::Sender<Result<(), EngineError>>,
+    ),
+}
+
+impl Command {
+    /// Returns the [`Market`] used to route this [`Command`] to a single [`Trader`], if it is
+    /// routed to one.
+    pub fn market(&self) -> Option<&Market> {
+        match self {
+            Command::ExitPosition(market, _)
+            | Command::ExitPositionBySignal(market, _, _)
+            | Command::CancelOrder(market, _, _)
+            | Command::CancelAllOrders(market, _)
+            | Command::ExitPositionsAndConfirm(market, _)
+            | Command::PauseTrader(market, _)
+            | Command::ResumeTrader(market, _)
+            | Command::UpdateStrategyParams(market, _, _) => Some(market),
+            Command::FetchOpenPositions(_)
+            | Command::Terminate(_, _)
+            | Command::ExitAllPositions(_) => None,
+        }
+    }
+
+    /// Acknowledges this [`Command`] with the provided [`EngineError`].
+    fn reject(self, error: EngineError) {
+        let acknowledged = match self {
+            Command::FetchOpenPositions(tx) => tx.send(Err(error)).is_ok(),
+            Command::Terminate(_, tx)
+            | Command::ExitAllPositions(tx)
+            | Command::ExitPosition(_, tx)
+            | Command::ExitPositionBySignal(_, _, tx)
+            | Command::ExitPositionsAndConfirm(_, tx)
+            | Command::PauseTrader(_, tx)
+            | Command::ResumeTrader(_, tx)
+            | Command::UpdateStrategyParams(_, _, tx) => tx.send(Err(error)).is_ok(),
+            Command::CancelOrder(_, _, tx) | Command::CancelAllOrders(_, tx) => {
+                tx.send(Err(error)).is_ok()
+            }
+        };
+
+        if !acknowledged {
+            warn!(
+                why = "oneshot receiver dropped",
+                "cannot acknowledge rejected Command"
+            );
+        }
+    }
 }
 
 /// Lego components for constructing an [`Engine`] via the new() constructor method.
@@ -73,12 +187,19 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     pub statistics_summary: Statistic,
+    /// Configures how open [`Position`]s are exited when a [`Command::Terminate`] is received.
+    pub shutdown_config: ShutdownConfig,
+    /// [`Clock`] shared with the Portfolio & [`Trader`]s, used to timestamp the [`ShutdownReport`].
+    pub clock: SharedClock,
+    /// If true, the trading session summary is printed to stdout once the [`Engine`] has shutdown.
+    pub print_summary: bool,
 }
 
 /// Multi-threaded Trading Engine capable of trading with an arbitrary number of [`Trader`]s, one
 /// for each unique [`Market`].
 ///
-/// Each [`Trader`] operates on it's own thread and has it's own Data handler, Strategy &
+/// Each [`Trader`] operates on it's own thread (see [`Engine::run`]), or as an asynchronous task
+/// on the tokio runtime (see [`Engine::run_async`]), and has it's own Data handler, Strategy &
 /// Execution Handler, as well as shared access to a global Portfolio instance. A graceful remote
 /// shutdown is made possible by sending a [`Command::Terminate`] to the Engine's broadcast::Receiver
 /// termination_rx.
@@ -114,6 +235,12 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     statistics_summary: Statistic,
+    /// Configures how open [`Position`]s are exited when a [`Command::Terminate`] is received.
+    shutdown_config: ShutdownConfig,
+    /// [`Clock`] shared with the Portfolio & [`Trader`]s, used to timestamp the [`ShutdownReport`].
+    clock: SharedClock,
+    /// If true, the trading session summary is printed to stdout once the [`Engine`] has shutdown.
+    print_summary: bool,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -145,6 +272,9 @@ where
             traders: lego.traders,
             trader_command_txs: lego.trader_command_txs,
             statistics_summary: lego.statistics_summary,
+            shutdown_config: lego.shutdown_config,
+            clock: lego.clock,
+            print_summary: lego.print_summary,
         }
     }
 
@@ -157,16 +287,27 @@ where
     /// receives [`Command`]s via the `command_rx` and actions them
     /// (eg/ terminate_traders, fetch_open_positions). If all of the [`Trader`]s stop organically
     /// (eg/ due to a finished [`MarketGenerator`]), the [`Engine`] terminates & prints a summary
-    /// for the trading session.
-    pub async fn run(mut self) {
+    /// for the trading session. Returns a [`ShutdownReport`] detailing any [`Position`]s left
+    /// open.
+    pub async fn run(mut self) -> ShutdownReport {
         // Run Traders on threads & send notification when they have stopped organically
-        let mut notify_traders_stopped = self.run_traders().await;
+        let notify_traders_stopped = self.run_traders().await;
+
+        self.run_until_shutdown(notify_traders_stopped).await
+    }
 
-        loop {
+    /// Actions remote [`Command`]s until the [`Engine`] is terminated, or until a notification is
+    /// received that all of the [`Trader`]s have stopped organically. Prints a summary for the
+    /// trading session & returns the [`ShutdownReport`].
+    async fn run_until_shutdown(
+        mut self,
+        mut notify_traders_stopped: mpsc::Receiver<bool>,
+    ) -> ShutdownReport {
+        let report = loop {
             // Action received commands from remote, or wait for all Traders to stop organically
             tokio::select! {
                 _ = notify_traders_stopped.recv() => {
-                    break;
+                    break self.shutdown_report(ShutdownReason::TradersStopped, vec![], vec![]);
                 },
 
                 command = self.command_rx.recv() => {
@@ -175,27 +316,48 @@ where
                             Command::FetchOpenPositions(positions_tx) => {
                                 self.fetch_open_positions(positions_tx).await;
                             },
-                            Command::Terminate(message) => {
-                                self.terminate_traders(message).await;
-                                break;
+                            Command::Terminate(message, ack_tx) => {
+                                let report = self.terminate_traders(message).await;
+                                if ack_tx.send(Ok(())).is_err() {
+                                    warn!(
+                                        why = "oneshot receiver dropped",
+                                        "cannot acknowledge Command::Terminate"
+                                    );
+                                }
+                                break report;
                             },
-                            Command::ExitPosition(market) => {
-                                self.exit_position(market).await;
+                            Command::ExitAllPositions(ack_tx) => {
+                                self.exit_all_positions(ack_tx).await;
                             },
-                            Command::ExitAllPositions => {
-                                self.exit_all_positions().await;
+                            command => {
+                                self.route_command(command).await;
                             },
                         }
                     } else {
                         // Terminate traders due to dropped receiver
-                        break;
+                        break self.shutdown_report(ShutdownReason::CommandTxDropped, vec![], vec![]);
                     }
                 }
             }
+        };
+
+        if report.is_clean() {
+            info!(reason = ?report.reason, "Engine shutdown complete");
+        } else {
+            warn!(
+                reason = ?report.reason,
+                unconfirmed_exits = ?report.unconfirmed_exits,
+                open_positions = report.open_positions.len(),
+                "Engine shutdown left Positions open"
+            );
         }
 
         // Print Trading Session Summary
-        self.generate_session_summary().printstd();
+        if self.print_summary {
+            self.generate_session_summary().printstd();
+        }
+
+        report
     }
 
     /// Runs each [`Trader`] it's own thread. Sends a message on the returned `mpsc::Receiver<bool>`
@@ -251,16 +413,18 @@ where
         }
     }
 
-    /// Terminate every running [`Trader`] associated with this [`Engine`].
-    async fn terminate_traders(&self, message: String) {
-        // Firstly, exit all Positions
-        self.exit_all_positions().await;
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    /// Terminate every running [`Trader`] associated with this [`Engine`], once they have
+    /// confirmed their open [`Position`]s have been exited (or failed to do so).
+    async fn terminate_traders(&self, message: String) -> ShutdownReport {
+        // Firstly, exit all Positions & wait for the Traders to confirm
+        let (confirmed_exits, unconfirmed_exits) = self.exit_all_positions_and_confirm().await;
 
         // Distribute Command::Terminate to all the Engine's Traders
+        let mut acks = Vec::with_capacity(self.trader_command_txs.len());
         for (market, command_tx) in self.trader_command_txs.iter() {
+            let (ack_tx, ack_rx) = oneshot::channel();
             if command_tx
-                .send(Command::Terminate(message.clone()))
+                .send(Command::Terminate(message.clone(), ack_tx))
                 .await
                 .is_err()
             {
@@ -269,93 +433,387 @@ where
                     why = "dropped receiver",
                     "failed to send Command::Terminate to Trader command_rx"
                 );
+                continue;
+            }
+            acks.push((market, ack_rx));
+        }
+
+        // Wait for the Traders to acknowledge they have stopped trading
+        let deadline = tokio::time::Instant::now() + self.shutdown_config.exit_timeout;
+        for (market, ack_rx) in acks {
+            if !matches!(
+                tokio::time::timeout_at(deadline, ack_rx).await,
+                Ok(Ok(Ok(())))
+            ) {
+                warn!(
+                    market = &*format!("{:?}", market),
+                    "Trader did not acknowledge Command::Terminate"
+                );
             }
         }
+
+        self.shutdown_report(
+            ShutdownReason::Terminated(message),
+            confirmed_exits,
+            unconfirmed_exits,
+        )
     }
 
-    /// Exit every open [`Position`] associated with this [`Engine`].
-    async fn exit_all_positions(&self) {
-        for (market, command_tx) in self.trader_command_txs.iter() {
+    /// Sends a [`Command::ExitPositionsAndConfirm`] to every [`Trader`] & waits for them to
+    /// confirm their exits, applying the [`ShutdownEscalation`] policy to any that fail. Returns
+    /// the [`Market`]s confirmed & the [`UnconfirmedExit`]s.
+    async fn exit_all_positions_and_confirm(&self) -> (Vec<Market>, Vec<UnconfirmedExit>) {
+        let max_attempts = match self.shutdown_config.escalation {
+            ShutdownEscalation::Abandon => 1,
+            ShutdownEscalation::CancelAndRetry { attempts } => attempts + 1,
+        };
+
+        let mut confirmed = Vec::with_capacity(self.trader_command_txs.len());
+        let mut unconfirmed = Vec::new();
+        let mut pending = self.trader_command_txs.keys().cloned().collect::<Vec<_>>();
+
+        for attempt in 1..=max_attempts {
+            // Escalate by cancelling any working orders before re-sending the exit orders
+            if attempt > 1 {
+                self.cancel_all_orders(&pending).await;
+            }
+
+            let mut acks = Vec::with_capacity(pending.len());
+            for market in pending.drain(..) {
+                let (ack_tx, ack_rx) = oneshot::channel();
+                match self.trader_command_txs.get(&market) {
+                    Some(command_tx)
+                        if command_tx
+                            .send(Command::ExitPositionsAndConfirm(market.clone(), ack_tx))
+                            .await
+                            .is_ok() =>
+                    {
+                        acks.push((market, ack_rx))
+                    }
+                    _ => unconfirmed.push(UnconfirmedExit {
+                        reason: EngineError::TraderUnavailable(market.clone()).to_string(),
+                        market,
+                        attempts: attempt,
+                    }),
+                }
+            }
+
+            let deadline = tokio::time::Instant::now() + self.shutdown_config.exit_timeout;
+            let mut failed = Vec::new();
+            for (market, ack_rx) in acks {
+                let reason = match tokio::time::timeout_at(deadline, ack_rx).await {
+                    Ok(Ok(Ok(()))) => {
+                        confirmed.push(market);
+                        continue;
+                    }
+                    Ok(Ok(Err(error))) => error.to_string(),
+                    Ok(Err(_)) => "Trader stopped before confirming exits".to_owned(),
+                    Err(_) => "timed out waiting for Trader to confirm exits".to_owned(),
+                };
+
+                warn!(
+                    market = &*format!("{:?}", market),
+                    attempt,
+                    %reason,
+                    "Trader failed to confirm Position exits"
+                );
+                failed.push(UnconfirmedExit {
+                    market,
+                    attempts: attempt,
+                    reason,
+                });
+            }
+
+            if attempt == max_attempts {
+                unconfirmed.extend(failed);
+                break;
+            }
+            pending = failed.into_iter().map(|exit| exit.market).collect();
+            if pending.is_empty() {
+                break;
+            }
+        }
+
+        (confirmed, unconfirmed)
+    }
+
+    /// Cancels every working order of the provided [`Market`]s, waiting up to the
+    /// [`ShutdownConfig::exit_timeout`] for the [`Trader`]s to acknowledge.
+    async fn cancel_all_orders(&self, markets: &[Market]) {
+        let deadline = tokio::time::Instant::now() + self.shutdown_config.exit_timeout;
+        for market in markets {
+            let command_tx = match self.trader_command_txs.get(market) {
+                Some(command_tx) => command_tx,
+                None => continue,
+            };
+
+            let (ack_tx, ack_rx) = oneshot::channel();
             if command_tx
-                .send(Command::ExitPosition(market.clone()))
+                .send(Command::CancelAllOrders(market.clone(), ack_tx))
                 .await
                 .is_err()
             {
-                error!(
+                continue;
+            }
+
+            if !matches!(
+                tokio::time::timeout_at(deadline, ack_rx).await,
+                Ok(Ok(Ok(_)))
+            ) {
+                warn!(
                     market = &*format!("{:?}", market),
-                    why = "dropped receiver",
-                    "failed to send Command::Terminate to Trader command_rx"
+                    "failed to cancel working orders before re-sending exit orders"
                 );
             }
         }
     }
 
-    /// Exit a [`Position`]. Uses the [`Market`] provided to route this [`Command`] to the relevant
-    /// [`Trader`] instance.
-    async fn exit_position(&self, market: Market) {
-        if let Some((market_ref, command_tx)) = self.trader_command_txs.get_key_value(&market) {
+    /// Generates a [`ShutdownReport`], fetching the [`Position`]s left open from the Portfolio.
+    fn shutdown_report(
+        &self,
+        reason: ShutdownReason,
+        confirmed_exits: Vec<Market>,
+        unconfirmed_exits: Vec<UnconfirmedExit>,
+    ) -> ShutdownReport {
+        let open_positions = self
+            .portfolio
+            .lock()
+            .get_open_markets_positions(self.engine_id, self.trader_command_txs.keys())
+            .unwrap_or_else(|error| {
+                error!(
+                    ?error,
+                    "failed to fetch open Positions when generating shutdown report"
+                );
+                vec![]
+            });
+
+        ShutdownReport {
+            time: self.clock.time(),
+            reason,
+            confirmed_exits,
+            unconfirmed_exits,
+            open_positions,
+        }
+    }
+
+    /// Exit every open [`Position`] associated with this [`Engine`], acknowledging once every
+    /// [`Trader`] has generated it's exit orders, or with the first error encountered.
+    async fn exit_all_positions(&self, ack_tx: oneshot::Sender<Result<(), EngineError>>) {
+        let mut result = Ok(());
+        let mut acks = Vec::with_capacity(self.trader_command_txs.len());
+        for (market, command_tx) in self.trader_command_txs.iter() {
+            let (trader_ack_tx, trader_ack_rx) = oneshot::channel();
             if command_tx
-                .send(Command::ExitPosition(market))
+                .send(Command::ExitPosition(market.clone(), trader_ack_tx))
                 .await
                 .is_err()
             {
                 error!(
-                    market = &*format!("{:?}", market_ref),
+                    market = &*format!("{:?}", market),
                     why = "dropped receiver",
-                    "failed to send Command::Terminate to Trader command_rx"
+                    "failed to send Command::ExitPosition to Trader command_rx"
                 );
+                result = result.and(Err(EngineError::TraderUnavailable(market.clone())));
+                continue;
             }
-        } else {
+            acks.push((market, trader_ack_rx));
+        }
+
+        let deadline = tokio::time::Instant::now() + self.shutdown_config.exit_timeout;
+        for (market, ack_rx) in acks {
+            let trader_result = match tokio::time::timeout_at(deadline, ack_rx).await {
+                Ok(Ok(trader_result)) => trader_result,
+                Ok(Err(_)) | Err(_) => Err(EngineError::TraderUnavailable(market.clone())),
+            };
+            result = result.and(trader_result);
+        }
+
+        if ack_tx.send(result).is_err() {
             warn!(
-                market = &*format!("{:?}", market),
-                why = "Engine has no trader_command_tx associated with provided Market",
-                "failed to exit Position"
+                why = "oneshot receiver dropped",
+                "cannot acknowledge Command::ExitAllPositions"
             );
         }
     }
 
-    /// Generate a trading session summary. Uses the Portfolio's statistics per [`Market`] in
-    /// combination with the average statistics across all [`Market`]s traded.
-    fn generate_session_summary(mut self) -> Table {
-        // Fetch statistics for each Market
-        let stats_per_market = self.trader_command_txs.into_keys().filter_map(|market| {
-            let market_id = MarketId::from(&market);
-
-            match self.portfolio.lock().get_statistics(&market_id) {
-                Ok(statistics) => Some((market_id.0, statistics)),
-                Err(error) => {
+    /// Routes a [`Command`] to the [`Trader`] associated with it's [`Market`], rejecting it if
+    /// there is no such [`Trader`] or it is no longer receiving [`Command`]s.
+    async fn route_command(&self, command: Command) {
+        let market = match command.market() {
+            Some(market) => market.clone(),
+            None => return,
+        };
+
+        match self.trader_command_txs.get(&market) {
+            Some(command_tx) => {
+                if let Err(mpsc::error::SendError(command)) = command_tx.send(command).await {
                     error!(
-                        ?error,
-                        ?market,
-                        "failed to get Market statistics when generating trading session summary"
+                        market = &*format!("{:?}", market),
+                        why = "dropped receiver",
+                        "failed to route Command to Trader command_rx"
                     );
-                    None
+                    command.reject(EngineError::TraderUnavailable(market));
                 }
             }
-        });
+            None => {
+                warn!(
+                    market = &*format!("{:?}", market),
+                    why = "Engine has no trader_command_tx associated with provided Market",
+                    "failed to route Command"
+                );
+                command.reject(EngineError::MarketNotFound(market));
+            }
+        }
+    }
 
-        // Generate average statistics across all markets using session's exited Positions
-        self.portfolio
+    /// Generate a trading session summary. Uses the Portfolio's statistics per [`Market`], and
+    /// per strategy trading each [`Market`] that exited attributed
+    /// [`Position`](crate::portfolio::position::Position)s, in combination with the average
+    /// statistics across all [`Market`]s traded.
+    fn generate_session_summary(mut self) -> Table {
+        // Fetch session's exited Positions
+        let exited_positions = self
+            .portfolio
             .lock()
             .get_exited_positions(self.engine_id)
-            .map(|exited_positions| {
-                self.statistics_summary.generate_summary(&exited_positions);
-            })
-            .unwrap_or_else(|error| {
+            .map_err(|error| {
                 warn!(
                     ?error,
                     why = "failed to get exited Positions from Portfolio's repository",
                     "failed to generate Statistics summary for trading session"
                 );
-            });
+            })
+            .ok();
+
+        // Determine the MarketIds of each Market & the strategies (or ensemble member strategies)
+        // attributed Positions on it
+        let market_ids = self
+            .trader_command_txs
+            .into_keys()
+            .flat_map(|market| {
+                let market_id = MarketId::from(&market);
+                let strategy_ids = exited_positions
+                    .iter()
+                    .flatten()
+                    .filter(|position| {
+                        MarketId::new(&position.exchange, &position.instrument) == market_id
+                    })
+                    .flat_map(|position| {
+                        position.strategy_id.clone().into_iter().chain(
+                            position
+                                .attributed_shares()
+                                .into_iter()
+                                .map(|(strategy_id, _)| strategy_id),
+                        )
+                    })
+                    .collect::<BTreeSet<_>>();
+
+                let strategy_market_ids = strategy_ids
+                    .into_iter()
+                    .map(|strategy_id| {
+                        (
+                            format!("{} [{}]", market_id.0, strategy_id),
+                            determine_strategy_market_id(&market_id, &strategy_id),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                std::iter::once((market_id.0.clone(), market_id)).chain(strategy_market_ids)
+            })
+            .collect::<Vec<_>>();
+
+        // Fetch statistics for each Market & strategy
+        let portfolio = self.portfolio.lock();
+        let stats_per_market = market_ids
+            .into_iter()
+            .filter_map(
+                |(name, market_id)| match portfolio.get_statistics(&market_id) {
+                    Ok(statistics) => Some((name, statistics)),
+                    Err(error) => {
+                        error!(
+                        ?error,
+                        ?market_id,
+                        "failed to get Market statistics when generating trading session summary"
+                    );
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+        drop(portfolio);
 
-        // Combine Total & Per-Market Statistics Into Table
+        // Generate average statistics across all markets using session's exited Positions
+        if let Some(exited_positions) = exited_positions {
+            self.statistics_summary.generate_summary(&exited_positions);
+        }
+
+        // Combine Total, Per-Market & Per-Strategy Statistics Into Table
         crate::statistic::summary::combine(
-            stats_per_market.chain([("Total".to_owned(), self.statistics_summary)]),
+            stats_per_market
+                .into_iter()
+                .chain([("Total".to_owned(), self.statistics_summary)]),
         )
     }
 }
 
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send + 'static,
+    Statistic: PositionSummariser + TableBuilder + Serialize + Send + 'static,
+    Portfolio: PositionHandler
+        + StatisticHandler<Statistic>
+        + MarketUpdater
+        + OrderGenerator
+        + FillUpdater
+        + Send
+        + 'static,
+    Data: MarketGenerator<MarketEvent<DataKind>>
+        + AsyncMarketGenerator<MarketEvent<DataKind>>
+        + Send
+        + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: AsyncExecutionClient + Send + 'static,
+{
+    /// Run the trading [`Engine`], running each [`Trader`] as an asynchronous task on the tokio
+    /// runtime rather than on it's own thread. Otherwise behaves identically to
+    /// [`Engine::run`].
+    pub async fn run_async(mut self) -> ShutdownReport {
+        // Run Traders as tasks & send notification when they have stopped organically
+        let notify_traders_stopped = self.run_trader_tasks();
+
+        self.run_until_shutdown(notify_traders_stopped).await
+    }
+
+    /// Runs each [`Trader`] as an asynchronous task. Sends a message on the returned
+    /// `mpsc::Receiver<bool>` if all the [`Trader`]s have stopped organically (eg/ due to a
+    /// finished [`MarketEvent`] feed).
+    fn run_trader_tasks(&mut self) -> mpsc::Receiver<bool> {
+        let task_handles = std::mem::take(&mut self.traders)
+            .into_iter()
+            .map(|trader| tokio::spawn(trader.run_async()))
+            .collect::<Vec<_>>();
+
+        // Create channel to notify the Engine when the Traders have stopped organically
+        let (notify_tx, notify_rx) = mpsc::channel(1);
+
+        tokio::spawn(async move {
+            for handle in task_handles {
+                if let Err(err) = handle.await {
+                    error!(
+                        error = &*format!("{:?}", err),
+                        "Trader task has panicked during execution",
+                    )
+                }
+            }
+
+            let _ = notify_tx.send(true).await;
+        });
+
+        notify_rx
+    }
+}
+
 /// Builder to construct [`Engine`] instances.
 #[derive(Debug, Default)]
 pub struct EngineBuilder<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -373,6 +831,9 @@ where
     traders: Option<Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>>,
     trader_command_txs: Option<HashMap<Market, mpsc::Sender<Command>>>,
     statistics_summary: Option<Statistic>,
+    shutdown_config: Option<ShutdownConfig>,
+    clock: Option<SharedClock>,
+    print_summary: Option<bool>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -398,6 +859,9 @@ where
             traders: None,
             trader_command_txs: None,
             statistics_summary: None,
+            shutdown_config: None,
+            clock: None,
+            print_summary: None,
         }
     }
 
@@ -446,6 +910,29 @@ where
         }
     }
 
+    pub fn shutdown_config(self, value: ShutdownConfig) -> Self {
+        Self {
+            shutdown_config: Some(value),
+            ..self
+        }
+    }
+
+    pub fn clock(self, value: SharedClock) -> Self {
+        Self {
+            clock: Some(value),
+            ..self
+        }
+    }
+
+    /// Determines if the trading session summary is printed to stdout once the [`Engine`] has
+    /// shutdown. Defaults to true.
+    pub fn print_summary(self, value: bool) -> Self {
+        Self {
+            print_summary: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -468,6 +955,9 @@ where
             statistics_summary: self
                 .statistics_summary
                 .ok_or(EngineError::BuilderIncomplete("statistics_summary"))?,
+            shutdown_config: self.shutdown_config.unwrap_or_default(),
+            clock: self.clock.unwrap_or_default(),
+            print_summary: self.print_summary.unwrap_or(true),
         })
     }
 }
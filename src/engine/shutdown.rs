@@ -0,0 +1,78 @@
+use crate::portfolio::position::Position;
+use barter_integration::model::Market;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Configures how an [`Engine`](super::Engine) exits it's open [`Position`]s when it receives a
+/// [`Command::Terminate`](super::Command::Terminate).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ShutdownConfig {
+    /// Maximum duration to wait for every [`Trader`](super::trader::Trader) to confirm it's exit
+    /// orders have been filled, per attempt.
+    pub exit_timeout: Duration,
+    /// Action taken if a [`Trader`](super::trader::Trader) fails to confirm it's exits.
+    pub escalation: ShutdownEscalation,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            exit_timeout: Duration::from_secs(5),
+            escalation: ShutdownEscalation::Abandon,
+        }
+    }
+}
+
+/// Escalation policy applied when a [`Trader`](super::trader::Trader) fails to confirm it's
+/// exits within the [`ShutdownConfig::exit_timeout`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum ShutdownEscalation {
+    /// Terminate the [`Trader`](super::trader::Trader), leaving any [`Position`]s it failed to
+    /// exit open.
+    Abandon,
+    /// Cancel the [`Trader`](super::trader::Trader)'s working orders & re-send it's exit orders,
+    /// up to the provided number of additional attempts, before abandoning.
+    CancelAndRetry { attempts: usize },
+}
+
+/// Reason an [`Engine`](super::Engine) stopped running.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum ShutdownReason {
+    /// [`Command::Terminate`](super::Command::Terminate) received with the provided message.
+    Terminated(String),
+    /// Every [`Trader`](super::trader::Trader) stopped organically (eg/ finished market feed).
+    TradersStopped,
+    /// Remote [`Command`](super::Command) transmitter was dropped.
+    CommandTxDropped,
+}
+
+/// [`Market`] whose [`Trader`](super::trader::Trader) failed to confirm it's exits.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct UnconfirmedExit {
+    pub market: Market,
+    /// Number of exit attempts made.
+    pub attempts: usize,
+    /// Reason the final attempt failed.
+    pub reason: String,
+}
+
+/// Structured outcome of an [`Engine`](super::Engine) shutdown.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ShutdownReport {
+    pub time: DateTime<Utc>,
+    pub reason: ShutdownReason,
+    /// [`Market`]s whose [`Trader`](super::trader::Trader) confirmed every exit order was filled.
+    pub confirmed_exits: Vec<Market>,
+    /// [`Market`]s whose [`Trader`](super::trader::Trader) failed to confirm it's exits.
+    pub unconfirmed_exits: Vec<UnconfirmedExit>,
+    /// [`Position`]s still open once the [`Engine`](super::Engine) stopped.
+    pub open_positions: Vec<Position>,
+}
+
+impl ShutdownReport {
+    /// Determines if the shutdown left no [`Position`]s open & every exit was confirmed.
+    pub fn is_clean(&self) -> bool {
+        self.unconfirmed_exits.is_empty() && self.open_positions.is_empty()
+    }
+}
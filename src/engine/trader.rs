@@ -1,19 +1,34 @@
 use super::{error::EngineError, Command};
 use crate::portfolio::OrderGeneratorResult;
-use crate::strategy::SignalInstrumentPositionsExit;
+use crate::strategy::{
+    ExitTrigger, Signal, SignalExtra, SignalInstrumentPositionsExit, SignalIntent,
+    SignalPositionExit, StrategyId,
+};
 use crate::{
-    data::{Feed, MarketGenerator},
+    clock::{Clock, SharedClock},
+    data::{AsyncMarketGenerator, Feed, MarketGenerator},
     event::{Event, MessageTransmitter},
-    execution::ExecutionClient,
-    portfolio::{position::PositionUpdateByMarket, FillUpdater, MarketUpdater, OrderGenerator},
+    execution::{
+        order::{ExecutionReport, OrderId, OrderState, OrderUpdate},
+        AsyncExecutionClient, ExecutionClient, FillEvent,
+    },
+    portfolio::{
+        position::PositionUpdateByMarket, protection::PriceRange, FillUpdater, MarketUpdater,
+        OrderGenerator,
+    },
     strategy::{SignalForceExit, SignalGenerator},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::Market;
 use parking_lot::Mutex;
 use serde::Serialize;
-use std::{collections::VecDeque, fmt::Debug, marker::PhantomData, sync::Arc};
-use tokio::sync::mpsc;
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Debug,
+    marker::PhantomData,
+    sync::Arc,
+};
+use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -44,16 +59,22 @@ where
     pub data: Data,
     /// Strategy that implements [`SignalGenerator`].
     pub strategy: Strategy,
+    /// Optional [`StrategyId`] attributed to every [`Signal`](crate::strategy::Signal) the
+    /// strategy generates without one.
+    pub strategy_id: Option<StrategyId>,
     /// Execution handler that implements [`ExecutionClient`].
     pub execution: Execution,
+    /// [`Clock`] advanced by every [`MarketEvent`] & used to timestamp generated exit
+    /// [`Signal`](crate::strategy::Signal)s.
+    pub clock: SharedClock,
     _statistic_marker: PhantomData<Statistic>,
 }
 
 /// Trader instance capable of trading a single market pair with it's own Data Handler, Strategy &
 /// Execution Handler, as well as shared access to a global Portfolio instance. It has a many-to-1
 /// relationship with an Engine/Portfolio. A graceful remote shutdown is made possible by sending
-/// a [`Command::Terminate`] to the Trader's
-/// mpsc::Receiver command_rx.
+/// a [`Command::ExitPositionsAndConfirm`], followed by a [`Command::Terminate`] once the exits
+/// have been confirmed, to the Trader's mpsc::Receiver command_rx.
 #[derive(Debug)]
 pub struct Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
 where
@@ -83,8 +104,22 @@ where
     data: Data,
     /// Strategy that implements [`SignalGenerator`].
     strategy: Strategy,
+    /// Optional [`StrategyId`] attributed to every [`Signal`](crate::strategy::Signal) the
+    /// strategy generates without one.
+    strategy_id: Option<StrategyId>,
     /// Execution handler that implements [`ExecutionClient`].
     execution: Execution,
+    /// [`Clock`] advanced by every [`MarketEvent`] & used to timestamp generated exit signals.
+    clock: SharedClock,
+    /// Determines if [`Signal`](crate::strategy::Signal) generation has been paused via a
+    /// [`Command::PauseTrader`].
+    paused: bool,
+    /// Close price of the latest [`MarketEvent`], used to price exits requested remotely.
+    market_close: Option<f64>,
+    /// Exit orders awaiting confirmation following a [`Command::ExitPositionsAndConfirm`].
+    pending_exit: Option<PendingExit>,
+    /// Entry [`Signal`] awaiting the exit of the opposite Positions it reverses.
+    pending_entry: Option<PendingEntry>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -115,7 +150,13 @@ where
             portfolio: lego.portfolio,
             data: lego.data,
             strategy: lego.strategy,
+            strategy_id: lego.strategy_id,
             execution: lego.execution,
+            clock: lego.clock,
+            paused: false,
+            market_close: None,
+            pending_exit: None,
+            pending_entry: None,
             _statistic_marker: PhantomData::default(),
         }
     }
@@ -125,176 +166,575 @@ where
         TraderBuilder::new()
     }
 
-    /// Run the trading event-loop for this [`Trader`] instance. Loop will run until [`Trader`]
-    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
-    /// [`MarketGenerator`] yields [`Feed::Finished`].
+    /// Run the trading event-loop for this [`Trader`] instance on the current thread. Loop will
+    /// run until [`Trader`] receives a [`Command::Terminate`] via the mpsc::Receiver command_rx,
+    /// or the [`MarketGenerator`] yields [`Feed::Finished`].
     pub fn run(mut self) {
         // Run trading loop for this Trader instance
         'trading: loop {
             // Check for new remote Commands before continuing to generate another MarketEvent
             while let Some(command) = self.receive_remote_command() {
-                match command {
-                    Command::Terminate(_) => break 'trading,
-                    Command::ExitPosition(market) => {
-                        self.event_q
-                            .push_back(Event::SignalForceExit(SignalForceExit::from(market)));
-                    }
-                    _ => continue,
+                if !self.handle_command(command) {
+                    break 'trading;
                 }
             }
 
+            // Populate event_q with any OrderUpdates & Fills received asynchronously from the
+            // ExecutionClient since the last MarketEvent
+            self.poll_execution_reports();
+
             // If the Feed<MarketEvent> yields, populate event_q with the next MarketEvent
-            match self.data.next() {
-                Feed::Next(market) => {
-                    self.event_tx.send(Event::Market(market.clone()));
-                    self.event_q.push_back(Event::Market(market));
-                }
-                Feed::Unhealthy => {
-                    warn!(
-                        engine_id = %self.engine_id,
-                        market = ?self.market,
-                        action = "continuing while waiting for healthy Feed",
-                        "MarketFeed unhealthy"
-                    );
-                    continue 'trading;
-                }
-                Feed::Finished => break 'trading,
+            let feed = self.data.next();
+            if !self.handle_feed(feed) {
+                break 'trading;
             }
 
-            // Handle Events in the event_q
-            // '--> While loop will break when event_q is empty and requires another MarketEvent
-            while let Some(event) = self.event_q.pop_front() {
-                match event {
-                    Event::Market(market) => {
-                        if let Some(signal) = self.strategy.generate_signal(&market) {
-                            self.event_tx.send(Event::Signal(signal.clone()));
-                            self.event_q.push_back(Event::Signal(signal));
-                        }
+            self.process_event_q();
+        }
 
-                        for position_update in self
-                            .portfolio
-                            .lock()
-                            .update_from_market(&market)
-                            .expect("failed to update Portfolio from market")
-                        {
-                            self.event_tx
-                                .send(Event::PositionUpdate(position_update.clone()));
-                            if let PositionUpdateByMarket::SignalExit(signal) = position_update {
-                                self.event_q.push_back(Event::SignalPositionExit(signal));
-                            }
-                        }
+        debug!(
+            engine_id = &*self.engine_id.to_string(),
+            market = &*format!("{:?}", self.market),
+            "Trader trading loop stopped"
+        );
+    }
+
+    /// Handles a remote [`Command`]. Returns false if the [`Trader`] should stop trading.
+    fn handle_command(&mut self, command: Command) -> bool {
+        match command {
+            Command::Terminate(_, ack_tx) => {
+                // Nothing awaits the acknowledgement of a Terminate synthesised by the Trader
+                let _ = ack_tx.send(Ok(()));
+                return false;
+            }
+            command => self.action_command(command),
+        }
+        true
+    }
+
+    /// Populates the event_q with any [`ExecutionReport`]s the [`ExecutionClient`] has received
+    /// asynchronously.
+    fn poll_execution_reports(&mut self) {
+        for report in self.execution.poll_reports() {
+            let event = Event::from(report);
+            self.event_tx.send(event.clone());
+            self.event_q.push_back(event);
+        }
+    }
+
+    /// Handles the next [`Feed`] yielded by the market data handler, populating the event_q with
+    /// the next [`MarketEvent`]. Returns false if the [`Feed`] is finished.
+    fn handle_feed(&mut self, feed: Feed<MarketEvent<DataKind>>) -> bool {
+        match feed {
+            Feed::Next(market) => {
+                self.event_tx.send(Event::Market(market.clone()));
+                self.event_q.push_back(Event::Market(market));
+            }
+            Feed::Unhealthy => {
+                warn!(
+                    engine_id = %self.engine_id,
+                    market = ?self.market,
+                    action = "continuing while waiting for healthy Feed",
+                    "MarketFeed unhealthy"
+                );
+            }
+            Feed::Finished => return false,
+        }
+        true
+    }
+
+    /// Handles every [`Event`] in the event_q, until it is empty and requires another
+    /// [`MarketEvent`].
+    fn process_event_q(&mut self) {
+        // Handle Events in the event_q
+        // '--> While loop will break when event_q is empty and requires another MarketEvent
+        while let Some(event) = self.event_q.pop_front() {
+            match event {
+                Event::Market(market) => {
+                    self.clock.advance(market.exchange_time);
+
+                    // Resting orders crossed by this MarketEvent are filled before any new
+                    // Signal is generated
+                    let reports = self
+                        .execution
+                        .update_from_market(&market)
+                        .expect("failed to update ExecutionClient from market");
+                    self.process_reports(reports);
+
+                    if let Some(range) = PriceRange::from_market(&market) {
+                        self.market_close = Some(range.close);
                     }
 
-                    Event::Signal(signal) => {
-                        let generate_order_result = {
-                            self.portfolio
-                                .lock()
-                                .generate_order(&signal)
-                                .expect("failed to generate order")
-                        };
-                        match generate_order_result {
-                            OrderGeneratorResult::OnlyExit(close_signal) => {
-                                for order in self
-                                    .portfolio
-                                    .lock()
-                                    .generate_instrument_exit_order(close_signal)
-                                    .expect("failed to generate forced exit orders")
-                                {
-                                    self.event_tx.send(Event::OrderNew(order.clone()));
-                                    self.event_q.push_back(Event::OrderNew(order));
+                    // Strategy is always updated so it's indicators are current once resumed,
+                    // but any intents generated while paused are discarded
+                    let intents = self.strategy.generate_intents(&market);
+                    if !self.paused {
+                        for mut intent in intents {
+                            if let SignalIntent::Signal(signal) = &mut intent {
+                                if signal.strategy_id.is_none() {
+                                    signal.strategy_id = self.strategy_id.clone();
                                 }
                             }
-                            OrderGeneratorResult::OnlyNew(order) => {
-                                self.event_tx.send(Event::OrderNew(order.clone()));
-                                self.event_q.push_back(Event::OrderNew(order));
-                            }
-                            OrderGeneratorResult::ExitAndNew(close_signal) => {
-                                for order in self
-                                    .portfolio
-                                    .lock()
-                                    .generate_instrument_exit_order(close_signal)
-                                    .expect("failed to generate forced exit orders")
-                                {
-                                    self.event_tx.send(Event::OrderNew(order.clone()));
-                                    self.event_q.push_back(Event::OrderNew(order));
-                                }
 
-                                // signal push back again, to generate open order
-                                self.event_q.push_back(Event::Signal(signal));
-                            }
-                            OrderGeneratorResult::None => {}
+                            let event = Event::from(intent);
+                            self.event_tx.send(event.clone());
+                            self.event_q.push_back(event);
                         }
                     }
-                    Event::SignalPositionExit(signal) => {
-                        if let Some(order) = self
-                            .portfolio
-                            .lock()
-                            .generate_exit_order(signal)
-                            .expect("failed to generate position exit order")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
+
+                    for position_update in self
+                        .portfolio
+                        .lock()
+                        .update_from_market(&market)
+                        .expect("failed to update Portfolio from market")
+                    {
+                        self.event_tx
+                            .send(Event::PositionUpdate(position_update.clone()));
+                        if let PositionUpdateByMarket::SignalExit(signal) = position_update {
+                            self.event_q.push_back(Event::SignalPositionExit(signal));
                         }
                     }
+                }
 
-                    Event::SignalInstrumentExit(signal) => {
-                        for order in self
-                            .portfolio
+                Event::Signal(signal) => {
+                    let generate_order_result = {
+                        self.portfolio
                             .lock()
-                            .generate_instrument_exit_order(signal)
-                            .expect("failed to generate forced exit orders")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
+                            .generate_order(&signal)
+                            .expect("failed to generate order")
+                    };
+                    match generate_order_result {
+                        OrderGeneratorResult::OnlyExit(close_signal) => {
+                            for order in self
+                                .portfolio
+                                .lock()
+                                .generate_instrument_exit_order(close_signal)
+                                .expect("failed to generate forced exit orders")
+                            {
+                                self.event_tx.send(Event::OrderNew(order.clone()));
+                                self.event_q.push_back(Event::OrderNew(order));
+                            }
                         }
-                    }
-                    Event::SignalForceExit(signal_force_exit) => {
-                        for order in self
-                            .portfolio
-                            .lock()
-                            .generate_instrument_exit_order(SignalInstrumentPositionsExit::from(
-                                signal_force_exit,
-                            ))
-                            .expect("failed to generate forced exit orders")
-                        {
+                        OrderGeneratorResult::OnlyNew(order) => {
                             self.event_tx.send(Event::OrderNew(order.clone()));
                             self.event_q.push_back(Event::OrderNew(order));
                         }
+                        OrderGeneratorResult::ExitAndNew(close_signal) => {
+                            self.exit_and_defer_entry(close_signal, signal);
+                        }
+                        OrderGeneratorResult::None => {}
                     }
+                }
+                Event::SignalPositionExit(signal) => {
+                    if let Some(order) = self
+                        .portfolio
+                        .lock()
+                        .generate_exit_order(signal)
+                        .expect("failed to generate position exit order")
+                    {
+                        self.event_tx.send(Event::OrderNew(order.clone()));
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+                }
 
-                    Event::OrderNew(order) => {
-                        let fill = {
-                            let fill = self
-                                .execution
-                                .generate_fill(&order)
-                                .expect("failed to generate Fill");
-                            self.event_tx.send(Event::Fill(fill.clone()));
-                            fill
-                        };
-
-                        // It is processed immediately afterwards to prevent the intermediate
-                        // balance from being updated when there are two OrderEvents at the
-                        // same time
-                        let fill_side_effect_events = self
-                            .portfolio
+                Event::SignalPositionAmend(signal) => {
+                    let amended_position = {
+                        self.portfolio
                             .lock()
-                            .update_from_fill(&fill)
-                            .expect("failed to update Portfolio from fill");
+                            .amend_position(&signal)
+                            .expect("failed to amend position")
+                    };
+                    if let Some(position) = amended_position {
+                        debug!(
+                            signal_id = %position.signal_id,
+                            signal_extra = ?position.signal_extra,
+                            "amended Position protective exit levels"
+                        );
+
+                        let reports = self
+                            .execution
+                            .amend_protective_orders(position.signal_id, position.signal_extra)
+                            .expect("failed to amend protective exit orders");
+
+                        self.process_reports(reports);
+                    }
+                }
+
+                Event::SignalInstrumentExit(signal) => {
+                    for order in self
+                        .portfolio
+                        .lock()
+                        .generate_instrument_exit_order(signal)
+                        .expect("failed to generate forced exit orders")
+                    {
+                        self.event_tx.send(Event::OrderNew(order.clone()));
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+                }
+                Event::SignalForceExit(signal_force_exit) => {
+                    for order in self
+                        .portfolio
+                        .lock()
+                        .generate_instrument_exit_order(SignalInstrumentPositionsExit::from(
+                            signal_force_exit,
+                        ))
+                        .expect("failed to generate forced exit orders")
+                    {
+                        self.event_tx.send(Event::OrderNew(order.clone()));
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+                }
+
+                Event::OrderNew(order) => {
+                    let reports = self
+                        .execution
+                        .submit_order(&order)
+                        .expect("failed to submit order");
+
+                    self.process_reports(reports);
+                }
+
+                Event::OrderUpdate(update) => {
+                    self.log_order_update(&update);
+                    self.confirm_exit(&update);
+                    self.abandon_pending_entry(&update);
+                }
+
+                Event::Fill(fill) => self.update_portfolio_from_fill(&fill),
+                _ => {}
+            }
+        }
+    }
+
+    /// Actions a [`Command`] routed to this [`Trader`], acknowledging the outcome on the
+    /// `oneshot::Sender` provided with the [`Command`].
+    fn action_command(&mut self, command: Command) {
+        match command {
+            Command::ExitPosition(market, ack_tx) => {
+                let result = self.exit_positions(market);
+                acknowledge(ack_tx, result);
+            }
+            Command::ExitPositionBySignal(_, signal_id, ack_tx) => {
+                let result = self.exit_position_by_signal(signal_id);
+                acknowledge(ack_tx, result);
+            }
+            Command::CancelOrder(_, order_id, ack_tx) => {
+                let result = self
+                    .execution
+                    .cancel_order(order_id)
+                    .map(|reports| self.process_cancel_reports(reports))
+                    .map_err(EngineError::from);
+                acknowledge(ack_tx, result);
+            }
+            Command::CancelAllOrders(_, ack_tx) => {
+                let result = self
+                    .execution
+                    .cancel_all_orders()
+                    .map(|reports| self.process_cancel_reports(reports))
+                    .map_err(EngineError::from);
+                acknowledge(ack_tx, result);
+            }
+            Command::PauseTrader(_, ack_tx) => {
+                self.paused = true;
+                info!(engine_id = %self.engine_id, market = ?self.market, "Trader paused");
+                acknowledge(ack_tx, Ok(()));
+            }
+            Command::ResumeTrader(_, ack_tx) => {
+                self.paused = false;
+                info!(engine_id = %self.engine_id, market = ?self.market, "Trader resumed");
+                acknowledge(ack_tx, Ok(()));
+            }
+            Command::ExitPositionsAndConfirm(market, ack_tx) => {
+                self.exit_positions_and_confirm(market, ack_tx);
+            }
+            Command::UpdateStrategyParams(_, params, ack_tx) => {
+                let result = self
+                    .strategy
+                    .update_params(params)
+                    .map_err(EngineError::from);
+                acknowledge(ack_tx, result);
+            }
+            _ => {}
+        }
+    }
+
+    /// Generates exit [`OrderEvent`](crate::portfolio::OrderEvent)s for every open
+    /// [`Position`](crate::portfolio::position::Position) of the provided [`Market`].
+    fn exit_positions(&mut self, market: Market) -> Result<(), EngineError> {
+        let orders = self.portfolio.lock().generate_instrument_exit_order(
+            SignalInstrumentPositionsExit::from(self.signal_force_exit(market)),
+        )?;
+
+        for order in orders {
+            self.event_tx.send(Event::OrderNew(order.clone()));
+            self.event_q.push_back(Event::OrderNew(order));
+        }
+        Ok(())
+    }
+
+    /// Generates an exit [`OrderEvent`](crate::portfolio::OrderEvent) for the open
+    /// [`Position`](crate::portfolio::position::Position) entered by the provided signal_id,
+    /// priced at the latest [`MarketEvent`] close.
+    fn exit_position_by_signal(&mut self, signal_id: Uuid) -> Result<(), EngineError> {
+        let price = self
+            .market_close
+            .ok_or(EngineError::NoMarketPrice(signal_id))?;
+
+        let signal = SignalPositionExit {
+            signal_id,
+            time: self.clock.time(),
+            exchange: self.market.exchange.clone(),
+            instrument: self.market.instrument.clone(),
+            signal_extra: SignalExtra::default(),
+            trigger: ExitTrigger::Manual,
+            price,
+        };
+
+        let order = self
+            .portfolio
+            .lock()
+            .generate_exit_order(signal)?
+            .ok_or(EngineError::PositionNotFound(signal_id))?;
+
+        self.event_tx.send(Event::OrderNew(order.clone()));
+        self.event_q.push_back(Event::OrderNew(order));
+        Ok(())
+    }
+
+    /// Constructs a [`SignalForceExit`] for the provided [`Market`], timestamped by the [`Clock`].
+    fn signal_force_exit(&self, market: Market) -> SignalForceExit {
+        SignalForceExit {
+            time: self.clock.time(),
+            ..SignalForceExit::from(market)
+        }
+    }
 
-                        self.event_tx.send_many(fill_side_effect_events);
+    /// Generates & immediately submits exit orders for every open
+    /// [`Position`](crate::portfolio::position::Position) of the provided [`Market`]. The
+    /// `oneshot::Sender` is acknowledged once every exit order has been filled, or as soon as one
+    /// reaches a terminal [`OrderState`] without being filled.
+    fn exit_positions_and_confirm(
+        &mut self,
+        market: Market,
+        ack_tx: oneshot::Sender<Result<(), EngineError>>,
+    ) {
+        let orders = match self.portfolio.lock().generate_instrument_exit_order(
+            SignalInstrumentPositionsExit::from(self.signal_force_exit(market)),
+        ) {
+            Ok(orders) => orders,
+            Err(error) => return acknowledge(ack_tx, Err(error.into())),
+        };
+
+        if orders.is_empty() {
+            return acknowledge(ack_tx, Ok(()));
+        }
+
+        // Any previous request is superseded before its exit orders were confirmed, so is
+        // acknowledged with an error rather than reporting its Positions as exited
+        if let Some(previous) = self.pending_exit.replace(PendingExit {
+            order_ids: orders.iter().map(|order| order.order_id).collect(),
+            ack_tx,
+        }) {
+            acknowledge(previous.ack_tx, Err(EngineError::ExitSuperseded));
+        }
+
+        for order in orders {
+            self.event_tx.send(Event::OrderNew(order.clone()));
+            match self.execution.submit_order(&order) {
+                Ok(reports) => self.process_reports(reports),
+                Err(error) => {
+                    if let Some(pending) = self.pending_exit.take() {
+                        acknowledge(pending.ack_tx, Err(error.into()));
                     }
+                    return;
+                }
+            }
+        }
+    }
 
-                    Event::Fill(_fill) => {
-                        panic!("FillEvent can only be processed at the same time as OrderEvent");
+    /// Tracks the progress of exit orders awaiting confirmation, acknowledging the pending
+    /// [`Command::ExitPositionsAndConfirm`] once they have all been filled, or as soon as one is
+    /// not.
+    fn confirm_exit(&mut self, update: &OrderUpdate) {
+        let pending = match &mut self.pending_exit {
+            Some(pending) if pending.order_ids.contains(&update.order_id) => pending,
+            _ => return,
+        };
+
+        match update.state {
+            OrderState::Filled => {
+                pending.order_ids.remove(&update.order_id);
+                if pending.order_ids.is_empty() {
+                    if let Some(pending) = self.pending_exit.take() {
+                        acknowledge(pending.ack_tx, Ok(()));
                     }
-                    _ => {}
                 }
             }
+            state if state.is_terminal() => {
+                if let Some(pending) = self.pending_exit.take() {
+                    acknowledge(
+                        pending.ack_tx,
+                        Err(EngineError::ExitOrderNotFilled(update.order_id, state)),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Generates & queues exit orders for the opposite Positions the entry [`Signal`] reverses,
+    /// deferring the entry until they have been exited. If exits are already working for a
+    /// previous entry, only the entry [`Signal`] is replaced.
+    fn exit_and_defer_entry(
+        &mut self,
+        close_signal: SignalInstrumentPositionsExit,
+        signal: Signal,
+    ) {
+        if let Some(pending) = &mut self.pending_entry {
+            pending.signal = signal;
+            return;
+        }
+
+        let orders = self
+            .portfolio
+            .lock()
+            .generate_instrument_exit_order(close_signal)
+            .expect("failed to generate forced exit orders");
+
+        if orders.is_empty() {
+            warn!(
+                engine_id = %self.engine_id,
+                market = ?self.market,
+                signal_id = %signal.signal_id,
+                "dropping entry Signal since no opposite Position exit was generated"
+            );
+            return;
+        }
+
+        self.pending_entry = Some(PendingEntry {
+            signal,
+            position_signal_ids: orders
+                .iter()
+                .filter_map(|order| order.position_signal_id)
+                .collect(),
+            order_ids: orders.iter().map(|order| order.order_id).collect(),
+        });
+
+        for order in orders {
+            self.event_tx.send(Event::OrderNew(order.clone()));
+            self.event_q.push_back(Event::OrderNew(order));
+        }
+    }
+
+    /// Re-queues the pending entry [`Signal`] once every opposite Position it reverses has been
+    /// exited.
+    fn confirm_entry_exit(&mut self, position_signal_id: Uuid) {
+        let pending = match &mut self.pending_entry {
+            Some(pending) if pending.position_signal_ids.contains(&position_signal_id) => pending,
+            _ => return,
+        };
+
+        pending.position_signal_ids.remove(&position_signal_id);
+        if pending.position_signal_ids.is_empty() {
+            if let Some(pending) = self.pending_entry.take() {
+                self.event_q.push_back(Event::Signal(pending.signal));
+            }
+        }
+    }
+
+    /// Drops the pending entry [`Signal`] if one of the exit orders it awaits reaches a terminal
+    /// [`OrderState`] without being filled, since the opposite Position remains open.
+    fn abandon_pending_entry(&mut self, update: &OrderUpdate) {
+        let abandoned = match &self.pending_entry {
+            Some(pending) => {
+                pending.order_ids.contains(&update.order_id)
+                    && update.state.is_terminal()
+                    && update.state != OrderState::Filled
+            }
+            None => false,
+        };
 
-            debug!(
-                engine_id = &*self.engine_id.to_string(),
-                market = &*format!("{:?}", self.market),
-                "Trader trading loop stopped"
+        if let (true, Some(pending)) = (abandoned, self.pending_entry.take()) {
+            warn!(
+                engine_id = %self.engine_id,
+                market = ?self.market,
+                signal_id = %pending.signal.signal_id,
+                order_id = %update.order_id,
+                state = ?update.state,
+                "dropping entry Signal since an opposite Position exit order was not filled"
+            );
+        }
+    }
+
+    /// Processes the [`ExecutionReport`]s generated by cancelling orders, returning the
+    /// [`OrderUpdate`]s to acknowledge the cancel [`Command`] with.
+    fn process_cancel_reports(&mut self, reports: Vec<ExecutionReport>) -> Vec<OrderUpdate> {
+        let updates = reports
+            .iter()
+            .filter_map(|report| match report {
+                ExecutionReport::Update(update) => Some(update.clone()),
+                ExecutionReport::Fill(_) => None,
+            })
+            .collect();
+
+        self.process_reports(reports);
+        updates
+    }
+
+    /// Sends each [`ExecutionReport`] generated synchronously by the [`ExecutionClient`], updating
+    /// the Portfolio from any [`FillEvent`]s.
+    fn process_reports(&mut self, reports: Vec<ExecutionReport>) {
+        for report in reports {
+            match report {
+                ExecutionReport::Update(update) => {
+                    self.log_order_update(&update);
+                    self.confirm_exit(&update);
+                    self.abandon_pending_entry(&update);
+                    self.event_tx.send(Event::OrderUpdate(update));
+                }
+                ExecutionReport::Fill(fill) => {
+                    self.event_tx.send(Event::Fill(fill.clone()));
+
+                    // It is processed immediately afterwards to prevent the intermediate
+                    // balance from being updated when there are two OrderEvents at the
+                    // same time
+                    self.update_portfolio_from_fill(&fill);
+                }
+            }
+        }
+    }
+
+    /// Updates the Portfolio from the input [`FillEvent`], sending any resulting side effect
+    /// [`Event`]s.
+    fn update_portfolio_from_fill(&mut self, fill: &FillEvent) {
+        let fill_side_effect_events = self
+            .portfolio
+            .lock()
+            .update_from_fill(fill)
+            .expect("failed to update Portfolio from fill");
+
+        let position_exited = fill.decision.is_exit()
+            && fill_side_effect_events.iter().any(|event| {
+                matches!(event, Event::PositionExit(exit) if exit.remaining_quantity == 0.0)
+            });
+
+        self.event_tx.send_many(fill_side_effect_events);
+
+        if let (true, Some(position_signal_id)) = (position_exited, fill.position_signal_id) {
+            self.confirm_entry_exit(position_signal_id);
+        }
+    }
+
+    /// Logs [`OrderUpdate`]s that were not executed as requested.
+    fn log_order_update(&self, update: &OrderUpdate) {
+        if let OrderState::Rejected | OrderState::Expired = update.state {
+            warn!(
+                engine_id = %self.engine_id,
+                market = ?self.market,
+                order_id = %update.order_id,
+                state = ?update.state,
+                reason = ?update.reason,
+                "order was not executed"
             );
         }
     }
@@ -320,6 +760,7 @@ where
                     );
                     Some(Command::Terminate(
                         "remote command transmitter dropped".to_owned(),
+                        oneshot::channel().0,
                     ))
                 }
             },
@@ -327,6 +768,92 @@ where
     }
 }
 
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data:
+        MarketGenerator<MarketEvent<DataKind>> + AsyncMarketGenerator<MarketEvent<DataKind>> + Send,
+    Strategy: SignalGenerator + Send,
+    Execution: AsyncExecutionClient + Send,
+{
+    /// Run the trading event-loop for this [`Trader`] instance as an asynchronous task on the
+    /// tokio runtime. Rather than polling for remote [`Command`]s & [`ExecutionReport`]s between
+    /// [`MarketEvent`]s, the [`Trader`] waits on whichever arrives first, polling for any other
+    /// [`ExecutionReport`]s after each. Loop will run until
+    /// [`Trader`] receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
+    /// [`AsyncMarketGenerator`] yields [`Feed::Finished`].
+    pub async fn run_async(mut self) {
+        'trading: loop {
+            tokio::select! {
+                feed = self.data.next_async() => {
+                    if !self.handle_feed(feed) {
+                        break 'trading;
+                    }
+                }
+                command = self.command_rx.recv() => {
+                    let command = command.unwrap_or_else(|| {
+                        Command::Terminate(
+                            "remote command transmitter dropped".to_owned(),
+                            oneshot::channel().0,
+                        )
+                    });
+                    if !self.handle_command(command) {
+                        break 'trading;
+                    }
+                }
+                Some(report) = self.execution.next_report() => {
+                    let event = Event::from(report);
+                    self.event_tx.send(event.clone());
+                    self.event_q.push_back(event);
+                }
+            }
+
+            // Populate event_q with any ExecutionReports the ExecutionClient only makes
+            // available via polling
+            self.poll_execution_reports();
+
+            self.process_event_q();
+        }
+
+        debug!(
+            engine_id = &*self.engine_id.to_string(),
+            market = &*format!("{:?}", self.market),
+            "Trader trading loop stopped"
+        );
+    }
+}
+
+/// Exit orders generated by a [`Command::ExitPositionsAndConfirm`] that are yet to be filled.
+#[derive(Debug)]
+struct PendingExit {
+    order_ids: HashSet<OrderId>,
+    ack_tx: oneshot::Sender<Result<(), EngineError>>,
+}
+
+/// Entry [`Signal`] of an [`OrderGeneratorResult::ExitAndNew`], deferred until the opposite
+/// Positions it reverses have been exited.
+#[derive(Debug)]
+struct PendingEntry {
+    signal: Signal,
+    /// Signal ids of the opposite Positions that are yet to be exited.
+    position_signal_ids: HashSet<Uuid>,
+    /// Exit orders submitted for the opposite Positions.
+    order_ids: HashSet<OrderId>,
+}
+
+/// Sends the outcome of an actioned [`Command`] on it's acknowledgement `oneshot::Sender`.
+fn acknowledge<T>(ack_tx: oneshot::Sender<Result<T, EngineError>>, result: Result<T, EngineError>) {
+    if ack_tx.send(result).is_err() {
+        warn!(
+            why = "oneshot receiver dropped",
+            "cannot acknowledge actioned Command"
+        );
+    }
+}
+
 /// Builder to construct [`Trader`] instances.
 #[derive(Debug, Default)]
 pub struct TraderBuilder<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -345,7 +872,9 @@ where
     portfolio: Option<Arc<Mutex<Portfolio>>>,
     data: Option<Data>,
     strategy: Option<Strategy>,
+    strategy_id: Option<StrategyId>,
     execution: Option<Execution>,
+    clock: Option<SharedClock>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -368,7 +897,9 @@ where
             portfolio: None,
             data: None,
             strategy: None,
+            strategy_id: None,
             execution: None,
+            clock: None,
             _statistic_marker: None,
         }
     }
@@ -422,6 +953,13 @@ where
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn execution(self, value: Execution) -> Self {
         Self {
             execution: Some(value),
@@ -429,6 +967,13 @@ where
         }
     }
 
+    pub fn clock(self, value: SharedClock) -> Self {
+        Self {
+            clock: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -453,9 +998,15 @@ where
             strategy: self
                 .strategy
                 .ok_or(EngineError::BuilderIncomplete("strategy"))?,
+            strategy_id: self.strategy_id,
             execution: self
                 .execution
                 .ok_or(EngineError::BuilderIncomplete("execution"))?,
+            clock: self.clock.unwrap_or_default(),
+            paused: false,
+            market_close: None,
+            pending_exit: None,
+            pending_entry: None,
             _statistic_marker: PhantomData::default(),
         })
     }
@@ -0,0 +1,250 @@
+use super::{Event, EventKind, MessageTransmitter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::{
+    runtime::Handle,
+    sync::mpsc::{self, error::TrySendError},
+};
+use tracing::warn;
+
+/// Fan-out [`Event`] transmitter that delivers every [`Event`] to each subscriber whose
+/// [`EventFilter`] accepts it. Useful for running many downstream consumers (eg/ audit journal,
+/// dashboard, metrics, risk monitor) off the same [`Trader`](crate::engine::trader::Trader)s.
+///
+/// Each subscriber chooses unbounded delivery, or bounded delivery with a [`Backpressure`]
+/// policy determining what happens when it falls behind. Subscribers whose receiver has been
+/// dropped are removed. Clones deliver to the same subscribers.
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    subscribers: Vec<Subscriber>,
+}
+
+/// Determines which [`Event`]s are delivered to an [`EventBus`] subscriber.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum EventFilter {
+    /// Deliver every [`Event`].
+    All,
+    /// Only deliver [`Event`]s of the provided [`EventKind`]s.
+    Kinds(HashSet<EventKind>),
+}
+
+impl EventFilter {
+    /// Constructs an [`EventFilter`] that only accepts the provided [`EventKind`]s.
+    pub fn kinds<Kinds: IntoIterator<Item = EventKind>>(kinds: Kinds) -> Self {
+        Self::Kinds(kinds.into_iter().collect())
+    }
+
+    /// Determines if the [`Event`] should be delivered.
+    pub fn accepts(&self, event: &Event) -> bool {
+        match self {
+            EventFilter::All => true,
+            EventFilter::Kinds(kinds) => kinds.contains(&event.kind()),
+        }
+    }
+}
+
+/// Action taken when a bounded [`EventBus`] subscriber's channel is full.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Backpressure {
+    /// Discard the [`Event`] that does not fit, keeping those already queued.
+    DropNewest,
+    /// Block the sender until the subscriber has capacity. Blocking would panic within an
+    /// asynchronous context (eg/ a tokio runtime), so [`Event`]s sent from one that do not fit
+    /// are discarded as per [`Backpressure::DropNewest`].
+    Block,
+    /// Unsubscribe the lagging subscriber.
+    Disconnect,
+}
+
+/// Channel transmitter of an [`EventBus`] subscriber.
+#[derive(Debug, Clone)]
+enum SubscriberTx {
+    Unbounded(mpsc::UnboundedSender<Event>),
+    Bounded(mpsc::Sender<Event>, Backpressure),
+}
+
+/// [`EventBus`] subscriber. Tracks the number of [`Event`]s dropped due to [`Backpressure`].
+#[derive(Debug, Clone)]
+struct Subscriber {
+    filter: EventFilter,
+    tx: SubscriberTx,
+    dropped: u64,
+}
+
+impl Subscriber {
+    /// Delivers the [`Event`] if it is accepted by the [`EventFilter`]. Returns false if the
+    /// subscriber should be removed from the [`EventBus`].
+    fn deliver(&mut self, event: &Event) -> bool {
+        if !self.filter.accepts(event) {
+            return true;
+        }
+
+        match &self.tx {
+            SubscriberTx::Unbounded(tx) => tx.send(event.clone()).is_ok(),
+            SubscriberTx::Bounded(tx, Backpressure::Block) if Handle::try_current().is_err() => {
+                tx.blocking_send(event.clone()).is_ok()
+            }
+            SubscriberTx::Bounded(tx, policy) => match tx.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Closed(_)) => false,
+                Err(TrySendError::Full(_)) if *policy == Backpressure::Disconnect => {
+                    warn!(
+                        action = "unsubscribing",
+                        why = "subscriber channel full",
+                        "EventBus subscriber lagging"
+                    );
+                    false
+                }
+                Err(TrySendError::Full(_)) => {
+                    self.dropped += 1;
+                    warn!(
+                        dropped = self.dropped,
+                        why = "subscriber channel full",
+                        "EventBus dropped Event"
+                    );
+                    true
+                }
+            },
+        }
+    }
+}
+
+impl EventBus {
+    /// Constructs a new [`EventBus`] without any subscribers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to the [`Event`]s accepted by the [`EventFilter`] via an unbounded channel.
+    pub fn subscribe_unbounded(&mut self, filter: EventFilter) -> mpsc::UnboundedReceiver<Event> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribers.push(Subscriber {
+            filter,
+            tx: SubscriberTx::Unbounded(tx),
+            dropped: 0,
+        });
+        rx
+    }
+
+    /// Subscribes to the [`Event`]s accepted by the [`EventFilter`] via a channel bounded by the
+    /// provided capacity, applying the [`Backpressure`] policy when it is full.
+    pub fn subscribe_bounded(
+        &mut self,
+        filter: EventFilter,
+        capacity: usize,
+        backpressure: Backpressure,
+    ) -> mpsc::Receiver<Event> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.subscribers.push(Subscriber {
+            filter,
+            tx: SubscriberTx::Bounded(tx, backpressure),
+            dropped: 0,
+        });
+        rx
+    }
+
+    /// Number of active subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+}
+
+impl MessageTransmitter<Event> for EventBus {
+    fn send(&mut self, message: Event) {
+        let subscribers_before = self.subscribers.len();
+        self.subscribers
+            .retain_mut(|subscriber| subscriber.deliver(&message));
+
+        if self.subscribers.len() < subscribers_before {
+            warn!(
+                action = "removing subscribers",
+                removed = subscribers_before - self.subscribers.len(),
+                "EventBus subscribers disconnected"
+            );
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        messages.into_iter().for_each(|message| self.send(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{fill_event, market_event_candle, order_event};
+
+    #[test]
+    fn should_only_deliver_events_accepted_by_each_subscriber_filter() {
+        let mut bus = EventBus::new();
+        let mut all_rx = bus.subscribe_unbounded(EventFilter::All);
+        let mut fill_rx = bus.subscribe_bounded(
+            EventFilter::kinds([EventKind::Fill]),
+            4,
+            Backpressure::DropNewest,
+        );
+
+        bus.send_many(vec![
+            Event::Market(market_event_candle()),
+            Event::OrderNew(order_event()),
+            Event::Fill(fill_event()),
+        ]);
+
+        let mut all = Vec::new();
+        while let Ok(event) = all_rx.try_recv() {
+            all.push(event.kind());
+        }
+        assert_eq!(
+            all,
+            vec![EventKind::Market, EventKind::OrderNew, EventKind::Fill]
+        );
+
+        assert_eq!(fill_rx.try_recv().unwrap().kind(), EventKind::Fill);
+        assert!(fill_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn should_apply_backpressure_policy_when_bounded_subscriber_is_full() {
+        let mut bus = EventBus::new();
+        let mut drop_rx = bus.subscribe_bounded(EventFilter::All, 1, Backpressure::DropNewest);
+        let _lagging_rx = bus.subscribe_bounded(EventFilter::All, 1, Backpressure::Disconnect);
+
+        bus.send(Event::Market(market_event_candle()));
+        assert_eq!(bus.subscriber_count(), 2);
+
+        bus.send(Event::Fill(fill_event()));
+        assert_eq!(bus.subscriber_count(), 1);
+
+        // DropNewest subscriber keeps the queued Event & discards the one that did not fit
+        assert_eq!(drop_rx.try_recv().unwrap().kind(), EventKind::Market);
+        assert!(drop_rx.try_recv().is_err());
+        assert_eq!(bus.subscribers[0].dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn block_backpressure_should_not_block_within_async_context() {
+        let mut bus = EventBus::new();
+        let mut block_rx = bus.subscribe_bounded(EventFilter::All, 1, Backpressure::Block);
+
+        bus.send(Event::Market(market_event_candle()));
+        bus.send(Event::Fill(fill_event()));
+
+        assert_eq!(bus.subscriber_count(), 1);
+        assert_eq!(block_rx.try_recv().unwrap().kind(), EventKind::Market);
+        assert!(block_rx.try_recv().is_err());
+        assert_eq!(bus.subscribers[0].dropped, 1);
+    }
+
+    #[test]
+    fn should_remove_subscribers_whose_receiver_is_dropped() {
+        let mut bus = EventBus::new();
+        let mut kept_rx = bus.subscribe_unbounded(EventFilter::All);
+        drop(bus.subscribe_unbounded(EventFilter::All));
+        drop(bus.subscribe_bounded(EventFilter::All, 1, Backpressure::Block));
+
+        bus.send(Event::Fill(fill_event()));
+
+        assert_eq!(bus.subscriber_count(), 1);
+        assert_eq!(kept_rx.try_recv().unwrap().kind(), EventKind::Fill);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::strategy::SignalPositionExit;
+use crate::{
+    data::quality::DataQualityIssue,
+    execution::{
+        order::{ExecutionReport, OrderUpdate},
+        FillEvent,
+    },
+    portfolio::{
+        position::{Position, PositionExit, PositionUpdateByMarket},
+        Balance, OrderEvent,
+    },
+    strategy::{
+        Signal, SignalForceExit, SignalInstrumentPositionsExit, SignalIntent, SignalPositionAmend,
+    },
+};
+use barter_data::event::{DataKind, MarketEvent};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Fan-out [`EventBus`](bus::EventBus) delivering filtered [`Event`]s to many subscribers.
+pub mod bus;
+
+/// Events that occur when bartering. [`MarketEvent`], [`Signal`], [`OrderEvent`], and
+/// [`FillEvent`] are vital to the [`Trader`](crate::engine::trader::Trader) event loop, dictating
+/// the trading sequence. The [`PositionExit`] Event is a representation of work done by the
+/// system, and is useful for analysing performance & reconciliations.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Event {
+    Market(MarketEvent<DataKind>),
+    Signal(Signal),
+    SignalForceExit(SignalForceExit),
+    SignalPositionExit(SignalPositionExit),
+    SignalPositionAmend(SignalPositionAmend),
+    SignalInstrumentExit(SignalInstrumentPositionsExit),
+    OrderNew(OrderEvent),
+    OrderUpdate(OrderUpdate),
+    Fill(FillEvent),
+    PositionNew(Position),
+    PositionUpdate(PositionUpdateByMarket),
+    // used in update_from_fill()
+    PositionExit(PositionExit),
+    Balance(Balance),
+    DataQuality(DataQualityIssue),
+}
+
+impl Event {
+    /// Returns the [`EventKind`] of this [`Event`].
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Market(_) => EventKind::Market,
+            Event::Signal(_) => EventKind::Signal,
+            Event::SignalForceExit(_) => EventKind::SignalForceExit,
+            Event::SignalPositionExit(_) => EventKind::SignalPositionExit,
+            Event::SignalPositionAmend(_) => EventKind::SignalPositionAmend,
+            Event::SignalInstrumentExit(_) => EventKind::SignalInstrumentExit,
+            Event::OrderNew(_) => EventKind::OrderNew,
+            Event::OrderUpdate(_) => EventKind::OrderUpdate,
+            Event::Fill(_) => EventKind::Fill,
+            Event::PositionNew(_) => EventKind::PositionNew,
+            Event::PositionUpdate(_) => EventKind::PositionUpdate,
+            Event::PositionExit(_) => EventKind::PositionExit,
+            Event::Balance(_) => EventKind::Balance,
+            Event::DataQuality(_) => EventKind::DataQuality,
+        }
+    }
+}
+
+/// Discriminant of each [`Event`] variant, used to subscribe to a subset of [`Event`]s.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum EventKind {
+    Market,
+    Signal,
+    SignalForceExit,
+    SignalPositionExit,
+    SignalPositionAmend,
+    SignalInstrumentExit,
+    OrderNew,
+    OrderUpdate,
+    Fill,
+    PositionNew,
+    PositionUpdate,
+    PositionExit,
+    Balance,
+    DataQuality,
+}
+
+impl From<SignalIntent> for Event {
+    fn from(intent: SignalIntent) -> Self {
+        match intent {
+            SignalIntent::Signal(signal) => Event::Signal(signal),
+            SignalIntent::Exit(exit) => Event::SignalPositionExit(exit),
+            SignalIntent::Amend(amend) => Event::SignalPositionAmend(amend),
+        }
+    }
+}
+
+impl From<ExecutionReport> for Event {
+    fn from(report: ExecutionReport) -> Self {
+        match report {
+            ExecutionReport::Update(update) => Event::OrderUpdate(update),
+            ExecutionReport::Fill(fill) => Event::Fill(fill),
+        }
+    }
+}
+
+/// Message transmitter for sending Barter messages to downstream consumers.
+pub trait MessageTransmitter<Message> {
+    /// Attempts to send a message to an external message subscriber.
+    fn send(&mut self, message: Message);
+
+    /// Attempts to send many messages to an external message subscriber.
+    fn send_many(&mut self, messages: Vec<Message>);
+}
+
+/// Transmitter for sending Barter [`Event`]s to an external sink. Useful for event-sourcing,
+/// real-time dashboards & general monitoring.
+#[derive(Debug, Clone)]
+pub struct EventTx {
+    /// Flag to communicate if the external [`Event`] receiver has been dropped.
+    receiver_dropped: bool,
+    /// [`Event`] channel transmitter to send [`Event`]s to an external sink.
+    event_tx: mpsc::UnboundedSender<Event>,
+}
+
+impl MessageTransmitter<Event> for EventTx {
+    fn send(&mut self, message: Event) {
+        if self.receiver_dropped {
+            return;
+        }
+
+        if self.event_tx.send(message).is_err() {
+            warn!(
+                action = "setting receiver_dropped = true",
+                why = "event receiver dropped",
+                "cannot send Events"
+            );
+            self.receiver_dropped = true;
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        if self.receiver_dropped {
+            return;
+        }
+
+        messages.into_iter().for_each(|message| self.send(message))
+    }
+}
+
+impl EventTx {
+    /// Constructs a new [`EventTx`] instance using the provided channel transmitter.
+    pub fn new(event_tx: mpsc::UnboundedSender<Event>) -> Self {
+        Self {
+            receiver_dropped: false,
+            event_tx,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{fill_event, market_event_candle};
+
+    #[test]
+    fn send_many_stops_sending_once_receiver_dropped() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut event_tx = EventTx::new(tx);
+        drop(rx);
+
+        event_tx.send_many(vec![
+            Event::Market(market_event_candle()),
+            Event::Fill(fill_event()),
+        ]);
+
+        assert!(event_tx.receiver_dropped);
+    }
+}
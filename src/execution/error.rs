@@ -1,3 +1,4 @@
+use crate::{execution::order::OrderId, portfolio::OrderType};
 use thiserror::Error;
 
 /// All errors generated in the barter::execution module.
@@ -5,4 +6,10 @@ use thiserror::Error;
 pub enum ExecutionError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
+
+    #[error("Cannot execute {0:?} OrderEvent without a price")]
+    MissingOrderPrice(OrderType),
+
+    #[error("No working order to cancel with OrderId: {0}")]
+    OrderNotFound(OrderId),
 }
@@ -1,27 +1,100 @@
-use crate::strategy::SignalExtra;
+use crate::strategy::{SignalAttribution, SignalExtra, StrategyId};
 use crate::{data::MarketMeta, portfolio::OrderEvent, strategy::Decision};
+use async_trait::async_trait;
+use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{Exchange, Instrument};
 use chrono::{DateTime, Utc};
 use error::ExecutionError;
+use order::{ExecutionReport, OrderId};
 use serde::{Deserialize, Serialize};
+use std::ops::AddAssign;
 use uuid::Uuid;
 
 /// Barter execution module specific errors.
 pub mod error;
 
+/// Order lifecycle identifiers, states & the reports an [`ExecutionClient`] generates.
+pub mod order;
+
 /// Handlers for simulated and live [`OrderEvent`] execution.
 pub mod simulated;
 
-/// Generates a result [`FillEvent`] by executing an [`OrderEvent`].
+/// Slippage & market impact models used by the simulated execution handler to adjust fill
+/// prices and quantities.
+pub mod slippage;
+
+/// Executes [`OrderEvent`]s at a venue, reporting the lifecycle [`OrderUpdate`](order::OrderUpdate)s & resulting
+/// [`FillEvent`]s as [`ExecutionReport`]s.
 pub trait ExecutionClient {
-    /// Return a [`FillEvent`] from executing the input [`OrderEvent`].
-    fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError>;
+    /// Submits the input [`OrderEvent`] to the venue, returning any [`ExecutionReport`]s available
+    /// immediately. Reports generated later (eg/ a resting
+    /// [`OrderType::Limit`](crate::portfolio::OrderType) being filled, or a live venue
+    /// acknowledging the order) are returned by update_from_market() or poll_reports().
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Vec<ExecutionReport>, ExecutionError>;
+
+    /// Return the [`ExecutionReport`]s of any working orders affected by the input
+    /// [`MarketEvent`].
+    fn update_from_market(
+        &mut self,
+        _market: &MarketEvent<DataKind>,
+    ) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Ok(vec![])
+    }
+
+    /// Non-blocking poll for any [`ExecutionReport`]s received asynchronously from the venue
+    /// since the last poll.
+    fn poll_reports(&mut self) -> Vec<ExecutionReport> {
+        vec![]
+    }
+
+    /// Cancels the working order with the provided [`OrderId`], returning the resulting
+    /// [`ExecutionReport`]s. Clients that do not work orders over time have nothing to cancel.
+    fn cancel_order(&mut self, order_id: OrderId) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Err(ExecutionError::OrderNotFound(order_id))
+    }
+
+    /// Cancels every working order, returning the resulting [`ExecutionReport`]s.
+    fn cancel_all_orders(&mut self) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Ok(vec![])
+    }
+
+    /// Replaces the working protective exit orders of the position entered by the provided
+    /// signal_id (eg/ bracket take profit & stop loss legs) with the levels of the amended
+    /// [`SignalExtra`], returning the resulting [`ExecutionReport`]s. Clients that do not work
+    /// protective exit orders have nothing to replace.
+    fn amend_protective_orders(
+        &mut self,
+        _position_signal_id: Uuid,
+        _signal_extra: SignalExtra,
+    ) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Ok(vec![])
+    }
+}
+
+/// [`ExecutionClient`] whose asynchronously received [`ExecutionReport`]s can be awaited rather
+/// than polled, allowing a [`Trader`](crate::engine::trader::Trader) running as an asynchronous
+/// task to wait on them alongside market data & remote [`Command`](crate::engine::Command)s.
+/// Submitting & cancelling orders remain non-blocking [`ExecutionClient`] methods, and any
+/// [`ExecutionReport`]s returned by poll_reports() are still actioned.
+#[async_trait]
+pub trait AsyncExecutionClient: ExecutionClient {
+    /// Waits for the next [`ExecutionReport`] received asynchronously from the venue. Returns
+    /// `None` if the client does not (or will no longer) receive any.
+    ///
+    /// This method must be cancel safe: the [`Trader`](crate::engine::trader::Trader) awaits it
+    /// in a `tokio::select!` alongside market data & remote [`Command`](crate::engine::Command)s,
+    /// dropping the future whenever another branch completes first. An [`ExecutionReport`] must
+    /// therefore only be removed from the client's internal state once it is returned (eg/ by
+    /// awaiting a cancel safe `mpsc::Receiver::recv`), never whilst partially read.
+    async fn next_report(&mut self) -> Option<ExecutionReport>;
 }
 
 /// Fills are journals of work done by an Execution handler. These are sent back to the portfolio
 /// so it can apply updates.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct FillEvent {
+    /// Identifier of the [`OrderEvent`] this [`FillEvent`] (partially) fills.
+    pub order_id: OrderId,
     pub signal_id: Uuid,
     pub time: DateTime<Utc>,
     pub exchange: Exchange,
@@ -39,6 +112,12 @@ pub struct FillEvent {
     pub signal_extra: SignalExtra,
     /// If it is to fill an existing position
     pub position_signal_id: Option<Uuid>,
+    /// Identifier of the strategy the filled [`OrderEvent`] is attributed to.
+    #[serde(default)]
+    pub strategy_id: Option<StrategyId>,
+    /// Contribution of each member strategy to the filled [`OrderEvent`], if any.
+    #[serde(default)]
+    pub attribution: Vec<SignalAttribution>,
 }
 
 impl FillEvent {
@@ -48,6 +127,19 @@ impl FillEvent {
     pub fn builder() -> FillEventBuilder {
         FillEventBuilder::new()
     }
+
+    /// Returns the portion of this [`FillEvent`] for the +ve or -ve quantity provided, with the
+    /// fill value & fees allocated pro rata, and the [`Decision`] replaced.
+    pub fn split(&self, quantity: f64, decision: Decision) -> FillEvent {
+        let ratio = (quantity / self.quantity).abs();
+        FillEvent {
+            decision,
+            quantity,
+            fill_value_gross: self.fill_value_gross * ratio,
+            fees: self.fees.scale(ratio),
+            ..self.clone()
+        }
+    }
 }
 
 /// All potential fees incurred by a [`FillEvent`].
@@ -55,25 +147,44 @@ impl FillEvent {
 pub struct Fees {
     /// Fee taken by the exchange/broker (eg/ commission).
     pub exchange: FeeAmount,
-    /// Order book slippage modelled as a fee.
+    /// Order book slippage modelled as a fee, in addition to any price slippage applied by a
+    /// [`SlippageModel`](slippage::SlippageModel).
     pub slippage: FeeAmount,
     /// Fee incurred by any required network transactions (eg/ GAS).
     pub network: FeeAmount,
 }
 
 impl Fees {
+    /// Returns these [Fees] with every [FeeAmount] multiplied by the provided ratio.
+    pub fn scale(&self, ratio: f64) -> Fees {
+        Fees {
+            exchange: self.exchange * ratio,
+            slippage: self.slippage * ratio,
+            network: self.network * ratio,
+        }
+    }
+
     /// Calculates the sum of every [FeeAmount] in [Fees].
     pub fn calculate_total_fees(&self) -> f64 {
         self.exchange + self.network + self.slippage
     }
 }
 
+impl AddAssign for Fees {
+    fn add_assign(&mut self, rhs: Self) {
+        self.exchange += rhs.exchange;
+        self.slippage += rhs.slippage;
+        self.network += rhs.network;
+    }
+}
+
 /// Communicative type alias for Fee amount as f64.
 pub type FeeAmount = f64;
 
 /// Builder to construct [FillEvent] instances.
 #[derive(Debug, Default)]
 pub struct FillEventBuilder {
+    pub order_id: Option<OrderId>,
     pub signal_id: Option<Uuid>,
     pub time: Option<DateTime<Utc>>,
     pub exchange: Option<Exchange>,
@@ -85,6 +196,8 @@ pub struct FillEventBuilder {
     pub fees: Option<Fees>,
     pub signal_extra: Option<SignalExtra>,
     pub position_signal_id: Option<Uuid>,
+    pub strategy_id: Option<StrategyId>,
+    pub attribution: Option<Vec<SignalAttribution>>,
 }
 
 impl FillEventBuilder {
@@ -92,6 +205,13 @@ impl FillEventBuilder {
         Self::default()
     }
 
+    pub fn order_id(self, value: OrderId) -> Self {
+        Self {
+            order_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn signal_id(self, value: Uuid) -> Self {
         Self {
             signal_id: Some(value),
@@ -169,8 +289,25 @@ impl FillEventBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
+    pub fn attribution(self, value: Vec<SignalAttribution>) -> Self {
+        Self {
+            attribution: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<FillEvent, ExecutionError> {
         Ok(FillEvent {
+            order_id: self
+                .order_id
+                .ok_or(ExecutionError::BuilderIncomplete("order_id"))?,
             signal_id: self
                 .signal_id
                 .ok_or(ExecutionError::BuilderIncomplete("signal_id"))?,
@@ -198,6 +335,8 @@ impl FillEventBuilder {
                 .signal_extra
                 .ok_or(ExecutionError::BuilderIncomplete("signal_extra"))?,
             position_signal_id: self.position_signal_id,
+            strategy_id: self.strategy_id,
+            attribution: self.attribution.unwrap_or_default(),
         })
     }
 }
@@ -0,0 +1,112 @@
+use crate::execution::FillEvent;
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+/// Unique identifier of an [`OrderEvent`](crate::portfolio::OrderEvent), used to correlate the
+/// [`OrderUpdate`]s & [`FillEvent`]s an [`ExecutionClient`](super::ExecutionClient) reports for it.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct OrderId(pub Uuid);
+
+impl OrderId {
+    /// Generates a new random [`OrderId`].
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for OrderId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for OrderId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Lifecycle state of an order at an execution venue.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum OrderState {
+    /// Order has been submitted but not yet confirmed by the venue.
+    New,
+    /// Order has been accepted by the venue and is working.
+    Acknowledged,
+    /// Part of the order quantity has been filled, the remainder is still working.
+    PartiallyFilled,
+    /// Entire order quantity has been filled.
+    Filled,
+    /// Order was cancelled before being completely filled.
+    Cancelled,
+    /// Order was refused by the venue.
+    Rejected,
+    /// Order reached the end of its time in force before being completely filled.
+    Expired,
+}
+
+impl OrderState {
+    /// Determines if the [`OrderState`] is final, meaning no further [`OrderUpdate`]s or
+    /// [`FillEvent`]s will be received for the order.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Filled | OrderState::Cancelled | OrderState::Rejected | OrderState::Expired
+        )
+    }
+}
+
+/// Change in the lifecycle [`OrderState`] of an order, reported by an
+/// [`ExecutionClient`](super::ExecutionClient).
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OrderUpdate {
+    pub order_id: OrderId,
+    pub signal_id: Uuid,
+    pub time: DateTime<Utc>,
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub state: OrderState,
+    /// +ve or -ve cumulative quantity filled so far.
+    pub filled_quantity: f64,
+    /// +ve or -ve quantity still working at the venue.
+    pub remaining_quantity: f64,
+    /// Reason provided for a [`OrderState::Rejected`] (or venue initiated cancel/expiry).
+    pub reason: Option<String>,
+}
+
+impl OrderUpdate {
+    pub const EVENT_TYPE: &'static str = "OrderUpdate";
+}
+
+/// Report generated by an [`ExecutionClient`](super::ExecutionClient) about an order it is
+/// working, either synchronously upon submission, or asynchronously via polling.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub enum ExecutionReport {
+    Update(OrderUpdate),
+    Fill(FillEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_state_is_terminal() {
+        let cases = [
+            (OrderState::New, false),
+            (OrderState::Acknowledged, false),
+            (OrderState::PartiallyFilled, false),
+            (OrderState::Filled, true),
+            (OrderState::Cancelled, true),
+            (OrderState::Rejected, true),
+            (OrderState::Expired, true),
+        ];
+
+        for (state, expected) in cases {
+            assert_eq!(state.is_terminal(), expected, "{state:?}");
+        }
+    }
+}
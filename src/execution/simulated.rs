@@ -1,57 +1,584 @@
-use chrono::Utc;
+use async_trait::async_trait;
+use barter_data::event::{DataKind, MarketEvent};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
+use crate::clock::{Clock, SharedClock};
+use crate::data::MarketMeta;
 use crate::execution::error::ExecutionError;
-use crate::execution::{ExecutionClient, Fees, FillEvent};
-use crate::portfolio::OrderEvent;
+use crate::execution::order::{ExecutionReport, OrderId, OrderState, OrderUpdate};
+use crate::execution::slippage::{NoSlippage, SlippageModel, SlippedFill};
+use crate::execution::{AsyncExecutionClient, ExecutionClient, Fees, FillEvent};
+use crate::portfolio::protection::PriceRange;
+use crate::portfolio::{OrderEvent, OrderType};
+use crate::strategy::{Decision, SignalExtra};
 
 /// Configuration for constructing a [`SimulatedExecution`] via the new() constructor method.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct Config {
     /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
     pub simulated_fees_pct: Fees,
+    /// If true, resting orders crossed by a trade are only filled up to the traded amount,
+    /// yielding partial [`FillEvent`]s. Otherwise resting orders are always filled in full.
+    #[serde(default)]
+    pub partial_fills: bool,
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 /// Simulated execution handler that executes [`OrderEvent`]s to generate [`FillEvent`]s via a
 /// simulated broker interaction.
-pub struct SimulatedExecution {
+///
+/// [`OrderType::Market`] orders are filled immediately at the [`OrderEvent`] market close.
+/// [`OrderType::Limit`] & [`OrderType::Stop`] orders rest in a simulated order book until a
+/// subsequent [`MarketEvent`] crosses their price. [`OrderType::Bracket`] orders additionally
+/// place one-cancels-the-other take profit & stop loss exit orders once the entry is filled.
+///
+/// Orders executed at the market (market orders & triggered stops) are filled at the price and
+/// quantity determined by the configured [`SlippageModel`]. Any quantity a market order cannot
+/// fill is cancelled, whereas a triggered stop rests until it is filled in full.
+pub struct SimulatedExecution<Slippage = NoSlippage> {
     fees_pct: Fees,
+    partial_fills: bool,
+    slippage: Slippage,
+    resting_orders: Vec<RestingOrder>,
+    #[serde(skip)]
+    clock: SharedClock,
 }
 
-impl ExecutionClient for SimulatedExecution {
-    fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
-        // Assume (for now) that all orders are filled at the market price
-        let fill_value_gross = SimulatedExecution::calculate_fill_value_gross(order);
+/// Order resting in the [`SimulatedExecution`] order book until the market crosses its price.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RestingOrder {
+    /// [`OrderEvent`] that is resting. For bracket exit legs this is derived from the entry order.
+    pub order: OrderEvent,
+    /// Limit or Stop behaviour of the resting order.
+    pub kind: RestingOrderKind,
+    /// Limit price, or stop trigger price.
+    pub price: f64,
+    /// +ve or -ve quantity still to be filled.
+    pub remaining_quantity: f64,
+    /// +ve or -ve quantity filled so far.
+    pub filled_quantity: f64,
+    /// Role of the resting order if it is part of an [`OrderType::Bracket`].
+    pub bracket: Option<BracketLeg>,
+}
 
-        Ok(FillEvent {
-            signal_id: order.signal_id,
-            time: Utc::now(),
-            exchange: order.exchange.clone(),
-            instrument: order.instrument.clone(),
-            market_meta: order.market_meta,
-            decision: order.decision,
-            quantity: order.quantity,
-            fill_value_gross,
-            fees: self.calculate_fees(&fill_value_gross),
-            signal_extra: order.signal_extra,
-            position_signal_id: order.position_signal_id,
-        })
+/// Behaviour of a [`RestingOrder`] once the market crosses its price.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum RestingOrderKind {
+    /// Fills at the price or better.
+    Limit,
+    /// Fills at the price (or worse if the market gaps through it) once triggered.
+    Stop,
+}
+
+/// Role of a [`RestingOrder`] within an [`OrderType::Bracket`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum BracketLeg {
+    Entry,
+    TakeProfit,
+    StopLoss,
+}
+
+impl RestingOrder {
+    /// Determines if the [`RestingOrder`] buys (+ve quantity) or sells (-ve quantity).
+    fn is_buy(&self) -> bool {
+        self.remaining_quantity.is_sign_positive()
+    }
+
+    /// Returns the fill price if the input [`PriceRange`] crosses this [`RestingOrder`].
+    fn crossed_price(&self, range: &PriceRange) -> Option<f64> {
+        match (self.kind, self.is_buy()) {
+            (RestingOrderKind::Limit, true) => {
+                (range.low <= self.price).then(|| range.open.min(self.price))
+            }
+            (RestingOrderKind::Limit, false) => {
+                (range.high >= self.price).then(|| range.open.max(self.price))
+            }
+            (RestingOrderKind::Stop, true) => {
+                (range.high >= self.price).then(|| range.open.max(self.price))
+            }
+            (RestingOrderKind::Stop, false) => {
+                (range.low <= self.price).then(|| range.open.min(self.price))
+            }
+        }
+    }
+
+    /// Returns the position signal_id & [`BracketLeg`] if this [`RestingOrder`] is a bracket
+    /// exit leg.
+    fn exit_leg(&self) -> Option<(Uuid, BracketLeg)> {
+        match (self.bracket, self.order.position_signal_id) {
+            (Some(BracketLeg::Entry), _) | (None, _) | (_, None) => None,
+            (Some(leg), Some(position_signal_id)) => Some((position_signal_id, leg)),
+        }
+    }
+
+    /// Determines if this [`RestingOrder`] is a bracket exit leg of the provided position.
+    fn is_exit_leg_of(&self, position_signal_id: Uuid) -> bool {
+        self.exit_leg()
+            .is_some_and(|(leg_position_signal_id, _)| leg_position_signal_id == position_signal_id)
+    }
+
+    /// Builds a new [`RestingOrder`] resting the quantity of this one left unfilled at the
+    /// provided price, with the provided [`SignalExtra`].
+    fn replaced(&self, price: f64, signal_extra: SignalExtra) -> RestingOrder {
+        let mut order = self.order.clone();
+        order.order_id = OrderId::new();
+        order.quantity = self.remaining_quantity;
+        order.price = Some(price);
+        order.signal_extra = signal_extra;
+
+        RestingOrder {
+            order,
+            kind: self.kind,
+            price,
+            remaining_quantity: self.remaining_quantity,
+            filled_quantity: 0.0,
+            bracket: self.bracket,
+        }
+    }
+
+    /// Records a fill of the signed quantity provided.
+    fn fill(&mut self, quantity: f64) {
+        self.filled_quantity += quantity;
+        self.reduce(quantity);
+    }
+
+    /// Builds an [`OrderUpdate`] describing the current [`OrderState`] of this [`RestingOrder`].
+    fn update(&self, time: DateTime<Utc>) -> OrderUpdate {
+        let state = match (self.filled_quantity == 0.0, self.remaining_quantity == 0.0) {
+            (true, _) => OrderState::Acknowledged,
+            (false, false) => OrderState::PartiallyFilled,
+            (false, true) => OrderState::Filled,
+        };
+        self.update_with(state, None, time)
+    }
+
+    /// Builds an [`OrderUpdate`] cancelling any quantity of this [`RestingOrder`] left unfilled.
+    fn cancelled(&self, time: DateTime<Utc>) -> OrderUpdate {
+        self.update_with(OrderState::Cancelled, None, time)
+    }
+
+    fn update_with(
+        &self,
+        state: OrderState,
+        reason: Option<String>,
+        time: DateTime<Utc>,
+    ) -> OrderUpdate {
+        OrderUpdate {
+            order_id: self.order.order_id,
+            signal_id: self.order.signal_id,
+            time,
+            exchange: self.order.exchange.clone(),
+            instrument: self.order.instrument.clone(),
+            state,
+            filled_quantity: self.filled_quantity,
+            remaining_quantity: match state.is_terminal() {
+                true => 0.0,
+                false => self.remaining_quantity,
+            },
+            reason,
+        }
+    }
+
+    /// Reduces the remaining quantity by the signed quantity provided, without flipping side.
+    fn reduce(&mut self, quantity: f64) {
+        let remaining = self.remaining_quantity - quantity;
+        self.remaining_quantity = if remaining.abs() <= f64::EPSILON
+            || remaining.signum() != self.remaining_quantity.signum()
+        {
+            0.0
+        } else {
+            remaining
+        };
+    }
+}
+
+impl<Slippage> ExecutionClient for SimulatedExecution<Slippage>
+where
+    Slippage: SlippageModel,
+{
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        let time = self.clock.time();
+        // Exiting a Position cancels any bracket exit legs still resting against it
+        let mut reports = match (order.decision.is_exit(), order.position_signal_id) {
+            (true, Some(position_signal_id)) => self.cancel_exit_legs(position_signal_id),
+            _ => vec![],
+        };
+
+        let close = order.market_meta.close;
+        match order.order_type {
+            OrderType::Market => {
+                // Market orders are filled at the market price, adjusted for slippage
+                reports.push(order_update(
+                    order,
+                    OrderState::Acknowledged,
+                    0.0,
+                    None,
+                    time,
+                ));
+                reports.extend(self.fill_at_market(order, close));
+            }
+            OrderType::Limit | OrderType::Stop => match order.price {
+                Some(price) => {
+                    let kind = match order.order_type {
+                        OrderType::Limit => RestingOrderKind::Limit,
+                        _ => RestingOrderKind::Stop,
+                    };
+                    reports.extend(self.place(order, kind, price, None, close));
+                }
+                None => reports.push(order_update(
+                    order,
+                    OrderState::Rejected,
+                    0.0,
+                    Some(ExecutionError::MissingOrderPrice(order.order_type).to_string()),
+                    time,
+                )),
+            },
+            OrderType::Bracket => {
+                let entry_reports = match order.price {
+                    Some(price) => self.place(
+                        order,
+                        RestingOrderKind::Limit,
+                        price,
+                        Some(BracketLeg::Entry),
+                        close,
+                    ),
+                    None => {
+                        let mut entry_reports = vec![order_update(
+                            order,
+                            OrderState::Acknowledged,
+                            0.0,
+                            None,
+                            time,
+                        )];
+                        entry_reports.extend(self.fill_at_market(order, close));
+                        entry_reports
+                    }
+                };
+
+                let entry_fill_quantity = filled_quantity(&entry_reports);
+                reports.extend(entry_reports);
+                if entry_fill_quantity != 0.0 {
+                    reports.extend(self.place_bracket_exits(order, entry_fill_quantity));
+                }
+            }
+        }
+
+        Ok(reports)
+    }
+
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent<DataKind>,
+    ) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        self.slippage.update_from_market(market);
+        let time = self.clock.time();
+
+        let range = match PriceRange::from_market(market) {
+            Some(range) => range,
+            None => return Ok(vec![]),
+        };
+
+        // Traded amount available to fill resting orders, if partial fills are simulated
+        let mut liquidity = match (&market.kind, self.partial_fills) {
+            (DataKind::Trade(trade), true) => Some(trade.amount),
+            _ => None,
+        };
+
+        let mut crossed = Vec::new();
+        let mut entries_filled = Vec::new();
+
+        // One-cancels-the-other: quantity exited by each bracket this MarketEvent, and by which leg
+        let mut exited: HashMap<Uuid, (BracketLeg, f64)> = HashMap::new();
+        let mut siblings_reduced: HashSet<Uuid> = HashSet::new();
+
+        for resting in self.resting_orders.iter_mut() {
+            if resting.order.exchange != market.exchange
+                || resting.order.instrument != market.instrument
+            {
+                continue;
+            }
+
+            // Reduce an exit leg by any quantity already exited by its sibling this MarketEvent
+            let exit_leg = resting.exit_leg();
+            if let Some((position_signal_id, leg)) = exit_leg {
+                if let Some((filled_leg, quantity)) = exited.get(&position_signal_id) {
+                    if *filled_leg != leg {
+                        resting.reduce(*quantity);
+                        siblings_reduced.insert(position_signal_id);
+                    }
+                }
+            }
+
+            if resting.remaining_quantity == 0.0 {
+                continue;
+            }
+
+            let fill_price = match resting.crossed_price(&range) {
+                Some(price) => price,
+                None => continue,
+            };
+
+            // Determine the quantity filled given the available liquidity
+            let fill_quantity = match liquidity {
+                Some(available) if available <= 0.0 => continue,
+                Some(available) => resting
+                    .remaining_quantity
+                    .abs()
+                    .min(available)
+                    .copysign(resting.remaining_quantity),
+                None => resting.remaining_quantity,
+            };
+
+            // Triggered stops execute at the market, so are subject to slippage
+            let (fill_quantity, fill_price) = match resting.kind {
+                RestingOrderKind::Limit => (fill_quantity, fill_price),
+                RestingOrderKind::Stop => {
+                    let slipped = self.slippage.slip(fill_quantity, fill_price);
+                    (slipped.quantity, slipped.price)
+                }
+            };
+            if fill_quantity == 0.0 {
+                continue;
+            }
+
+            let fill_quantity_abs = fill_quantity.abs();
+            if let Some(available) = liquidity.as_mut() {
+                *available -= fill_quantity_abs;
+            }
+            resting.fill(fill_quantity);
+
+            crossed.push((
+                resting.order.clone(),
+                fill_quantity,
+                fill_quantity_abs * fill_price,
+                MarketMeta {
+                    close: fill_price,
+                    time: market.exchange_time,
+                },
+                resting.update(time),
+            ));
+
+            match (resting.bracket, exit_leg) {
+                (Some(BracketLeg::Entry), _) => {
+                    entries_filled.push((resting.order.clone(), fill_quantity))
+                }
+                (_, Some((position_signal_id, leg))) => {
+                    exited.entry(position_signal_id).or_insert((leg, 0.0)).1 += fill_quantity;
+                }
+                _ => {}
+            }
+        }
+
+        // Reduce sibling exit legs that rest ahead of the leg that filled
+        for resting in self.resting_orders.iter_mut() {
+            if let Some((position_signal_id, leg)) = resting.exit_leg() {
+                if siblings_reduced.contains(&position_signal_id) {
+                    continue;
+                }
+                if let Some((filled_leg, quantity)) = exited.get(&position_signal_id) {
+                    if *filled_leg != leg {
+                        resting.reduce(*quantity);
+                    }
+                }
+            }
+        }
+
+        let mut reports = Vec::with_capacity(crossed.len() * 2);
+        for (order, quantity, fill_value_gross, market_meta, update) in crossed {
+            reports.push(ExecutionReport::Fill(self.build_fill(
+                &order,
+                quantity,
+                fill_value_gross,
+                market_meta,
+            )));
+            reports.push(ExecutionReport::Update(update));
+        }
+
+        // Remove filled resting orders, cancelling any exit legs left unfilled by a sibling
+        self.resting_orders.retain(|resting| {
+            if resting.remaining_quantity != 0.0 {
+                return true;
+            }
+            if resting.filled_quantity.abs() < resting.order.quantity.abs() {
+                reports.push(ExecutionReport::Update(resting.cancelled(time)));
+            }
+            false
+        });
+
+        // Bracket exit legs of entries filled by this MarketEvent become active from the next one
+        for (entry, fill_quantity) in entries_filled {
+            reports.extend(self.place_bracket_exits(&entry, fill_quantity));
+        }
+
+        Ok(reports)
+    }
+
+    fn cancel_order(&mut self, order_id: OrderId) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        let time = self.clock.time();
+        let index = self
+            .resting_orders
+            .iter()
+            .position(|resting| resting.order.order_id == order_id)
+            .ok_or(ExecutionError::OrderNotFound(order_id))?;
+
+        let cancelled = self.resting_orders.remove(index);
+        Ok(vec![ExecutionReport::Update(cancelled.cancelled(time))])
+    }
+
+    fn cancel_all_orders(&mut self) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        let time = self.clock.time();
+        Ok(self
+            .resting_orders
+            .drain(..)
+            .map(|resting| ExecutionReport::Update(resting.cancelled(time)))
+            .collect())
+    }
+
+    fn amend_protective_orders(
+        &mut self,
+        position_signal_id: Uuid,
+        signal_extra: SignalExtra,
+    ) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        let time = self.clock.time();
+
+        // A bracket entry still resting places it's exit legs at the amended levels once filled
+        for resting in self.resting_orders.iter_mut() {
+            if resting.bracket == Some(BracketLeg::Entry)
+                && resting.order.signal_id == position_signal_id
+            {
+                resting.order.signal_extra = signal_extra;
+            }
+        }
+
+        // Only positions with bracket exit legs resting have protective orders to replace
+        let template = match self
+            .resting_orders
+            .iter()
+            .find(|resting| resting.is_exit_leg_of(position_signal_id))
+        {
+            Some(resting) => resting.clone(),
+            None => return Ok(vec![]),
+        };
+
+        let legs = [
+            (
+                BracketLeg::StopLoss,
+                RestingOrderKind::Stop,
+                signal_extra.stop_loss_price,
+            ),
+            (
+                BracketLeg::TakeProfit,
+                RestingOrderKind::Limit,
+                signal_extra.take_profit_price,
+            ),
+        ];
+
+        let mut reports = vec![];
+        for (leg, kind, price) in legs {
+            let index = self.resting_orders.iter().position(|resting| {
+                resting.bracket == Some(leg) && resting.is_exit_leg_of(position_signal_id)
+            });
+
+            match (index, price) {
+                (Some(index), Some(price)) if self.resting_orders[index].price == price => {}
+                (Some(index), Some(price)) => {
+                    // Cancel the resting leg & replace it in place, preserving it's priority
+                    let replacement = self.resting_orders[index].replaced(price, signal_extra);
+                    reports.push(ExecutionReport::Update(
+                        self.resting_orders[index].cancelled(time),
+                    ));
+                    reports.push(ExecutionReport::Update(replacement.update(time)));
+                    self.resting_orders[index] = replacement;
+                }
+                (Some(index), None) => {
+                    let cancelled = self.resting_orders.remove(index);
+                    reports.push(ExecutionReport::Update(cancelled.cancelled(time)));
+                }
+                (None, Some(price)) => {
+                    let mut resting = template.replaced(price, signal_extra);
+                    resting.kind = kind;
+                    resting.bracket = Some(leg);
+                    resting.order.order_type = match kind {
+                        RestingOrderKind::Limit => OrderType::Limit,
+                        RestingOrderKind::Stop => OrderType::Stop,
+                    };
+                    reports.push(ExecutionReport::Update(resting.update(time)));
+
+                    // Stop loss rests ahead of take profit so it takes priority if both are
+                    // crossed at once
+                    let take_profit = self.resting_orders.iter().position(|resting| {
+                        resting.bracket == Some(BracketLeg::TakeProfit)
+                            && resting.is_exit_leg_of(position_signal_id)
+                    });
+                    match (leg, take_profit) {
+                        (BracketLeg::StopLoss, Some(index)) => {
+                            self.resting_orders.insert(index, resting)
+                        }
+                        _ => self.resting_orders.push(resting),
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        Ok(reports)
+    }
+}
+
+#[async_trait]
+impl<Slippage> AsyncExecutionClient for SimulatedExecution<Slippage>
+where
+    Slippage: SlippageModel + Send,
+{
+    /// Every [`ExecutionReport`] is generated synchronously upon submission or market update.
+    async fn next_report(&mut self) -> Option<ExecutionReport> {
+        None
     }
 }
 
 impl SimulatedExecution {
-    /// Constructs a new [`SimulatedExecution`] component.
+    /// Constructs a new [`SimulatedExecution`] component that fills market orders without
+    /// slippage.
     pub fn new(cfg: Config) -> Self {
-        Self {
-            fees_pct: cfg.simulated_fees_pct,
-        }
+        Self::with_slippage(cfg, NoSlippage)
     }
 
     /// Calculates the simulated gross fill value (excluding TotalFees) based on the input [`OrderEvent`].
     fn calculate_fill_value_gross(order: &OrderEvent) -> f64 {
         order.quantity.abs() * order.market_meta.close
     }
+}
+
+impl<Slippage> SimulatedExecution<Slippage>
+where
+    Slippage: SlippageModel,
+{
+    /// Constructs a new [`SimulatedExecution`] component that fills market orders using the
+    /// provided [`SlippageModel`].
+    pub fn with_slippage(cfg: Config, slippage: Slippage) -> Self {
+        Self {
+            fees_pct: cfg.simulated_fees_pct,
+            partial_fills: cfg.partial_fills,
+            slippage,
+            resting_orders: Vec::new(),
+            clock: SharedClock::default(),
+        }
+    }
+
+    /// Timestamps generated [`FillEvent`]s & [`OrderUpdate`]s using the provided [`Clock`]
+    /// rather than the default [`LiveClock`](crate::clock::LiveClock), eg/ a
+    /// [`HistoricalClock`](crate::clock::HistoricalClock) when backtesting.
+    pub fn with_clock(self, clock: SharedClock) -> Self {
+        Self { clock, ..self }
+    }
+
+    /// Returns the orders currently resting in the simulated order book.
+    pub fn resting_orders(&self) -> &[RestingOrder] {
+        &self.resting_orders
+    }
 
     /// Calculates the simulated [`Fees`] a [`FillEvent`] will incur, based on the input [`OrderEvent`].
     fn calculate_fees(&self, fill_value_gross: &f64) -> Fees {
@@ -61,28 +588,356 @@ impl SimulatedExecution {
             network: self.fees_pct.network * fill_value_gross,
         }
     }
+
+    /// Fills the input [`OrderEvent`] immediately if it is marketable at the current close,
+    /// otherwise rests it in the simulated order book.
+    fn place(
+        &mut self,
+        order: &OrderEvent,
+        kind: RestingOrderKind,
+        price: f64,
+        bracket: Option<BracketLeg>,
+        close: f64,
+    ) -> Vec<ExecutionReport> {
+        let time = self.clock.time();
+        let mut resting = RestingOrder {
+            order: order.clone(),
+            kind,
+            price,
+            remaining_quantity: order.quantity,
+            filled_quantity: 0.0,
+            bracket,
+        };
+
+        let mut reports = vec![ExecutionReport::Update(resting.update(time))];
+
+        let fill = match (resting.crossed_price(&PriceRange::point(close)), kind) {
+            (None, _) => None,
+            (Some(fill_price), RestingOrderKind::Limit) => Some(self.build_fill(
+                order,
+                order.quantity,
+                order.quantity.abs() * fill_price,
+                MarketMeta {
+                    close: fill_price,
+                    time: order.market_meta.time,
+                },
+            )),
+            (Some(fill_price), RestingOrderKind::Stop) => {
+                let slipped = self.slippage.slip(order.quantity, fill_price);
+                self.slipped_fill(order, slipped)
+            }
+        };
+
+        // Rest any quantity that was not filled immediately
+        if let Some(fill) = fill {
+            resting.fill(fill.quantity);
+            reports.push(ExecutionReport::Fill(fill));
+            reports.push(ExecutionReport::Update(resting.update(time)));
+        }
+        if resting.remaining_quantity != 0.0 {
+            self.resting_orders.push(resting);
+        }
+
+        reports
+    }
+
+    /// Fills the input [`OrderEvent`] at the market price, adjusted by the [`SlippageModel`].
+    /// Any quantity the [`SlippageModel`] leaves unfilled is cancelled.
+    fn fill_at_market(&self, order: &OrderEvent, price: f64) -> Vec<ExecutionReport> {
+        let time = self.clock.time();
+        let slipped = self.slippage.slip(order.quantity, price);
+
+        match self.slipped_fill(order, slipped) {
+            Some(fill) if fill.quantity == order.quantity => vec![
+                ExecutionReport::Fill(fill),
+                order_update(order, OrderState::Filled, order.quantity, None, time),
+            ],
+            Some(fill) => {
+                let filled_quantity = fill.quantity;
+                vec![
+                    ExecutionReport::Fill(fill),
+                    order_update(
+                        order,
+                        OrderState::Cancelled,
+                        filled_quantity,
+                        Some("insufficient market liquidity".to_owned()),
+                        time,
+                    ),
+                ]
+            }
+            None => vec![order_update(
+                order,
+                OrderState::Cancelled,
+                0.0,
+                Some("insufficient market liquidity".to_owned()),
+                time,
+            )],
+        }
+    }
+
+    /// Builds the [`FillEvent`] of an [`OrderEvent`] executed at the market, if the
+    /// [`SlippedFill`] leaves any quantity to fill.
+    fn slipped_fill(&self, order: &OrderEvent, slipped: SlippedFill) -> Option<FillEvent> {
+        if slipped.quantity == 0.0 {
+            return None;
+        }
+
+        let filled = OrderEvent {
+            quantity: slipped.quantity,
+            market_meta: MarketMeta {
+                close: slipped.price,
+                time: order.market_meta.time,
+            },
+            ..order.clone()
+        };
+
+        Some(self.build_fill(
+            order,
+            filled.quantity,
+            SimulatedExecution::calculate_fill_value_gross(&filled),
+            filled.market_meta,
+        ))
+    }
+
+    /// Cancels any bracket exit legs resting against the provided position.
+    fn cancel_exit_legs(&mut self, position_signal_id: Uuid) -> Vec<ExecutionReport> {
+        let time = self.clock.time();
+        let mut reports = vec![];
+        self.resting_orders.retain(|resting| {
+            if resting.is_exit_leg_of(position_signal_id) {
+                reports.push(ExecutionReport::Update(resting.cancelled(time)));
+                false
+            } else {
+                true
+            }
+        });
+        reports
+    }
+
+    /// Places (or grows) the take profit & stop loss exit legs of a filled bracket entry.
+    fn place_bracket_exits(
+        &mut self,
+        entry: &OrderEvent,
+        entry_fill_quantity: f64,
+    ) -> Vec<ExecutionReport> {
+        let time = self.clock.time();
+        // Stop loss rests ahead of take profit so it takes priority if both are crossed at once
+        let legs = [
+            (
+                BracketLeg::StopLoss,
+                RestingOrderKind::Stop,
+                entry.signal_extra.stop_loss_price,
+            ),
+            (
+                BracketLeg::TakeProfit,
+                RestingOrderKind::Limit,
+                entry.signal_extra.take_profit_price,
+            ),
+        ];
+
+        let mut reports = vec![];
+        for (leg, kind, price) in legs {
+            let price = match price {
+                Some(price) => price,
+                None => continue,
+            };
+
+            if let Some(existing) = self.resting_orders.iter_mut().find(|resting| {
+                resting.bracket == Some(leg) && resting.is_exit_leg_of(entry.signal_id)
+            }) {
+                existing.remaining_quantity -= entry_fill_quantity;
+                existing.order.quantity -= entry_fill_quantity;
+                reports.push(ExecutionReport::Update(existing.update(time)));
+                continue;
+            }
+
+            let mut exit = entry.clone();
+            exit.order_id = OrderId::new();
+            exit.decision = match entry.decision {
+                Decision::Long => Decision::CloseLong,
+                _ => Decision::CloseShort,
+            };
+            exit.quantity = -entry_fill_quantity;
+            exit.order_type = match kind {
+                RestingOrderKind::Limit => OrderType::Limit,
+                RestingOrderKind::Stop => OrderType::Stop,
+            };
+            exit.price = Some(price);
+            exit.position_signal_id = Some(entry.signal_id);
+
+            let resting = RestingOrder {
+                order: exit,
+                kind,
+                price,
+                remaining_quantity: -entry_fill_quantity,
+                filled_quantity: 0.0,
+                bracket: Some(leg),
+            };
+            reports.push(ExecutionReport::Update(resting.update(time)));
+            self.resting_orders.push(resting);
+        }
+
+        reports
+    }
+
+    /// Builds a [`FillEvent`] for the provided quantity of an [`OrderEvent`].
+    fn build_fill(
+        &self,
+        order: &OrderEvent,
+        quantity: f64,
+        fill_value_gross: f64,
+        market_meta: MarketMeta,
+    ) -> FillEvent {
+        FillEvent {
+            order_id: order.order_id,
+            signal_id: order.signal_id,
+            time: self.clock.time(),
+            exchange: order.exchange.clone(),
+            instrument: order.instrument.clone(),
+            market_meta,
+            decision: order.decision,
+            quantity,
+            fill_value_gross,
+            fees: self.calculate_fees(&fill_value_gross),
+            signal_extra: order.signal_extra,
+            position_signal_id: order.position_signal_id,
+            strategy_id: order.strategy_id.clone(),
+            attribution: order.attribution.clone(),
+        }
+    }
+}
+
+/// Builds an [`ExecutionReport::Update`] for the input [`OrderEvent`].
+fn order_update(
+    order: &OrderEvent,
+    state: OrderState,
+    filled_quantity: f64,
+    reason: Option<String>,
+    time: DateTime<Utc>,
+) -> ExecutionReport {
+    let remaining_quantity = match state.is_terminal() {
+        true => 0.0,
+        false => order.quantity - filled_quantity,
+    };
+
+    ExecutionReport::Update(OrderUpdate {
+        order_id: order.order_id,
+        signal_id: order.signal_id,
+        time,
+        exchange: order.exchange.clone(),
+        instrument: order.instrument.clone(),
+        state,
+        filled_quantity,
+        remaining_quantity,
+        reason,
+    })
+}
+
+/// Sums the quantity of every [`ExecutionReport::Fill`].
+fn filled_quantity(reports: &[ExecutionReport]) -> f64 {
+    reports
+        .iter()
+        .filter_map(|report| match report {
+            ExecutionReport::Fill(fill) => Some(fill.quantity),
+            ExecutionReport::Update(_) => None,
+        })
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::order_event;
+    use crate::execution::slippage::FixedBps;
+    use crate::test_util::{market_event_candle, market_event_trade, order_event};
+    use barter_integration::model::Side;
+
+    fn simulated_execution(partial_fills: bool) -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees::default(),
+            partial_fills,
+        })
+    }
+
+    fn resting_order_event(order_type: OrderType, quantity: f64, price: Option<f64>) -> OrderEvent {
+        let market = market_event_trade(Side::Buy);
+        let mut order = order_event();
+        order.exchange = market.exchange;
+        order.instrument = market.instrument;
+        order.market_meta.close = 1000.0;
+        order.order_type = order_type;
+        order.quantity = quantity;
+        order.price = price;
+        order
+    }
+
+    fn fill_events(reports: Vec<ExecutionReport>) -> Vec<FillEvent> {
+        reports
+            .into_iter()
+            .filter_map(|report| match report {
+                ExecutionReport::Fill(fill) => Some(fill),
+                ExecutionReport::Update(_) => None,
+            })
+            .collect()
+    }
+
+    fn updates(reports: &[ExecutionReport]) -> Vec<OrderState> {
+        reports
+            .iter()
+            .filter_map(|report| match report {
+                ExecutionReport::Update(update) => Some(update.state),
+                ExecutionReport::Fill(_) => None,
+            })
+            .collect()
+    }
+
+    fn trade_at(price: f64, amount: f64) -> MarketEvent<DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        if let DataKind::Trade(trade) = &mut market.kind {
+            trade.price = price;
+            trade.amount = amount;
+        }
+        market
+    }
+
+    #[test]
+    fn should_timestamp_reports_using_the_historical_clock() {
+        let clock = SharedClock::historical();
+        let mut simulated_execution = simulated_execution(false).with_clock(clock.clone());
+        let resting = resting_order_event(OrderType::Limit, 1.0, Some(990.0));
+
+        let market = trade_at(985.0, 1.0);
+        clock.advance(market.exchange_time);
+        simulated_execution.submit_order(&resting).unwrap();
+        let reports = simulated_execution.update_from_market(&market).unwrap();
+
+        assert!(!reports.is_empty());
+        for report in reports {
+            let time = match report {
+                ExecutionReport::Fill(fill) => fill.time,
+                ExecutionReport::Update(update) => update.time,
+            };
+            assert_eq!(time, market.exchange_time);
+        }
+    }
 
     #[test]
     fn should_generate_ok_fill_event_with_valid_order_event_provided() {
-        let simulated_execution = SimulatedExecution::new(Config {
+        let mut simulated_execution = SimulatedExecution::new(Config {
             simulated_fees_pct: Fees {
                 exchange: 0.1,
                 slippage: 0.05,
                 network: 0.0,
             },
+            partial_fills: false,
         });
 
         let mut input_order = order_event();
         input_order.quantity = 10.0;
         input_order.market_meta.close = 10.0;
 
-        let actual_result = simulated_execution.generate_fill(&input_order);
+        let actual_result = simulated_execution
+            .submit_order(&input_order)
+            .map(fill_events);
 
         let expected_fill_value_gross = 100.0;
         let expected_fees = Fees {
@@ -92,7 +947,7 @@ mod tests {
         };
 
         assert!(actual_result.is_ok());
-        let actual_result = actual_result.unwrap();
+        let actual_result = actual_result.unwrap().remove(0);
         assert_eq!(actual_result.fill_value_gross, expected_fill_value_gross);
         assert_eq!(actual_result.fees, expected_fees);
     }
@@ -131,6 +986,7 @@ mod tests {
                 slippage: 0.1,
                 network: 0.001,
             },
+            partial_fills: false,
         });
 
         let input_fill_value_gross = 100.0;
@@ -145,4 +1001,327 @@ mod tests {
 
         assert_eq!(actual_result, expected)
     }
+
+    #[test]
+    fn should_reject_limit_or_stop_order_without_price() {
+        let mut execution = simulated_execution(false);
+
+        for order_type in [OrderType::Limit, OrderType::Stop] {
+            let order = resting_order_event(order_type, 1.0, None);
+            let reports = execution.submit_order(&order).unwrap();
+            assert_eq!(updates(&reports), vec![OrderState::Rejected]);
+            assert!(execution.resting_orders().is_empty());
+        }
+    }
+
+    #[test]
+    fn should_rest_limit_order_until_market_crosses_limit_price() {
+        let mut execution = simulated_execution(false);
+
+        let order = resting_order_event(OrderType::Limit, 2.0, Some(950.0));
+        assert!(fill_events(execution.submit_order(&order).unwrap()).is_empty());
+        assert_eq!(execution.resting_orders().len(), 1);
+
+        // Market above limit price
+        assert!(
+            fill_events(execution.update_from_market(&trade_at(960.0, 1.0)).unwrap()).is_empty()
+        );
+
+        // Candle low crosses limit price, open (960.0) is above so fills at the limit price
+        let fills = fill_events(
+            execution
+                .update_from_market(&market_event_candle())
+                .unwrap(),
+        );
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 2.0);
+        assert_eq!(fills[0].market_meta.close, 950.0);
+        assert_eq!(fills[0].fill_value_gross, 1900.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_fill_marketable_limit_order_immediately() {
+        let mut execution = simulated_execution(false);
+
+        let order = resting_order_event(OrderType::Limit, 1.0, Some(1010.0));
+        let fills = fill_events(execution.submit_order(&order).unwrap());
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].market_meta.close, 1000.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_fill_triggered_sell_stop_at_worse_open_when_market_gaps_through() {
+        let mut execution = simulated_execution(false);
+
+        let order = resting_order_event(OrderType::Stop, -1.0, Some(970.0));
+        assert!(fill_events(execution.submit_order(&order).unwrap()).is_empty());
+
+        // Candle opens at 960.0, below the stop trigger
+        let fills = fill_events(
+            execution
+                .update_from_market(&market_event_candle())
+                .unwrap(),
+        );
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, -1.0);
+        assert_eq!(fills[0].market_meta.close, 960.0);
+    }
+
+    #[test]
+    fn should_partially_fill_resting_order_up_to_traded_amount() {
+        let mut execution = simulated_execution(true);
+
+        let order = resting_order_event(OrderType::Limit, 3.0, Some(990.0));
+        fill_events(execution.submit_order(&order).unwrap());
+
+        let fills = fill_events(execution.update_from_market(&trade_at(985.0, 1.0)).unwrap());
+        assert_eq!(fills[0].quantity, 1.0);
+        assert_eq!(fills[0].market_meta.close, 985.0);
+        assert_eq!(execution.resting_orders()[0].remaining_quantity, 2.0);
+
+        let fills = fill_events(execution.update_from_market(&trade_at(990.0, 5.0)).unwrap());
+        assert_eq!(fills[0].quantity, 2.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_place_bracket_exit_legs_and_cancel_other_when_one_fills() {
+        let mut execution = simulated_execution(false);
+
+        let mut order = resting_order_event(OrderType::Bracket, 1.0, None);
+        order.decision = Decision::Long;
+        order.signal_extra.take_profit_price = Some(1050.0);
+        order.signal_extra.stop_loss_price = Some(900.0);
+
+        // Entry without a price fills at market
+        let fills = fill_events(execution.submit_order(&order).unwrap());
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].decision, Decision::Long);
+        assert_eq!(execution.resting_orders().len(), 2);
+
+        // Take profit crossed
+        let fills = fill_events(
+            execution
+                .update_from_market(&trade_at(1060.0, 1.0))
+                .unwrap(),
+        );
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].decision, Decision::CloseLong);
+        assert_eq!(fills[0].quantity, -1.0);
+        assert_eq!(fills[0].market_meta.close, 1060.0);
+        assert_eq!(fills[0].position_signal_id, Some(order.signal_id));
+
+        // Stop loss cancelled
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_fill_bracket_stop_loss_only_when_both_exit_legs_crossed() {
+        let mut execution = simulated_execution(false);
+
+        let mut order = resting_order_event(OrderType::Bracket, 1.0, None);
+        order.decision = Decision::Long;
+        order.signal_extra.take_profit_price = Some(1050.0);
+        order.signal_extra.stop_loss_price = Some(955.0);
+        fill_events(execution.submit_order(&order).unwrap());
+
+        // Candle range 950.0 - 1100.0 crosses both exit legs
+        let fills = fill_events(
+            execution
+                .update_from_market(&market_event_candle())
+                .unwrap(),
+        );
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].market_meta.close, 955.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_cancel_bracket_exit_legs_when_position_exit_order_received() {
+        let mut execution = simulated_execution(false);
+
+        let mut order = resting_order_event(OrderType::Bracket, 1.0, None);
+        order.decision = Decision::Long;
+        order.signal_extra.take_profit_price = Some(1050.0);
+        order.signal_extra.stop_loss_price = Some(900.0);
+        fill_events(execution.submit_order(&order).unwrap());
+
+        let mut exit = resting_order_event(OrderType::Market, -1.0, None);
+        exit.decision = Decision::CloseLong;
+        exit.position_signal_id = Some(order.signal_id);
+
+        let fills = fill_events(execution.submit_order(&exit).unwrap());
+        assert_eq!(fills.len(), 1);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_replace_bracket_exit_legs_when_protective_levels_amended() {
+        let mut execution = simulated_execution(false);
+
+        let mut order = resting_order_event(OrderType::Bracket, 1.0, None);
+        order.decision = Decision::Long;
+        order.signal_extra.take_profit_price = Some(1050.0);
+        order.signal_extra.stop_loss_price = Some(900.0);
+        fill_events(execution.submit_order(&order).unwrap());
+
+        // Stop loss raised & take profit removed
+        let mut signal_extra = order.signal_extra;
+        signal_extra.stop_loss_price = Some(980.0);
+        signal_extra.take_profit_price = None;
+
+        let reports = execution
+            .amend_protective_orders(order.signal_id, signal_extra)
+            .unwrap();
+        assert_eq!(
+            updates(&reports),
+            vec![
+                OrderState::Cancelled,
+                OrderState::Acknowledged,
+                OrderState::Cancelled
+            ]
+        );
+        assert_eq!(execution.resting_orders().len(), 1);
+        assert_eq!(execution.resting_orders()[0].price, 980.0);
+        assert_eq!(
+            execution.resting_orders()[0].bracket,
+            Some(BracketLeg::StopLoss)
+        );
+
+        // Amended stop loss triggered
+        let fills = fill_events(execution.update_from_market(&trade_at(975.0, 1.0)).unwrap());
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].decision, Decision::CloseLong);
+        assert_eq!(fills[0].market_meta.close, 975.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn should_fill_market_and_triggered_stop_orders_at_slipped_price() {
+        let mut execution = SimulatedExecution::with_slippage(
+            Config {
+                simulated_fees_pct: Fees::default(),
+                partial_fills: false,
+            },
+            FixedBps { bps: 100.0 },
+        );
+
+        let order = resting_order_event(OrderType::Market, 2.0, None);
+        let fills = fill_events(execution.submit_order(&order).unwrap());
+        assert!((fills[0].market_meta.close - 1010.0).abs() < 1e-9);
+        assert!((fills[0].fill_value_gross - 2020.0).abs() < 1e-9);
+
+        // Limit orders fill at their limit price without slippage
+        let order = resting_order_event(OrderType::Limit, 1.0, Some(950.0));
+        fill_events(execution.submit_order(&order).unwrap());
+        let order = resting_order_event(OrderType::Stop, -1.0, Some(970.0));
+        fill_events(execution.submit_order(&order).unwrap());
+
+        let fills = fill_events(
+            execution
+                .update_from_market(&market_event_candle())
+                .unwrap(),
+        );
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].market_meta.close, 950.0);
+        assert!((fills[1].market_meta.close - 960.0 * 0.99).abs() < 1e-9);
+    }
+
+    #[test]
+    fn should_report_order_lifecycle_updates() {
+        let mut execution = simulated_execution(true);
+
+        // Market order
+        let order = resting_order_event(OrderType::Market, 1.0, None);
+        let reports = execution.submit_order(&order).unwrap();
+        assert_eq!(
+            updates(&reports),
+            vec![OrderState::Acknowledged, OrderState::Filled]
+        );
+        assert_eq!(fill_events(reports)[0].order_id, order.order_id);
+
+        // Resting limit order filled across two trades
+        let order = resting_order_event(OrderType::Limit, 2.0, Some(990.0));
+        let reports = execution.submit_order(&order).unwrap();
+        assert_eq!(updates(&reports), vec![OrderState::Acknowledged]);
+
+        let reports = execution.update_from_market(&trade_at(990.0, 1.5)).unwrap();
+        assert_eq!(updates(&reports), vec![OrderState::PartiallyFilled]);
+        match &reports[1] {
+            ExecutionReport::Update(update) => {
+                assert_eq!(update.order_id, order.order_id);
+                assert_eq!(update.filled_quantity, 1.5);
+                assert_eq!(update.remaining_quantity, 0.5);
+            }
+            report => panic!("expected OrderUpdate, found: {report:?}"),
+        }
+
+        let reports = execution.update_from_market(&trade_at(990.0, 1.5)).unwrap();
+        assert_eq!(updates(&reports), vec![OrderState::Filled]);
+    }
+
+    #[test]
+    fn should_report_bracket_sibling_exit_leg_cancelled() {
+        let mut execution = simulated_execution(false);
+
+        let mut order = resting_order_event(OrderType::Bracket, 1.0, None);
+        order.decision = Decision::Long;
+        order.signal_extra.take_profit_price = Some(1050.0);
+        order.signal_extra.stop_loss_price = Some(900.0);
+
+        // Entry filled & both exit legs acknowledged
+        let reports = execution.submit_order(&order).unwrap();
+        assert_eq!(
+            updates(&reports),
+            vec![
+                OrderState::Acknowledged,
+                OrderState::Filled,
+                OrderState::Acknowledged,
+                OrderState::Acknowledged
+            ]
+        );
+
+        let reports = execution
+            .update_from_market(&trade_at(1060.0, 1.0))
+            .unwrap();
+        assert_eq!(
+            updates(&reports),
+            vec![OrderState::Filled, OrderState::Cancelled]
+        );
+    }
+
+    #[test]
+    fn should_cancel_resting_orders_on_request() {
+        let mut execution = simulated_execution(false);
+
+        let limit = resting_order_event(OrderType::Limit, 2.0, Some(950.0));
+        let stop = resting_order_event(OrderType::Stop, -1.0, Some(900.0));
+        execution.submit_order(&limit).unwrap();
+        execution.submit_order(&stop).unwrap();
+        assert_eq!(execution.resting_orders().len(), 2);
+
+        let reports = execution.cancel_order(limit.order_id).unwrap();
+        assert_eq!(updates(&reports), vec![OrderState::Cancelled]);
+        assert_eq!(execution.resting_orders().len(), 1);
+
+        assert!(matches!(
+            execution.cancel_order(limit.order_id),
+            Err(ExecutionError::OrderNotFound(order_id)) if order_id == limit.order_id
+        ));
+
+        let reports = execution.cancel_all_orders().unwrap();
+        assert_eq!(updates(&reports), vec![OrderState::Cancelled]);
+        assert!(execution.resting_orders().is_empty());
+
+        // Cancelled orders are no longer filled when the market crosses their price
+        assert!(fill_events(
+            execution
+                .update_from_market(&market_event_candle())
+                .unwrap()
+        )
+        .is_empty());
+    }
 }
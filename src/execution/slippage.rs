@@ -0,0 +1,384 @@
+use crate::portfolio::protection::PriceRange;
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::book::{Level, OrderBookSide},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::warn;
+
+/// Models the slippage & market impact incurred when an order is executed against the market,
+/// adjusting the price (and possibly quantity) a [`SimulatedExecution`](super::simulated::SimulatedExecution)
+/// fills at.
+pub trait SlippageModel {
+    /// Updates any internal market state (eg/ volatility, volume, order book) the model relies
+    /// upon from the input [`MarketEvent`].
+    fn update_from_market(&mut self, _market: &MarketEvent<DataKind>) {}
+
+    /// Returns the [`SlippedFill`] of executing the +ve (buy) or -ve (sell) quantity at the
+    /// reference price.
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill;
+}
+
+/// Price & quantity an order is filled at after applying a [`SlippageModel`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct SlippedFill {
+    /// Average price the quantity is filled at.
+    pub price: f64,
+    /// +ve or -ve quantity filled, which may be less than the quantity requested.
+    pub quantity: f64,
+}
+
+impl SlippedFill {
+    /// Constructs a [`SlippedFill`] moving the reference price against the order by the provided
+    /// fraction (eg/ 0.001 for 10 bps).
+    fn adverse(quantity: f64, price: f64, fraction: f64) -> Self {
+        Self {
+            price: price * (1.0 + fraction.copysign(quantity)),
+            quantity,
+        }
+    }
+}
+
+/// Fills every order at the reference price.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct NoSlippage;
+
+impl SlippageModel for NoSlippage {
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill {
+        SlippedFill { price, quantity }
+    }
+}
+
+/// Moves the fill price against the order by a fixed number of basis points.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct FixedBps {
+    pub bps: f64,
+}
+
+impl SlippageModel for FixedBps {
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill {
+        SlippedFill::adverse(quantity, price, self.bps / 10_000.0)
+    }
+}
+
+/// Moves the fill price against the order by a multiple of the rolling standard deviation of
+/// close-to-close returns observed over the last `window` [`MarketEvent`]s.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct VolatilityScaled {
+    multiplier: f64,
+    window: usize,
+    last_close: Option<f64>,
+    returns: VecDeque<f64>,
+}
+
+impl VolatilityScaled {
+    /// Constructs a new [`VolatilityScaled`] slippage model.
+    pub fn new(multiplier: f64, window: usize) -> Self {
+        Self {
+            multiplier,
+            window,
+            last_close: None,
+            returns: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Sample standard deviation of the returns in the current window.
+    pub fn volatility(&self) -> f64 {
+        let count = self.returns.len();
+        if count < 2 {
+            return 0.0;
+        }
+
+        let mean = self.returns.iter().sum::<f64>() / count as f64;
+        let variance = self
+            .returns
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / (count - 1) as f64;
+
+        variance.sqrt()
+    }
+}
+
+impl SlippageModel for VolatilityScaled {
+    fn update_from_market(&mut self, market: &MarketEvent<DataKind>) {
+        let close = match PriceRange::from_market(market) {
+            Some(range) => range.close,
+            None => return,
+        };
+
+        if let Some(last_close) = self.last_close.replace(close) {
+            if self.returns.len() == self.window {
+                self.returns.pop_front();
+            }
+            self.returns.push_back(close / last_close - 1.0);
+        }
+    }
+
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill {
+        SlippedFill::adverse(quantity, price, self.multiplier * self.volatility())
+    }
+}
+
+/// Square-root market impact model, moving the fill price against the order by
+/// `coefficient * sqrt(|quantity| / volume)`, where volume is the most recent candle volume
+/// (or trade amount).
+///
+/// If a max participation rate is configured, the filled quantity is capped at that fraction of
+/// the observed volume.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct SquareRootImpact {
+    coefficient: f64,
+    max_participation: Option<f64>,
+    volume: Option<f64>,
+}
+
+impl SquareRootImpact {
+    /// Constructs a new [`SquareRootImpact`] slippage model.
+    pub fn new(coefficient: f64, max_participation: Option<f64>) -> Self {
+        Self {
+            coefficient,
+            max_participation,
+            volume: None,
+        }
+    }
+}
+
+impl SlippageModel for SquareRootImpact {
+    fn update_from_market(&mut self, market: &MarketEvent<DataKind>) {
+        match &market.kind {
+            DataKind::Candle(candle) => self.volume = Some(candle.volume),
+            DataKind::Trade(trade) => self.volume = Some(trade.amount),
+            _ => {}
+        }
+    }
+
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill {
+        let volume = match self.volume {
+            Some(volume) if volume > 0.0 => volume,
+            _ => return SlippedFill { price, quantity },
+        };
+
+        let quantity = match self.max_participation {
+            Some(participation) => quantity
+                .abs()
+                .min(participation * volume)
+                .copysign(quantity),
+            None => quantity,
+        };
+
+        let impact = self.coefficient * (quantity.abs() / volume).sqrt();
+        SlippedFill::adverse(quantity, price, impact)
+    }
+}
+
+/// Walks the levels of the most recent [`DataKind::OrderBook`] (or [`DataKind::OrderBookL1`]),
+/// filling at the volume weighted average price of the levels consumed. If the book does not
+/// have enough depth, only the available quantity is filled.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct OrderBookWalk {
+    /// Bid [`Level`]s, best (highest) first.
+    bids: Vec<Level>,
+    /// Ask [`Level`]s, best (lowest) first.
+    asks: Vec<Level>,
+}
+
+impl OrderBookWalk {
+    /// Constructs a new [`OrderBookWalk`] slippage model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extracts the finite [`Level`]s of an [`OrderBookSide`], which are only exposed via its
+    /// serde representation (where non-finite prices & amounts are serialised as null).
+    fn levels(side: &OrderBookSide) -> Vec<Level> {
+        #[derive(Deserialize)]
+        struct Levels {
+            levels: Vec<MaybeLevel>,
+        }
+
+        #[derive(Deserialize)]
+        struct MaybeLevel {
+            price: Option<f64>,
+            amount: Option<f64>,
+        }
+
+        match serde_json::to_value(side).and_then(serde_json::from_value::<Levels>) {
+            Ok(side) => side
+                .levels
+                .into_iter()
+                .filter_map(|level| match (level.price, level.amount) {
+                    (Some(price), Some(amount)) if price.is_finite() && amount.is_finite() => {
+                        Some(Level::new(price, amount))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            Err(error) => {
+                warn!(
+                    %error,
+                    action = "ignoring OrderBookSide",
+                    "OrderBookWalk failed to extract OrderBookSide Levels"
+                );
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl SlippageModel for OrderBookWalk {
+    fn update_from_market(&mut self, market: &MarketEvent<DataKind>) {
+        match &market.kind {
+            DataKind::OrderBookL1(book) => {
+                self.bids = vec![book.best_bid];
+                self.asks = vec![book.best_ask];
+            }
+            DataKind::OrderBook(book) => {
+                self.bids = Self::levels(&book.bids);
+                self.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+                self.asks = Self::levels(&book.asks);
+                self.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+            }
+            _ => {}
+        }
+    }
+
+    fn slip(&self, quantity: f64, price: f64) -> SlippedFill {
+        // Buys consume the asks, sells consume the bids
+        let levels = if quantity.is_sign_positive() {
+            &self.asks
+        } else {
+            &self.bids
+        };
+
+        if levels.is_empty() {
+            return SlippedFill { price, quantity };
+        }
+
+        let mut remaining = quantity.abs();
+        let mut filled = 0.0;
+        let mut value = 0.0;
+        for level in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let consumed = remaining.min(level.amount);
+            filled += consumed;
+            value += consumed * level.price;
+            remaining -= consumed;
+        }
+
+        if filled == 0.0 {
+            return SlippedFill {
+                price,
+                quantity: 0.0,
+            };
+        }
+
+        SlippedFill {
+            price: value / filled,
+            quantity: filled.copysign(quantity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+    use barter_data::subscription::book::OrderBook;
+    use barter_integration::model::Side;
+    use chrono::Utc;
+
+    #[test]
+    fn fixed_bps_moves_price_against_order_side() {
+        let model = FixedBps { bps: 10.0 };
+
+        let buy = model.slip(2.0, 1000.0);
+        assert!((buy.price - 1001.0).abs() < 1e-9);
+        assert_eq!(buy.quantity, 2.0);
+
+        let sell = model.slip(-2.0, 1000.0);
+        assert!((sell.price - 999.0).abs() < 1e-9);
+        assert_eq!(sell.quantity, -2.0);
+    }
+
+    #[test]
+    fn volatility_scaled_uses_rolling_return_volatility() {
+        let mut model = VolatilityScaled::new(1.0, 2);
+        assert_eq!(model.slip(1.0, 100.0).price, 100.0);
+
+        let mut market = market_event_candle();
+        for close in [100.0, 110.0, 99.0, 108.9] {
+            if let DataKind::Candle(candle) = &mut market.kind {
+                candle.close = close;
+            }
+            model.update_from_market(&market);
+        }
+
+        // Window holds the last two returns: -10% & +10%
+        let expected_volatility = (0.02_f64).sqrt();
+        assert!((model.volatility() - expected_volatility).abs() < 1e-9);
+        let fill = model.slip(1.0, 100.0);
+        assert!((fill.price - 100.0 * (1.0 + expected_volatility)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn square_root_impact_scales_with_participation_and_caps_quantity() {
+        let mut model = SquareRootImpact::new(0.1, Some(0.01));
+
+        // No volume observed yet
+        assert_eq!(model.slip(10.0, 100.0).price, 100.0);
+
+        // market_event_candle() volume is 100000.0
+        model.update_from_market(&market_event_candle());
+
+        let fill = model.slip(100.0, 100.0);
+        assert!((fill.price - 100.0 * (1.0 + 0.1 * (0.001_f64).sqrt())).abs() < 1e-9);
+        assert_eq!(fill.quantity, 100.0);
+
+        let fill = model.slip(-5000.0, 100.0);
+        assert_eq!(fill.quantity, -1000.0);
+        assert!((fill.price - 100.0 * (1.0 - 0.1 * (0.01_f64).sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn order_book_walk_consumes_levels_and_limits_quantity_to_depth() {
+        let mut model = OrderBookWalk::new();
+
+        let mut market = market_event_candle();
+        market.kind = DataKind::OrderBook(OrderBook {
+            last_update_time: Utc::now(),
+            bids: OrderBookSide::new(Side::Buy, [(99.0, 1.0), (98.0, 2.0)]),
+            asks: OrderBookSide::new(Side::Sell, [(102.0, 2.0), (101.0, 1.0)]),
+        });
+        model.update_from_market(&market);
+
+        let buy = model.slip(2.0, 100.0);
+        assert_eq!(buy.quantity, 2.0);
+        assert!((buy.price - 101.5).abs() < 1e-9);
+
+        let sell = model.slip(-5.0, 100.0);
+        assert_eq!(sell.quantity, -3.0);
+        assert!((sell.price - 295.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn order_book_walk_extracts_finite_levels_from_order_book_side_serde_shape() {
+        let side = OrderBookSide::new(
+            Side::Buy,
+            [(99.0, 1.0), (f64::NAN, 1.0), (98.0, f64::INFINITY)],
+        );
+
+        // OrderBookWalk relies on OrderBookSide serialising it's Levels under "levels"
+        let value = serde_json::to_value(OrderBookSide::new(Side::Buy, [(99.0, 1.0)])).unwrap();
+        assert_eq!(
+            value["levels"],
+            serde_json::json!([{ "price": 99.0, "amount": 1.0 }])
+        );
+
+        assert_eq!(OrderBookWalk::levels(&side), vec![Level::new(99.0, 1.0)]);
+    }
+}
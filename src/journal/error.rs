@@ -0,0 +1,21 @@
+use crate::portfolio::error::PortfolioError;
+use thiserror::Error;
+
+/// All errors generated in the barter::journal module.
+#[derive(Error, Debug)]
+pub enum JournalError {
+    #[error("Failed to read or write journal: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to (de)serialise JSON journal entry: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed to (de)serialise binary journal entry: {0}")]
+    Binary(#[from] bincode::Error),
+
+    #[error("Binary journal entry length {0} bytes exceeds the maximum of {1} bytes")]
+    EntryTooLarge(usize, usize),
+
+    #[error("Failed to replay journal entry {0} into Portfolio: {1}")]
+    Replay(u64, PortfolioError),
+}
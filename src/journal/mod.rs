@@ -0,0 +1,333 @@
+use crate::{
+    event::{Event, MessageTransmitter},
+    journal::error::JournalError,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Barter journal module specific errors.
+pub mod error;
+
+/// Rebuilds Portfolio state & re-drives [`Trader`](crate::engine::trader::Trader)s from a
+/// recorded journal.
+pub mod replay;
+
+/// Encoding used to persist [`JournalEntry`]s.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+pub enum JournalFormat {
+    /// Human readable, one JSON encoded [`JournalEntry`] per line.
+    #[default]
+    JsonLines,
+    /// Compact binary, each bincode encoded [`JournalEntry`] is prefixed by it's little endian
+    /// `u32` byte length.
+    Binary,
+}
+
+/// [`Event`] recorded in a journal, along with it's position in the journal & the time it was
+/// recorded.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct JournalEntry {
+    pub sequence: u64,
+    pub time: DateTime<Utc>,
+    pub event: Event,
+}
+
+/// Append-only journal sink for [`Event`]s. Implements [`MessageTransmitter`] so it can be used
+/// directly as a [`Trader`](crate::engine::trader::Trader) `EventTx`, or it can
+/// [`record`](Self::record) the [`Event`]s received by an [`EventBus`](crate::event::bus::EventBus)
+/// subscriber.
+#[derive(Debug)]
+pub struct JournalWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    format: JournalFormat,
+    sequence: u64,
+}
+
+impl JournalWriter<BufWriter<File>> {
+    /// Opens the journal file at the provided path for appending, creating it if it does not
+    /// exist.
+    pub fn create<P: AsRef<Path>>(path: P, format: JournalFormat) -> Result<Self, JournalError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file), format))
+    }
+}
+
+impl<W> JournalWriter<W>
+where
+    W: Write,
+{
+    /// Constructs a new [`JournalWriter`] that appends [`JournalEntry`]s to the provided writer.
+    pub fn new(writer: W, format: JournalFormat) -> Self {
+        Self {
+            writer,
+            format,
+            sequence: 0,
+        }
+    }
+
+    /// Appends the [`Event`] to the journal as the next [`JournalEntry`].
+    pub fn append(&mut self, event: Event) -> Result<(), JournalError> {
+        let entry = JournalEntry {
+            sequence: self.sequence,
+            time: Utc::now(),
+            event,
+        };
+
+        match self.format {
+            JournalFormat::JsonLines => {
+                serde_json::to_writer(&mut self.writer, &entry)?;
+                self.writer.write_all(b"\n")?;
+            }
+            JournalFormat::Binary => {
+                let bytes = bincode::serialize(&entry)?;
+                self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                self.writer.write_all(&bytes)?;
+            }
+        }
+
+        self.sequence += 1;
+        Ok(())
+    }
+
+    /// Flushes any buffered [`JournalEntry`]s to the underlying writer.
+    pub fn flush(&mut self) -> Result<(), JournalError> {
+        self.writer.flush().map_err(JournalError::from)
+    }
+
+    /// Appends every [`Event`] received until the provided receiver is closed, flushing after
+    /// each batch of [`Event`]s.
+    pub async fn record(
+        &mut self,
+        mut event_rx: mpsc::UnboundedReceiver<Event>,
+    ) -> Result<(), JournalError> {
+        while let Some(event) = event_rx.recv().await {
+            self.append(event)?;
+            while let Ok(event) = event_rx.try_recv() {
+                self.append(event)?;
+            }
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the [`JournalWriter`], returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> MessageTransmitter<Event> for JournalWriter<W>
+where
+    W: Write,
+{
+    fn send(&mut self, message: Event) {
+        if let Err(error) = self.append(message).and_then(|_| self.flush()) {
+            warn!(%error, sequence = self.sequence, "failed to journal Event");
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        let result = messages
+            .into_iter()
+            .try_for_each(|message| self.append(message))
+            .and_then(|_| self.flush());
+
+        if let Err(error) = result {
+            warn!(%error, sequence = self.sequence, "failed to journal Events");
+        }
+    }
+}
+
+/// Maximum byte length of a binary [`JournalEntry`] accepted by a [`JournalReader`], guarding
+/// against allocating for the corrupt length prefix of a damaged journal.
+pub const MAX_BINARY_ENTRY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Reads the [`JournalEntry`]s of a journal in the order they were recorded.
+#[derive(Debug)]
+pub struct JournalReader<R>
+where
+    R: BufRead,
+{
+    reader: R,
+    format: JournalFormat,
+    /// Set once an IO error or oversized entry has been encountered, since the remaining bytes
+    /// cannot be trusted.
+    finished: bool,
+}
+
+impl JournalReader<BufReader<File>> {
+    /// Opens the journal file at the provided path for reading.
+    pub fn open<P: AsRef<Path>>(path: P, format: JournalFormat) -> Result<Self, JournalError> {
+        Ok(Self::new(BufReader::new(File::open(path)?), format))
+    }
+}
+
+impl<R> JournalReader<R>
+where
+    R: BufRead,
+{
+    /// Constructs a new [`JournalReader`] that reads [`JournalEntry`]s from the provided reader.
+    pub fn new(reader: R, format: JournalFormat) -> Self {
+        Self {
+            reader,
+            format,
+            finished: false,
+        }
+    }
+
+    /// Reads the next JSON line, skipping blank lines.
+    fn next_json(&mut self) -> Option<Result<JournalEntry, JournalError>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => return Some(serde_json::from_str(&line).map_err(JournalError::from)),
+                Err(error) => return Some(Err(error.into())),
+            }
+        }
+    }
+
+    /// Reads the next length prefixed binary entry. A journal ending exactly on an entry
+    /// boundary is finished, whereas a truncated entry, or one longer than
+    /// [`MAX_BINARY_ENTRY_BYTES`], yields an error.
+    fn next_binary(&mut self) -> Option<Result<JournalEntry, JournalError>> {
+        match self.reader.fill_buf() {
+            Ok([]) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error.into())),
+        }
+
+        let mut length = [0; 4];
+        if let Err(error) = self.reader.read_exact(&mut length) {
+            return Some(Err(error.into()));
+        }
+
+        let length = u32::from_le_bytes(length) as usize;
+        if length > MAX_BINARY_ENTRY_BYTES {
+            return Some(Err(JournalError::EntryTooLarge(
+                length,
+                MAX_BINARY_ENTRY_BYTES,
+            )));
+        }
+
+        let mut bytes = vec![0; length];
+        if let Err(error) = self.reader.read_exact(&mut bytes) {
+            return Some(Err(error.into()));
+        }
+
+        Some(bincode::deserialize(&bytes).map_err(JournalError::from))
+    }
+}
+
+impl<R> Iterator for JournalReader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<JournalEntry, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let entry = match self.format {
+            JournalFormat::JsonLines => self.next_json(),
+            JournalFormat::Binary => self.next_binary(),
+        };
+
+        if let Some(Err(JournalError::Io(_) | JournalError::EntryTooLarge(..))) = &entry {
+            self.finished = true;
+        }
+
+        entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{fill_event, market_event_candle, order_event, position};
+    use std::io::Cursor;
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::Market(market_event_candle()),
+            Event::OrderNew(order_event()),
+            Event::Fill(fill_event()),
+            Event::PositionNew(position()),
+        ]
+    }
+
+    #[test]
+    fn journal_entries_round_trip_in_every_format() {
+        let events = events();
+
+        for format in [JournalFormat::JsonLines, JournalFormat::Binary] {
+            let mut writer = JournalWriter::new(Vec::new(), format);
+            writer.send_many(events.clone());
+
+            let entries = JournalReader::new(Cursor::new(writer.into_inner()), format)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            assert_eq!(
+                entries
+                    .iter()
+                    .map(|entry| entry.sequence)
+                    .collect::<Vec<_>>(),
+                vec![0, 1, 2, 3],
+                "{format:?}"
+            );
+            assert_eq!(
+                entries
+                    .into_iter()
+                    .map(|entry| entry.event)
+                    .collect::<Vec<_>>(),
+                events,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_binary_journal_yields_error_after_complete_entries() {
+        let mut writer = JournalWriter::new(Vec::new(), JournalFormat::Binary);
+        writer.send_many(events());
+        let mut bytes = writer.into_inner();
+        bytes.truncate(bytes.len() - 3);
+
+        let mut reader = JournalReader::new(Cursor::new(bytes), JournalFormat::Binary);
+        for _ in 0..3 {
+            assert!(reader.next().unwrap().is_ok());
+        }
+        assert!(matches!(reader.next(), Some(Err(JournalError::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn oversized_binary_entry_length_yields_error_without_allocating() {
+        let mut bytes = u32::MAX.to_le_bytes().to_vec();
+        bytes.extend([0; 16]);
+
+        let mut reader = JournalReader::new(Cursor::new(bytes), JournalFormat::Binary);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(JournalError::EntryTooLarge(length, MAX_BINARY_ENTRY_BYTES)))
+                if length == u32::MAX as usize
+        ));
+        assert!(reader.next().is_none());
+    }
+}
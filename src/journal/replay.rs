@@ -0,0 +1,245 @@
+use crate::{
+    data::historical::MarketFeed,
+    event::Event,
+    journal::{error::JournalError, JournalEntry, JournalFormat, JournalReader},
+    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::Market;
+use serde::{Deserialize, Serialize};
+use std::{io::BufRead, path::Path};
+
+/// Recorded [`JournalEntry`]s of an [`Engine`](crate::engine::Engine) session, used for
+/// post-mortems & crash recovery.
+///
+/// Replaying the journal's [`MarketEvent`]s, [`FillEvent`](crate::execution::FillEvent)s &
+/// [`SignalPositionAmend`](crate::strategy::SignalPositionAmend)s into a freshly initialised
+/// Portfolio (with the same starting cash & configuration as the recorded
+/// session) deterministically rebuilds it's repository state. The recorded [`MarketEvent`]s of
+/// each [`Market`] can also be used as a [`MarketFeed`] to re-drive a
+/// [`Trader`](crate::engine::trader::Trader).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Replay {
+    entries: Vec<JournalEntry>,
+}
+
+/// Summary of the [`Event`]s applied to a Portfolio by [`Replay::rebuild_portfolio`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct ReplaySummary {
+    /// Number of [`Event::Market`]s applied.
+    pub market_events: usize,
+    /// Number of [`Event::Fill`]s applied.
+    pub fills: usize,
+    /// Number of [`Event::SignalPositionAmend`]s applied.
+    pub amendments: usize,
+}
+
+impl Replay {
+    /// Constructs a [`Replay`] from the provided [`JournalEntry`]s, ordering them by sequence.
+    pub fn new<Entries: IntoIterator<Item = JournalEntry>>(entries: Entries) -> Self {
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.sequence);
+        Self { entries }
+    }
+
+    /// Reads every [`JournalEntry`] from the provided [`JournalReader`].
+    pub fn from_reader<R: BufRead>(reader: JournalReader<R>) -> Result<Self, JournalError> {
+        reader.collect::<Result<Vec<_>, _>>().map(Self::new)
+    }
+
+    /// Reads every [`JournalEntry`] from the journal file at the provided path.
+    pub fn open<P: AsRef<Path>>(path: P, format: JournalFormat) -> Result<Self, JournalError> {
+        Self::from_reader(JournalReader::open(path, format)?)
+    }
+
+    /// Recorded [`JournalEntry`]s, ordered by sequence.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Rebuilds the provided Portfolio's state by applying the recorded [`Event::Market`]s,
+    /// [`Event::Fill`]s & [`Event::SignalPositionAmend`]s in the order they were journaled.
+    /// Amendments originate from the strategy, which is not re-run, so are applied as recorded.
+    /// Every other [`Event`] is derived from these, so is skipped.
+    pub fn rebuild_portfolio<Portfolio>(
+        &self,
+        portfolio: &mut Portfolio,
+    ) -> Result<ReplaySummary, JournalError>
+    where
+        Portfolio: MarketUpdater + FillUpdater + OrderGenerator,
+    {
+        let mut summary = ReplaySummary::default();
+
+        for entry in &self.entries {
+            match &entry.event {
+                Event::Market(market) => {
+                    portfolio
+                        .update_from_market(market)
+                        .map_err(|error| JournalError::Replay(entry.sequence, error))?;
+                    summary.market_events += 1;
+                }
+                Event::Fill(fill) => {
+                    portfolio
+                        .update_from_fill(fill)
+                        .map_err(|error| JournalError::Replay(entry.sequence, error))?;
+                    summary.fills += 1;
+                }
+                Event::SignalPositionAmend(amend) => {
+                    portfolio
+                        .amend_position(amend)
+                        .map_err(|error| JournalError::Replay(entry.sequence, error))?;
+                    summary.amendments += 1;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Recorded [`MarketEvent`]s of the provided [`Market`], in the order they were journaled.
+    pub fn market_events(&self, market: &Market) -> Vec<MarketEvent<DataKind>> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.event {
+                Event::Market(event)
+                    if event.exchange == market.exchange
+                        && event.instrument == market.instrument =>
+                {
+                    Some(event.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Constructs a [`MarketFeed`] yielding the recorded [`MarketEvent`]s of the provided
+    /// [`Market`], used to deterministically re-drive a [`Trader`](crate::engine::trader::Trader).
+    pub fn market_feed(
+        &self,
+        market: &Market,
+    ) -> MarketFeed<std::vec::IntoIter<MarketEvent<DataKind>>, MarketEvent<DataKind>> {
+        MarketFeed::new(self.market_events(market))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::MessageTransmitter,
+        journal::JournalWriter,
+        portfolio::{
+            allocator::DefaultAllocator,
+            portfolio::MetaPortfolio,
+            repository::{in_memory::InMemoryRepository, PositionHandler},
+            risk::DefaultRisk,
+        },
+        statistic::summary::pnl::PnLReturnSummary,
+        strategy::{Decision, SignalPositionAmend},
+        test_util::{fill_event, market_event_candle, order_event},
+    };
+    use std::io::Cursor;
+    use uuid::Uuid;
+
+    fn portfolio(
+        engine_id: Uuid,
+    ) -> MetaPortfolio<
+        InMemoryRepository<PnLReturnSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        PnLReturnSummary,
+    > {
+        let fill = fill_event();
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![Market::new(fill.exchange, fill.instrument)])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap()
+    }
+
+    #[test]
+    fn rebuild_portfolio_from_journal_matches_recorded_session() {
+        let engine_id = Uuid::new_v4();
+        let mut recorded = portfolio(engine_id);
+        let mut journal = JournalWriter::new(Vec::new(), JournalFormat::Binary);
+
+        let mut enter = fill_event();
+        enter.decision = Decision::Long;
+        enter.quantity = 1.0;
+        enter.fill_value_gross = 100.0;
+        let mut exit = fill_event();
+        exit.position_signal_id = Some(enter.signal_id);
+        exit.decision = Decision::CloseLong;
+        exit.quantity = -1.0;
+        exit.fill_value_gross = 120.0;
+
+        // Record a session, journaling each Event along with the Portfolio side effects
+        let market_event = market_event_candle();
+        journal.send(Event::Market(market_event.clone()));
+        recorded.update_from_market(&market_event).unwrap();
+        for fill in [enter, exit] {
+            journal.send(Event::OrderNew(order_event()));
+            journal.send(Event::Fill(fill.clone()));
+            journal.send_many(recorded.update_from_fill(&fill).unwrap());
+        }
+
+        // Enter a second Position that is left open, then amend it's protective exit levels
+        let mut open = fill_event();
+        open.decision = Decision::Long;
+        open.quantity = 1.0;
+        open.fill_value_gross = 110.0;
+        journal.send(Event::Fill(open.clone()));
+        journal.send_many(recorded.update_from_fill(&open).unwrap());
+
+        let mut amend = SignalPositionAmend {
+            signal_id: open.signal_id,
+            time: open.time,
+            exchange: open.exchange.clone(),
+            instrument: open.instrument.clone(),
+            signal_extra: open.signal_extra,
+        };
+        amend.signal_extra.stop_loss_price = Some(100.0);
+        amend.signal_extra.take_profit_price = Some(130.0);
+        journal.send(Event::SignalPositionAmend(amend.clone()));
+        recorded.amend_position(&amend).unwrap();
+
+        let replay = Replay::from_reader(JournalReader::new(
+            Cursor::new(journal.into_inner()),
+            JournalFormat::Binary,
+        ))
+        .unwrap();
+
+        let mut rebuilt = portfolio(engine_id);
+        let summary = replay.rebuild_portfolio(&mut rebuilt).unwrap();
+
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                market_events: 1,
+                fills: 3,
+                amendments: 1,
+            }
+        );
+        let open_positions = rebuilt.get_all_open_positions().unwrap();
+        assert_eq!(open_positions, recorded.get_all_open_positions().unwrap());
+        assert_eq!(open_positions[0].signal_extra, amend.signal_extra);
+        assert_eq!(
+            rebuilt.get_exited_positions(engine_id).unwrap(),
+            recorded.get_exited_positions(engine_id).unwrap()
+        );
+
+        let market = Market::new(
+            market_event.exchange.clone(),
+            market_event.instrument.clone(),
+        );
+        assert_eq!(replay.market_events(&market), vec![market_event]);
+    }
+}
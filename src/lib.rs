@@ -98,6 +98,7 @@
 //! use std::marker::PhantomData;
 //! use uuid::Uuid;
 //! use barter::strategy::SignalInstrumentPositionsExit;
+//! use barter::clock::SharedClock;
 //!
 //! let components = PortfolioLego {
 //!     engine_id: Uuid::new_v4(),
@@ -111,6 +112,7 @@
 //!         trading_days_per_year: 365,
 //!         risk_free_return: 0.0
 //!     },
+//!     clock: SharedClock::default(),
 //!     _statistic_marker: PhantomData::<TradingSummary>::default()
 //! };
 //!
@@ -151,14 +153,15 @@
 //!         exchange: 0.1,
 //!         slippage: 0.05, // Simulated slippage modelled as a Fee
 //!         network: 0.0,
-//!     }
+//!     },
+//!     partial_fills: false,
 //! };
 //!
 //! let mut execution = SimulatedExecution::new(config);
 //!
 //! let order_event = test_util::order_event();
 //!
-//! let fill_event = execution.generate_fill(&order_event);
+//! let execution_reports = execution.submit_order(&order_event);
 //! ```
 //!
 //! ### Statistic
@@ -238,18 +241,33 @@ pub mod event;
 /// several key metrics such as Sharpe Ratio, Calmar Ratio, and Max Drawdown.
 pub mod statistic;
 
+/// Append-only journal of every [`Event`](event::Event) in JSON lines or compact binary format,
+/// as well as a [`Replay`](journal::replay::Replay) that rebuilds Portfolio state & re-drives
+/// Traders from the journal for post-mortems & crash recovery.
+pub mod journal;
+
+/// Clock abstraction providing the current time to the Engine, Portfolio & Execution components.
+/// Contains a wall-clock [`LiveClock`](clock::LiveClock) for live trading, and a
+/// [`HistoricalClock`](clock::HistoricalClock) driven by market time for backtests.
+pub mod clock;
+
 /// Multi-threaded trading Engine capable of trading with an arbitrary number market pairs. Contains
 /// a Trader for each Market pair that consists of it's own Data, Strategy &
 /// Execution components, as well as shared access to a global Portfolio.
 pub mod engine;
 
+/// Parameter sweep backtest runner that backtests every combination of a grid of strategy,
+/// allocator, risk & execution configurations with independent in-memory Engines in parallel,
+/// and ranks their trading summaries into a table exportable as CSV & JSON.
+pub mod backtest;
+
 #[macro_use]
 extern crate prettytable;
 
 pub mod test_util {
     use crate::{
         data::MarketMeta,
-        execution::{Fees, FillEvent},
+        execution::{order::OrderId, Fees, FillEvent},
         portfolio::{position::Position, OrderEvent, OrderType},
         strategy::{Decision, Signal, SignalExtra},
     };
@@ -309,12 +327,15 @@ pub mod test_util {
             suggest: Default::default(),
             market_meta: Default::default(),
             extra: SignalExtra::default(),
+            attribution: Vec::new(),
+            strategy_id: None,
         }
     }
 
     /// Build an [`OrderEvent`] to buy 1.0 contract.
     pub fn order_event() -> OrderEvent {
         OrderEvent {
+            order_id: OrderId::new(),
             signal_id: Uuid::new_v4(),
             time: Utc::now(),
             exchange: Exchange::from("binance"),
@@ -323,14 +344,18 @@ pub mod test_util {
             decision: Decision::default(),
             quantity: 1.0,
             order_type: OrderType::default(),
+            price: None,
             signal_extra: SignalExtra::default(),
             position_signal_id: None,
+            strategy_id: None,
+            attribution: Vec::new(),
         }
     }
 
     /// Build a [`FillEvent`] for a single bought contract.
     pub fn fill_event() -> FillEvent {
         FillEvent {
+            order_id: OrderId::new(),
             signal_id: Uuid::new_v4(),
             time: Utc::now(),
             exchange: Exchange::from("binance"),
@@ -342,6 +367,8 @@ pub mod test_util {
             fees: Fees::default(),
             signal_extra: SignalExtra::default(),
             position_signal_id: None,
+            strategy_id: None,
+            attribution: Vec::new(),
         }
     }
 
@@ -350,13 +377,17 @@ pub mod test_util {
         Position {
             instrument_id: "engine_id_trader_{}_{}_position".to_owned(),
             signal_id: Uuid::new_v4(),
+            strategy_id: None,
+            attribution: Vec::new(),
             exchange: Exchange::from("binance"),
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
             meta: Default::default(),
             side: Side::Buy,
             quantity: 1.0,
+            exit_quantity: 0.0,
             enter_fees: Default::default(),
             enter_fees_total: 0.0,
+            enter_fees_booked: 0.0,
             enter_avg_price_gross: 100.0,
             enter_value_gross: 100.0,
             exit_fees: Default::default(),
@@ -367,7 +398,9 @@ pub mod test_util {
             current_value_gross: 100.0,
             unrealised_profit_loss: 0.0,
             realised_profit_loss: 0.0,
+            fills: vec![],
             signal_extra: SignalExtra::default(),
+            protection: Default::default(),
         }
     }
 }
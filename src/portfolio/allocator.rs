@@ -1,11 +1,12 @@
 use crate::{
     portfolio::{
-        position::Position,
+        position::{Position, PositionMode},
         repository::{BalanceHandler, PositionHandler},
         OrderEvent,
     },
     strategy::{Decision, SuggestInfo},
 };
+use barter_integration::model::Side;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -39,7 +40,7 @@ where
 {
     fn allocate_order<'a, Positions: Iterator<Item = &'a Position>>(
         &self,
-        _repository: &Repository,
+        repository: &Repository,
         _engine_id: Uuid,
         order: &mut OrderEvent,
         instrument_positions: Positions,
@@ -49,25 +50,50 @@ where
         let default_order_size = self.default_order_value / order.market_meta.close;
         let default_order_size = (default_order_size * 10000.0).floor() / 10000.0;
 
+        // In PositionMode::Netting entry orders also close any Position open on the opposite
+        // Side, since the entry fill flips the net Position. In PositionMode::PerSignal opposite
+        // Positions are hedged & left untouched.
+        let netting = repository.position_mode() == PositionMode::Netting;
         match order.decision {
             // Entry
-            Decision::Long => order.quantity = default_order_size * signal_suggest_info.strength,
+            Decision::Long => {
+                order.quantity = default_order_size * signal_suggest_info.strength;
+                if netting {
+                    order.quantity -= opposite_quantity(instrument_positions, Side::Sell);
+                }
+            }
 
             // Entry
-            Decision::Short => order.quantity = -default_order_size * signal_suggest_info.strength,
+            Decision::Short => {
+                order.quantity = -default_order_size * signal_suggest_info.strength;
+                if netting {
+                    order.quantity -= opposite_quantity(instrument_positions, Side::Buy);
+                }
+            }
 
             // Exit
             _ => {
                 order.quantity = 0.0
                     - instrument_positions
                         .into_iter()
-                        .map(|p| p.quantity)
+                        .map(|p| p.remaining_quantity())
                         .sum::<f64>()
             }
         }
     }
 }
 
+/// Sums the +ve or -ve remaining quantity of the input [`Position`]s open on the provided [`Side`].
+fn opposite_quantity<'a, Positions: Iterator<Item = &'a Position>>(
+    positions: Positions,
+    side: Side,
+) -> f64 {
+    positions
+        .filter(|position| position.side == side)
+        .map(Position::remaining_quantity)
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,6 +105,10 @@ mod tests {
         InMemoryRepository::new()
     }
 
+    fn netting_repository() -> InMemoryRepository<TradingSummary> {
+        InMemoryRepository::with_position_mode(PositionMode::Netting)
+    }
+
     #[test]
     fn should_allocate_order_to_exit_open_long_position() {
         let allocator = DefaultAllocator {
@@ -252,4 +282,57 @@ mod tests {
         assert_ne!(actual_result, 0.0);
         assert_eq!(actual_result, expected_result)
     }
+
+    #[test]
+    fn should_allocate_order_to_enter_long_position_and_close_opposite_net_position() {
+        let allocator = DefaultAllocator {
+            default_order_value: 1000.0,
+        };
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 100.0;
+        input_order.decision = Decision::Long;
+
+        let mut input_position = position();
+        input_position.side = Side::Sell;
+        input_position.quantity = -3.0;
+
+        allocator.allocate_order(
+            &netting_repository(),
+            Uuid::new_v4(),
+            &mut input_order,
+            [input_position].iter(),
+            SuggestInfo::new_only_strength(1.0),
+        );
+
+        assert_eq!(input_order.quantity, 10.0 + 3.0)
+    }
+
+    #[test]
+    fn should_allocate_order_to_enter_long_position_ignoring_hedged_positions_per_signal() {
+        let allocator = DefaultAllocator {
+            default_order_value: 1000.0,
+        };
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 100.0;
+        input_order.decision = Decision::Long;
+
+        let mut long_position = position();
+        long_position.quantity = 2.0;
+
+        let mut short_position = position();
+        short_position.side = Side::Sell;
+        short_position.quantity = -30.0;
+
+        allocator.allocate_order(
+            &repository(),
+            Uuid::new_v4(),
+            &mut input_order,
+            [long_position, short_position].iter(),
+            SuggestInfo::new_only_strength(1.0),
+        );
+
+        assert_eq!(input_order.quantity, 10.0)
+    }
 }
@@ -19,6 +19,9 @@ pub enum PortfolioError {
     #[error("Cannot exit Position with an entry decision FillEvent.")]
     CannotExitPositionWithEntryFill,
 
+    #[error("Cannot exit more than the quantity remaining in the Position")]
+    ExitQuantityExceedsPosition,
+
     #[error("Cannot generate PositionExit from Position that has not been exited")]
     PositionExit,
 
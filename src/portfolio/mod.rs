@@ -2,9 +2,12 @@ use crate::portfolio::position::Position;
 use crate::{
     data::MarketMeta,
     event::Event,
-    execution::FillEvent,
+    execution::{order::OrderId, FillEvent},
     portfolio::{error::PortfolioError, position::PositionUpdateByMarket},
-    strategy::{Decision, Signal, SignalExtra, SignalInstrumentPositionsExit, SignalPositionExit},
+    strategy::{
+        Decision, Signal, SignalAttribution, SignalExtra, SignalInstrumentPositionsExit,
+        SignalPositionAmend, SignalPositionExit, StrategyId,
+    },
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{Exchange, Instrument};
@@ -27,6 +30,10 @@ pub mod portfolio;
 /// well as the logic for entering, updating and exiting them.
 pub mod position;
 
+/// Evaluation of protective exit levels (eg/ take profit & stop loss) for open
+/// [`Position`](position::Position)s.
+pub mod protection;
+
 /// Repositories for persisting Portfolio state.
 pub mod repository;
 
@@ -45,6 +52,7 @@ pub trait MarketUpdater {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum OrderGeneratorResult {
     OnlyExit(SignalInstrumentPositionsExit),
     OnlyNew(OrderEvent),
@@ -69,6 +77,16 @@ pub trait OrderGenerator {
         &mut self,
         signal: SignalPositionExit,
     ) -> Result<Option<OrderEvent>, PortfolioError>;
+
+    /// Replaces the protective exit levels of an open [`Position`](position::Position) with those
+    /// of the input [`SignalPositionAmend`], returning the amended
+    /// [`Position`](position::Position) if it is open. Protective orders already working at the
+    /// execution venue are replaced via
+    /// [`ExecutionClient::amend_protective_orders`](crate::execution::ExecutionClient::amend_protective_orders).
+    fn amend_position(
+        &mut self,
+        signal: &SignalPositionAmend,
+    ) -> Result<Option<Position>, PortfolioError>;
 }
 
 /// Updates the Portfolio from an input [`FillEvent`].
@@ -83,6 +101,9 @@ pub trait FillUpdater {
 /// open a trade.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct OrderEvent {
+    /// Unique identifier used to correlate the [`OrderUpdate`](crate::execution::order::OrderUpdate)s
+    /// & [`FillEvent`]s reported for this order.
+    pub order_id: OrderId,
     pub signal_id: Uuid,
     pub time: DateTime<Utc>,
     pub exchange: Exchange,
@@ -95,11 +116,22 @@ pub struct OrderEvent {
     pub quantity: f64,
     /// MARKET, LIMIT etc
     pub order_type: OrderType,
+    /// Limit price of an [`OrderType::Limit`] (or [`OrderType::Bracket`] entry), or trigger price
+    /// of an [`OrderType::Stop`]. A [`OrderType::Bracket`] without a price enters at market.
+    pub price: Option<f64>,
     // provide take profit and stop loss prices
     pub signal_extra: SignalExtra,
     // If the order is for an existing position, the signal id of the
     // previous position needs to be provided
     pub position_signal_id: Option<Uuid>,
+    /// Identifier of the strategy the originating [`Signal`] (or exited [`Position`]) is
+    /// attributed to.
+    #[serde(default)]
+    pub strategy_id: Option<StrategyId>,
+    /// Contribution of each member strategy to the originating combined [`Signal`] (or exited
+    /// [`Position`]), if any.
+    #[serde(default)]
+    pub attribution: Vec<SignalAttribution>,
 }
 
 impl OrderEvent {
@@ -111,32 +143,40 @@ impl OrderEvent {
         OrderEventBuilder::new()
     }
 
-    pub fn new(signal: &Signal, decision: Decision) -> Self {
+    /// Constructs a new [`OrderEvent`] for the provided [`Signal`] & [`Decision`], timestamped
+    /// with the provided time (eg/ from the Portfolio [`Clock`](crate::clock::Clock)).
+    pub fn new(signal: &Signal, decision: Decision, time: DateTime<Utc>) -> Self {
         OrderEvent {
+            order_id: OrderId::new(),
             signal_id: signal.signal_id,
-            time: Utc::now(),
+            time,
             exchange: signal.exchange.clone(),
             instrument: signal.instrument.clone(),
             market_meta: signal.market_meta,
             decision,
             quantity: 0.0,
             order_type: OrderType::default(),
+            price: None,
             signal_extra: signal.extra,
             position_signal_id: None,
+            strategy_id: signal.strategy_id.clone(),
+            attribution: signal.attribution.clone(),
         }
     }
 
-    /// generate a order to exit this position
+    /// generate a order to exit this position, timestamped with the provided time
     pub fn exit_order(
         position: &Position,
         signal_id: Uuid,
         exchange: Exchange,
         instrument: Instrument,
         signal_extra: Option<SignalExtra>,
+        time: DateTime<Utc>,
     ) -> Self {
         OrderEvent {
+            order_id: OrderId::new(),
             signal_id,
-            time: Utc::now(),
+            time,
             exchange,
             instrument,
             market_meta: MarketMeta {
@@ -144,10 +184,13 @@ impl OrderEvent {
                 time: position.meta.update_time,
             },
             decision: position.determine_exit_decision(),
-            quantity: 0.0 - position.quantity,
+            quantity: 0.0 - position.remaining_quantity(),
             order_type: OrderType::Market,
+            price: None,
             signal_extra: signal_extra.unwrap_or(SignalExtra::default()),
             position_signal_id: Some(position.signal_id),
+            strategy_id: position.strategy_id.clone(),
+            attribution: position.attribution.clone(),
         }
     }
 }
@@ -157,6 +200,9 @@ impl OrderEvent {
 pub enum OrderType {
     Market,
     Limit,
+    Stop,
+    /// Entry order with attached take profit & stop loss exit orders (one-cancels-the-other),
+    /// using the [`SignalExtra`] take_profit_price & stop_loss_price.
     Bracket,
 }
 
@@ -169,6 +215,7 @@ impl Default for OrderType {
 /// Builder to construct OrderEvent instances.
 #[derive(Debug, Default)]
 pub struct OrderEventBuilder {
+    pub order_id: Option<OrderId>,
     pub signal_id: Option<Uuid>,
     pub time: Option<DateTime<Utc>>,
     pub exchange: Option<Exchange>,
@@ -177,8 +224,11 @@ pub struct OrderEventBuilder {
     pub decision: Option<Decision>,
     pub quantity: Option<f64>,
     pub order_type: Option<OrderType>,
+    pub price: Option<f64>,
     pub signal_extra: Option<SignalExtra>,
     pub position_signal_id: Option<Uuid>,
+    pub strategy_id: Option<StrategyId>,
+    pub attribution: Option<Vec<SignalAttribution>>,
 }
 
 impl OrderEventBuilder {
@@ -186,6 +236,13 @@ impl OrderEventBuilder {
         Self::default()
     }
 
+    pub fn order_id(self, value: OrderId) -> Self {
+        Self {
+            order_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn signal_id(self, value: Uuid) -> Self {
         Self {
             signal_id: Some(value),
@@ -242,6 +299,13 @@ impl OrderEventBuilder {
         }
     }
 
+    pub fn price(self, value: f64) -> Self {
+        Self {
+            price: Some(value),
+            ..self
+        }
+    }
+
     pub fn signal_extra(self, value: SignalExtra) -> Self {
         Self {
             signal_extra: Some(value),
@@ -256,8 +320,23 @@ impl OrderEventBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
+    pub fn attribution(self, value: Vec<SignalAttribution>) -> Self {
+        Self {
+            attribution: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<OrderEvent, PortfolioError> {
         Ok(OrderEvent {
+            order_id: self.order_id.unwrap_or_default(),
             signal_id: self
                 .signal_id
                 .ok_or(PortfolioError::BuilderIncomplete("signal_id"))?,
@@ -280,10 +359,13 @@ impl OrderEventBuilder {
             order_type: self
                 .order_type
                 .ok_or(PortfolioError::BuilderIncomplete("order_type"))?,
+            price: self.price,
             signal_extra: self
                 .signal_extra
                 .ok_or(PortfolioError::BuilderIncomplete("signal_extra"))?,
             position_signal_id: self.position_signal_id,
+            strategy_id: self.strategy_id,
+            attribution: self.attribution.unwrap_or_default(),
         })
     }
 }
@@ -2,27 +2,32 @@ use super::{
     allocator::OrderAllocator,
     error::PortfolioError,
     position::{
-        determine_instrument_id, InstrumentId, Position, PositionEnterer, PositionExiter,
-        PositionUpdater,
+        determine_instrument_id, InstrumentId, Position, PositionEnterer, PositionExit,
+        PositionExiter, PositionMode, PositionScaler, PositionUpdate, PositionUpdater,
+        QUANTITY_TOLERANCE,
+    },
+    protection::ProtectiveExitEvaluator,
+    repository::{
+        determine_strategy_market_id, error::RepositoryError, BalanceHandler, PositionHandler,
+        StatisticHandler,
     },
-    repository::{error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler},
     risk::OrderEvaluator,
     Balance, FillUpdater, MarketUpdater, OrderEvent, OrderGenerator,
 };
 use crate::{
+    clock::{Clock, SharedClock},
     event::Event,
     execution::FillEvent,
     portfolio::position::PositionUpdateByMarket,
     portfolio::OrderGeneratorResult,
     statistic::summary::{Initialiser, PositionSummariser},
     strategy::{
-        Decision, Signal, SignalForceExit, SignalInstrumentPositionsExit, SignalPositionExit,
-        Suggest, SuggestInfo,
+        Decision, Signal, SignalForceExit, SignalInstrumentPositionsExit, SignalPositionAmend,
+        SignalPositionExit, StrategyId, Suggest, SuggestInfo,
     },
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{Market, MarketId, Side};
-use chrono::Utc;
 use serde::Serialize;
 use std::marker::PhantomData;
 use tracing::info;
@@ -55,11 +60,17 @@ where
     /// Configuration used to initialise the Statistics for every Market's performance tracked by a
     /// [`MetaPortfolio`].
     pub statistic_config: Statistic::Config,
+    /// [`Clock`] used to timestamp the [`Balance`]s, [`OrderEvent`]s & protective exit
+    /// [`SignalPositionExit`]s generated by a [`MetaPortfolio`].
+    pub clock: SharedClock,
     pub _statistic_marker: PhantomData<Statistic>,
 }
 
 /// Portfolio with state persisted in a repository. [`MarketUpdater`], [`OrderGenerator`],
 /// [`FillUpdater`] and [`PositionHandler`].
+///
+/// Open [`Position`]s are tracked using the [`PositionMode`] of the repository, either one
+/// [`Position`] per signal, or a single net [`Position`] per instrument.
 #[derive(Debug)]
 pub struct MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
@@ -77,6 +88,11 @@ where
     allocation_manager: Allocator,
     /// Risk manager implements [`OrderEvaluator`].
     risk_manager: RiskManager,
+    /// [`Clock`] used to timestamp generated [`Balance`]s, [`OrderEvent`]s & [`SignalPositionExit`]s.
+    clock: SharedClock,
+    /// Configuration used to initialise the Statistics of each strategy the first time one of
+    /// it's attributed [`Position`]s is exited.
+    statistic_config: Statistic::Config,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -102,32 +118,31 @@ where
             .get_open_instrument_positions(&instrument_id)?;
         let mut positions_update = Vec::with_capacity(positions.len());
         for mut position in positions {
+            // Evaluate the Position's protective exit levels against the market price range
+            // '--> before updating, so levels derived from this MarketEvent (eg/ trailing stop)
+            //      are only active from the next MarketEvent
+            let protective_exit = position.evaluate_protective_exit(market);
+
             // Derive PositionUpdate event that communicates the open Position's change in state
             if let Some(position_update) = position.update(market) {
                 let signal_extra = position.signal_extra;
-                let position_current_symbol_price = position.current_symbol_price;
+                let signal_id = position.signal_id;
 
                 // Save updated open Position in the repository
                 self.repository.set_open_position(position)?;
 
                 positions_update.push(PositionUpdateByMarket::Update(position_update));
 
-                if signal_extra
-                    .take_profit_price
-                    .map(|take_profit_price| position_current_symbol_price >= take_profit_price)
-                    .unwrap_or(false)
-                    || signal_extra
-                        .stop_loss_price
-                        .map(|stop_loss_price| position_current_symbol_price >= stop_loss_price)
-                        .unwrap_or(false)
-                {
+                if let Some(protective_exit) = protective_exit {
                     // generate a signal exit this position
                     let signal_position_exit = SignalPositionExit {
-                        signal_id: Uuid::new_v4(),
-                        time: Utc::now(),
+                        signal_id,
+                        time: self.clock.time(),
                         exchange: market.exchange.clone(),
                         instrument: market.instrument.clone(),
                         signal_extra,
+                        trigger: protective_exit.trigger,
+                        price: protective_exit.price,
                     };
                     positions_update.push(PositionUpdateByMarket::SignalExit(signal_position_exit));
                 }
@@ -160,7 +175,7 @@ where
         }
 
         // Parse signals from Strategy to determine net signal decision & associated strength
-        match parse_signal_suggest(&positions, &signal.suggest) {
+        match parse_signal_suggest(self.repository.position_mode(), &positions, &signal.suggest) {
             (Some(_), Some(_)) => {
                 let exit = SignalInstrumentPositionsExit {
                     signal_id: signal.signal_id,
@@ -184,7 +199,7 @@ where
                 Ok(OrderGeneratorResult::OnlyExit(exit))
             }
             (None, Some((open_decision, open_strength))) => {
-                let mut order = OrderEvent::new(signal, open_decision);
+                let mut order = OrderEvent::new(signal, open_decision, self.clock.time());
                 // Manage OrderEvent size allocation
                 self.allocation_manager.allocate_order(
                     &self.repository,
@@ -235,6 +250,7 @@ where
                     signal.signal_force_exit.exchange.clone(),
                     signal.signal_force_exit.instrument.clone(),
                     None,
+                    self.clock.time(),
                 )
             })
             .collect())
@@ -261,13 +277,47 @@ where
             return Ok(None);
         };
         let position = position.unwrap();
-        Ok(Some(OrderEvent::exit_order(
+        let mut order = OrderEvent::exit_order(
             &position,
             signal.signal_id,
             signal.exchange.clone(),
             signal.instrument,
             Some(signal.signal_extra),
-        )))
+            self.clock.time(),
+        );
+
+        // Exit at the price the protective exit level was touched, rather than the latest close
+        order.market_meta.close = signal.price;
+
+        Ok(Some(order))
+    }
+
+    fn amend_position(
+        &mut self,
+        signal: &SignalPositionAmend,
+    ) -> Result<Option<Position>, PortfolioError> {
+        let instrument_id =
+            determine_instrument_id(self.engine_id, &signal.exchange, &signal.instrument);
+
+        let mut position = match self
+            .repository
+            .get_open_position(&instrument_id, &signal.signal_id)?
+        {
+            Some(position) => position,
+            None => {
+                info!(
+                    instrument_id = &*instrument_id,
+                    outcome = "no Position amended",
+                    "cannot amend a Position that isn't open"
+                );
+                return Ok(None);
+            }
+        };
+
+        position.signal_extra = signal.signal_extra;
+        self.repository.set_open_position(position.clone())?;
+
+        Ok(Some(position))
     }
 }
 
@@ -296,58 +346,67 @@ where
                 let existing_position_signal_id = fill
                     .position_signal_id
                     .ok_or(PortfolioError::PositionExit)?;
-                if let Some(mut position) = self
+                if let Some(position) = self
                     .repository
                     .remove_position(&instrument_id, &existing_position_signal_id)?
                 {
                     // EXIT SCENARIO - FillEvent for Symbol-Exchange combination with open Position
-                    // Exit Position (in place mutation), & add the PositionExit event to Vec<Event>
-                    let position_exit = position.exit(balance, fill)?;
+                    let position_exit = self.exit_position(position, &mut balance, fill)?;
                     generated_events.push(Event::PositionExit(position_exit));
-
-                    // Update Portfolio balance on Position exit
-                    // '--> available balance adds enter_total_fees since included in result PnL calc
-                    balance.available += position.enter_value_gross
-                        + position.realised_profit_loss
-                        + position.enter_fees_total;
-                    balance.total += position.realised_profit_loss;
-
-                    // Update statistics for exited Position market
-                    let market_id = MarketId::new(&fill.exchange, &fill.instrument);
-
-                    let mut stats = self.repository.get_statistics(&market_id)?;
-                    stats.update(&position);
-
-                    // Persist exited Position & Updated Market statistics in Repository
-                    self.repository.set_statistics(market_id, stats)?;
-                    self.repository
-                        .set_exited_position(self.engine_id, position)?;
                 } else {
                     unreachable!("close a not exist position")
                 }
             }
-            // Enter new Position, & add the PositionNew event to Vec<Event>
+            // Enter new Position (or scale into the Position this entry fill belongs to)
             Decision::Long | Decision::Short => {
                 let existed_positions = self
                     .repository
                     .get_open_instrument_positions(&instrument_id)?;
-                if let Some(p) = existed_positions.first() {
-                    if (p.side == Side::Sell && fill.decision == Decision::Long)
-                        || (p.side == Side::Buy && fill.decision == Decision::Short)
-                    {
-                        return Err(PortfolioError::ExistingOppositePosition);
+                let entry_side = Position::parse_entry_side(fill)?;
+
+                let existing_position = match self.repository.position_mode() {
+                    PositionMode::PerSignal => {
+                        if existed_positions
+                            .first()
+                            .is_some_and(|position| position.side != entry_side)
+                        {
+                            return Err(PortfolioError::ExistingOppositePosition);
+                        }
+
+                        // Partial fills of the same entry order, or explicit scale-in orders,
+                        // increase the existing Position
+                        let position_signal_id = fill.position_signal_id.unwrap_or(fill.signal_id);
+                        existed_positions
+                            .into_iter()
+                            .find(|position| position.signal_id == position_signal_id)
                     }
-                }
-
-                let new_position = Position::enter(self.engine_id, fill)?;
-                generated_events.push(Event::PositionNew(new_position.clone()));
-
-                // Update Portfolio Balance.available on Position entry
-                balance.available +=
-                    -new_position.enter_value_gross - new_position.enter_fees_total;
+                    // Every entry fill is applied to the single net Position
+                    PositionMode::Netting => existed_positions.into_iter().next(),
+                };
 
-                // Add to current Positions in Repository
-                self.repository.set_open_position(new_position)?;
+                match existing_position {
+                    Some(position) if position.side == entry_side => {
+                        let position_update =
+                            self.scale_in_position(position, &mut balance, fill)?;
+                        generated_events.push(Event::PositionUpdate(
+                            PositionUpdateByMarket::Update(position_update),
+                        ));
+                    }
+                    Some(position) => {
+                        // NETTING SCENARIO - entry fill on the opposite Side reduces, closes or
+                        // flips the net Position
+                        self.net_opposite_fill(
+                            position,
+                            &mut balance,
+                            fill,
+                            &mut generated_events,
+                        )?;
+                    }
+                    None => {
+                        let new_position = self.enter_position(&mut balance, fill)?;
+                        generated_events.push(Event::PositionNew(new_position));
+                    }
+                }
             }
         }
         // Add new Balance event to the Vec<Event>
@@ -418,6 +477,23 @@ where
     }
 }
 
+impl<Repository, Allocator, RiskManager, Statistic> BalanceHandler
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator<Repository>,
+    RiskManager: OrderEvaluator<Repository>,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn set_balance(&mut self, _: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.repository.set_balance(self.engine_id, balance)
+    }
+
+    fn get_balance(&self, _: Uuid) -> Result<Balance, RepositoryError> {
+        self.repository.get_balance(self.engine_id)
+    }
+}
+
 impl<Repository, Allocator, RiskManager, Statistic> StatisticHandler<Statistic>
     for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
@@ -458,6 +534,8 @@ where
             repository: lego.repository,
             allocation_manager: lego.allocator,
             risk_manager: lego.risk,
+            clock: lego.clock,
+            statistic_config: lego.statistic_config,
             _statistic_marker: PhantomData::default(),
         };
 
@@ -483,7 +561,7 @@ where
         self.repository.set_balance(
             self.engine_id,
             Balance {
-                time: Utc::now(),
+                time: self.clock.time(),
                 total: starting_cash,
                 available: starting_cash,
             },
@@ -509,6 +587,159 @@ where
             .map(|balance| balance.available == 0.0)
             .map_err(PortfolioError::RepositoryInteraction)
     }
+
+    /// Enters a new [`Position`] from the input entry [`FillEvent`], persisting it in the
+    /// repository & updating the Portfolio [`Balance`].
+    fn enter_position(
+        &mut self,
+        balance: &mut Balance,
+        fill: &FillEvent,
+    ) -> Result<Position, PortfolioError> {
+        let new_position = Position::enter(self.engine_id, fill)?;
+
+        // Update Portfolio Balance.available on Position entry
+        balance.available += -new_position.enter_value_gross - new_position.enter_fees_total;
+
+        // Add to current Positions in Repository
+        self.repository.set_open_position(new_position.clone())?;
+
+        Ok(new_position)
+    }
+
+    /// Scales into the open [`Position`] using the input entry [`FillEvent`], persisting it in the
+    /// repository & updating the Portfolio [`Balance`].
+    fn scale_in_position(
+        &mut self,
+        mut position: Position,
+        balance: &mut Balance,
+        fill: &FillEvent,
+    ) -> Result<PositionUpdate, PortfolioError> {
+        let position_update = position.scale_in(fill)?;
+
+        // Update Portfolio Balance.available on Position scale in
+        balance.available += -fill.fill_value_gross - fill.fees.calculate_total_fees();
+
+        self.repository.set_open_position(position)?;
+
+        Ok(position_update)
+    }
+
+    /// Exits all, or part, of a [`Position`] that has been removed from the repository using the
+    /// input exit [`FillEvent`]. Fully exited [`Position`]s update the market statistics, and
+    /// partially exited [`Position`]s are returned to the open [`Position`]s.
+    fn exit_position(
+        &mut self,
+        mut position: Position,
+        balance: &mut Balance,
+        fill: &FillEvent,
+    ) -> Result<PositionExit, PortfolioError> {
+        // Exit all or part of the Position (in place mutation)
+        let exit_allocation = position.calculate_exit_allocation(fill.quantity);
+        let position_exit = position.exit(*balance, fill)?;
+
+        // Update Portfolio balance on Position exit
+        // '--> available balance adds the entry value & fees allocated to the quantity
+        //      exited, since entry fees are included in the result PnL calc
+        let fill_profit_loss = position_exit
+            .fill
+            .map_or(position.realised_profit_loss, |fill| {
+                fill.realised_profit_loss
+            });
+        balance.available += exit_allocation + fill_profit_loss;
+        balance.total += fill_profit_loss;
+
+        if position.is_exited() {
+            // Update statistics for exited Position market
+            let market_id = MarketId::new(&fill.exchange, &fill.instrument);
+
+            let mut stats = self.repository.get_statistics(&market_id)?;
+            stats.update(&position);
+
+            // Update statistics for the strategy the exited Position is attributed to, if any
+            if let Some(strategy_id) = &position.strategy_id {
+                self.update_strategy_statistics(&market_id, strategy_id, &position)?;
+            }
+
+            // Update statistics for each member strategy with it's weighted share of the Position
+            for (strategy_id, share) in position.attributed_shares() {
+                self.update_strategy_statistics(&market_id, &strategy_id, &share)?;
+            }
+
+            // Persist exited Position & Updated Market statistics in Repository
+            self.repository.set_statistics(market_id, stats)?;
+            self.repository
+                .set_exited_position(self.engine_id, position)?;
+        } else {
+            // Return partially exited Position to the open Positions
+            self.repository.set_open_position(position)?;
+        }
+
+        Ok(position_exit)
+    }
+
+    /// Updates the statistics of the provided strategy trading the [`MarketId`] with the exited
+    /// [`Position`], initialising them if this is the first [`Position`] attributed to it.
+    fn update_strategy_statistics(
+        &mut self,
+        market_id: &MarketId,
+        strategy_id: &StrategyId,
+        position: &Position,
+    ) -> Result<(), PortfolioError> {
+        let strategy_market_id = determine_strategy_market_id(market_id, strategy_id);
+
+        let mut strategy_stats = match self.repository.get_statistics(&strategy_market_id) {
+            Ok(strategy_stats) => strategy_stats,
+            Err(RepositoryError::ExpectedDataNotPresentError) => {
+                Statistic::init(self.statistic_config)
+            }
+            Err(error) => return Err(error.into()),
+        };
+        strategy_stats.update(position);
+        self.repository
+            .set_statistics(strategy_market_id, strategy_stats)?;
+
+        Ok(())
+    }
+
+    /// Applies an entry [`FillEvent`] on the opposite [`Side`] to the net [`Position`] in
+    /// [`PositionMode::Netting`]. The fill quantity first exits the net [`Position`], and any
+    /// remaining quantity enters a new [`Position`] on the fill [`Side`].
+    fn net_opposite_fill(
+        &mut self,
+        position: Position,
+        balance: &mut Balance,
+        fill: &FillEvent,
+        generated_events: &mut Vec<Event>,
+    ) -> Result<(), PortfolioError> {
+        let position_quantity = position.remaining_quantity();
+        let exit_quantity = fill
+            .quantity
+            .abs()
+            .min(position_quantity.abs())
+            .copysign(fill.quantity);
+        let flip_quantity = fill.quantity - exit_quantity;
+
+        let exit_decision = match position.side {
+            Side::Buy => Decision::CloseLong,
+            Side::Sell => Decision::CloseShort,
+        };
+        let mut exit_fill = fill.split(exit_quantity, exit_decision);
+        exit_fill.position_signal_id = Some(position.signal_id);
+
+        self.repository
+            .remove_position(&position.instrument_id, &position.signal_id)?;
+        let position_exit = self.exit_position(position, balance, &exit_fill)?;
+        generated_events.push(Event::PositionExit(position_exit));
+
+        // Only flip the net Position if the remaining quantity is not floating point error
+        if flip_quantity.abs() > fill.quantity.abs() * QUANTITY_TOLERANCE {
+            let enter_fill = fill.split(flip_quantity, fill.decision);
+            let new_position = self.enter_position(balance, &enter_fill)?;
+            generated_events.push(Event::PositionNew(new_position));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default)]
@@ -526,6 +757,7 @@ where
     allocation_manager: Option<Allocator>,
     risk_manager: Option<RiskManager>,
     statistic_config: Option<Statistic::Config>,
+    clock: Option<SharedClock>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -546,6 +778,7 @@ where
             allocation_manager: None,
             risk_manager: None,
             statistic_config: None,
+            clock: None,
             _statistic_marker: None,
         }
     }
@@ -599,9 +832,20 @@ where
         }
     }
 
+    pub fn clock(self, value: SharedClock) -> Self {
+        Self {
+            clock: Some(value),
+            ..self
+        }
+    }
+
     pub fn build_and_init(
         self,
     ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
+        let statistic_config = self
+            .statistic_config
+            .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?;
+
         // Construct Portfolio
         let mut portfolio = MetaPortfolio {
             engine_id: self
@@ -616,6 +860,8 @@ where
             risk_manager: self
                 .risk_manager
                 .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            clock: self.clock.unwrap_or_default(),
+            statistic_config,
             _statistic_marker: PhantomData::default(),
         };
 
@@ -626,22 +872,28 @@ where
             &self
                 .markets
                 .ok_or(PortfolioError::BuilderIncomplete("markets"))?,
-            self.statistic_config
-                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
+            statistic_config,
         )?;
 
         Ok(portfolio)
     }
 }
 
+/// Parses the [`Suggest`] of a [`Signal`] against the open [`Position`]s of it's instrument,
+/// returning the exit [`Decision`] (if any open [`Position`]s should be closed) & the entry
+/// [`Decision`] (if a [`Position`] should be entered).
+///
+/// In [`PositionMode::Netting`] the exit & entry are combined into a single entry [`Decision`],
+/// since an entry fill on the opposite [`Side`] closes & flips the net [`Position`].
 pub fn parse_signal_suggest<'a>(
+    position_mode: PositionMode,
     positions: &'a [Position],
     signal_suggest: &'a Suggest,
 ) -> (
     Option<(Decision, &'a SuggestInfo)>,
     Option<(Decision, &'a SuggestInfo)>,
 ) {
-    match (signal_suggest, positions.first().map(|p| p.side)) {
+    let suggestion = match (signal_suggest, positions.first().map(|p| p.side)) {
         (Suggest::SuggestLong(s), None) => (None, Some((Decision::Long, s))),
         (Suggest::SuggestLong(s), Some(Side::Buy)) => (
             None,
@@ -677,6 +929,11 @@ pub fn parse_signal_suggest<'a>(
                 None
             },
         ),
+    };
+
+    match (position_mode, suggestion) {
+        (PositionMode::Netting, (Some(_), Some(entry))) => (None, Some(entry)),
+        (_, suggestion) => suggestion,
     }
 }
 
@@ -688,12 +945,14 @@ pub mod tests {
     use crate::portfolio::allocator::DefaultAllocator;
     use crate::portfolio::position::PositionBuilder;
     use crate::portfolio::repository::error::RepositoryError;
+    use crate::portfolio::repository::in_memory::InMemoryRepository;
     use crate::portfolio::risk::DefaultRisk;
     use crate::portfolio::OrderType;
     use crate::statistic::summary::pnl::PnLReturnSummary;
-    use crate::strategy::{SignalExtra, SignalForceExit};
-    use crate::test_util::{fill_event, market_event_trade, position, signal};
+    use crate::strategy::{ExitTrigger, SignalAttribution, SignalExtra, SignalForceExit};
+    use crate::test_util::{fill_event, market_event_candle, market_event_trade, position, signal};
     use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+    use chrono::Utc;
 
     #[derive(Default)]
     struct MockRepository<Statistic> {
@@ -833,6 +1092,7 @@ pub mod tests {
     where
         Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
         Statistic: PositionSummariser + Initialiser,
+        Statistic::Config: Default,
     {
         let builder = MetaPortfolio::builder()
             .engine_id(Uuid::new_v4())
@@ -852,6 +1112,7 @@ pub mod tests {
     where
         Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
         Statistic: PositionSummariser + Initialiser,
+        Statistic::Config: Default,
     {
         Ok(MetaPortfolio {
             engine_id: builder
@@ -866,6 +1127,8 @@ pub mod tests {
             risk_manager: builder
                 .risk_manager
                 .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            clock: builder.clock.unwrap_or_default(),
+            statistic_config: builder.statistic_config.unwrap_or_default(),
             _statistic_marker: Default::default(),
         })
     }
@@ -1093,6 +1356,7 @@ pub mod tests {
                 input_position.signal_extra = SignalExtra {
                     take_profit_price: Some(200.0),
                     stop_loss_price: Some(50.0),
+                    ..Default::default()
                 };
                 input_position
             }])
@@ -1154,6 +1418,7 @@ pub mod tests {
                 input_position.signal_extra = SignalExtra {
                     take_profit_price: Some(200.0),
                     stop_loss_price: Some(50.0),
+                    ..Default::default()
                 };
                 input_position
             }])
@@ -1195,6 +1460,142 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn update_from_market_with_long_position_above_stop_loss_does_not_exit() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_instrument_positions = Some(|_| {
+            Ok(vec![{
+                let mut input_position = position();
+                input_position.side = Side::Buy;
+                input_position.quantity = 1.0;
+                input_position.signal_extra = SignalExtra {
+                    take_profit_price: Some(200.0),
+                    stop_loss_price: Some(50.0),
+                    ..Default::default()
+                };
+                input_position
+            }])
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent above the stop loss & below the take profit
+        let mut input_market = market_event_trade(Side::Buy);
+        if let DataKind::Trade(ref mut trade) = input_market.kind {
+            trade.price = 120.0;
+        }
+
+        let positions_update = portfolio.update_from_market(&input_market).unwrap();
+
+        assert_eq!(positions_update.len(), 1);
+        assert!(matches!(
+            positions_update.first().unwrap(),
+            PositionUpdateByMarket::Update(_)
+        ));
+    }
+
+    #[test]
+    fn update_from_market_with_short_position_stop_loss() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_instrument_positions = Some(|_| {
+            Ok(vec![{
+                let mut input_position = position();
+                input_position.side = Side::Sell;
+                input_position.quantity = -1.0;
+                input_position.signal_extra = SignalExtra {
+                    take_profit_price: Some(50.0),
+                    stop_loss_price: Some(150.0),
+                    ..Default::default()
+                };
+                input_position
+            }])
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent Candle that closes below the stop loss, but touches it intra-bar
+        let mut input_market = market_event_candle();
+        if let DataKind::Candle(ref mut candle) = input_market.kind {
+            candle.open = 100.0;
+            candle.high = 160.0;
+            candle.low = 95.0;
+            candle.close = 110.0;
+        }
+
+        let positions_update = portfolio.update_from_market(&input_market).unwrap();
+
+        assert_eq!(positions_update.len(), 2);
+        match positions_update.get(1).unwrap() {
+            PositionUpdateByMarket::SignalExit(signal) => {
+                assert_eq!(signal.trigger, ExitTrigger::StopLoss);
+                assert_eq!(signal.price, 150.0);
+            }
+            _ => panic!("expected PositionUpdateByMarket::SignalExit"),
+        }
+    }
+
+    #[test]
+    fn generate_exit_order_from_signal_position_exit_at_trigger_price() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_, _| {
+            let mut position = position();
+            position.side = Side::Buy;
+            position.quantity = 1.0;
+            position.current_symbol_price = 110.0;
+            Ok(Some(position))
+        });
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalPositionExit
+        let input_signal = SignalPositionExit {
+            signal_id: Uuid::new_v4(),
+            time: Utc::now(),
+            exchange: Exchange::from("binance"),
+            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            signal_extra: SignalExtra::default(),
+            trigger: ExitTrigger::StopLoss,
+            price: 90.0,
+        };
+
+        let actual = portfolio
+            .generate_exit_order(input_signal)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(actual.decision, Decision::CloseLong);
+        assert_eq!(actual.quantity, -1.0);
+        assert_eq!(actual.market_meta.close, 90.0);
+    }
+
+    #[test]
+    fn amend_position_replaces_protective_exit_levels_of_open_position() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_, _| Ok(Some(position())));
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalPositionAmend
+        let input_signal = SignalPositionAmend {
+            signal_id: Uuid::new_v4(),
+            time: Utc::now(),
+            exchange: Exchange::from("binance"),
+            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            signal_extra: SignalExtra {
+                take_profit_price: Some(120.0),
+                stop_loss_price: Some(95.0),
+                ..SignalExtra::default()
+            },
+        };
+
+        let actual = portfolio.amend_position(&input_signal).unwrap().unwrap();
+
+        assert_eq!(actual.signal_extra, input_signal.signal_extra);
+    }
+
     #[test]
     fn generate_no_order_with_no_position_and_no_cash() {
         // Build Portfolio
@@ -1728,7 +2129,8 @@ pub mod tests {
         let position = vec![position];
 
         let suggest = Suggest::new_short(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal.unwrap().0, Decision::CloseLong);
         assert_eq!(actual, None);
     }
@@ -1741,7 +2143,8 @@ pub mod tests {
         let position = vec![position];
 
         let suggest = Suggest::new_long(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal, None);
         assert_eq!(actual, None);
     }
@@ -1754,7 +2157,8 @@ pub mod tests {
         let position = vec![position];
 
         let suggest = Suggest::new_long(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal.unwrap().0, Decision::CloseShort);
         assert_eq!(actual, None);
     }
@@ -1767,7 +2171,8 @@ pub mod tests {
         let position = vec![position];
 
         let suggest = Suggest::new_short(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal, None);
         assert_eq!(actual, None);
     }
@@ -1777,7 +2182,8 @@ pub mod tests {
         let position = vec![];
 
         let suggest = Suggest::new_long(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal, None);
         assert_eq!(actual.unwrap().0, Decision::Long);
     }
@@ -1787,8 +2193,238 @@ pub mod tests {
         let position: Vec<Position> = vec![];
 
         let suggest = Suggest::new_short(SuggestInfo::new_only_strength(1.0));
-        let (close_signal, actual) = parse_signal_suggest(&position, &suggest);
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
         assert_eq!(close_signal, None);
         assert_eq!(actual.unwrap().0, Decision::Short);
     }
+
+    #[test]
+    fn update_from_fill_in_netting_mode_flips_then_closes_net_position() {
+        let fill = fill_event();
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                fill.exchange.clone(),
+                fill.instrument.clone(),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::with_position_mode(
+                PositionMode::Netting,
+            ))
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        // Enter net long Position
+        let mut long_fill = fill_event();
+        long_fill.decision = Decision::Long;
+        long_fill.quantity = 1.0;
+        long_fill.fill_value_gross = 100.0;
+        portfolio.update_from_fill(&long_fill).unwrap();
+
+        // Opposite entry fill from a different signal exits the long & flips the net Position
+        let mut short_fill = fill_event();
+        short_fill.decision = Decision::Short;
+        short_fill.quantity = -3.0;
+        short_fill.fill_value_gross = 330.0;
+        let events = portfolio.update_from_fill(&short_fill).unwrap();
+
+        assert!(matches!(events[0], Event::PositionExit(_)));
+        assert!(matches!(events[1], Event::PositionNew(_)));
+        let open_positions = portfolio.get_all_open_positions().unwrap();
+        assert_eq!(open_positions.len(), 1);
+        assert_eq!(open_positions[0].side, Side::Sell);
+        assert_eq!(open_positions[0].quantity, -2.0);
+        assert_eq!(open_positions[0].enter_avg_price_gross, 110.0);
+
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(balance.total, 1000.0 + 10.0);
+        assert_eq!(balance.available, 1000.0 + 10.0 - 220.0);
+
+        // Opposite entry fill of the same quantity closes the net Position
+        long_fill.signal_id = Uuid::new_v4();
+        long_fill.quantity = 2.0;
+        long_fill.fill_value_gross = 200.0;
+        portfolio.update_from_fill(&long_fill).unwrap();
+
+        assert!(portfolio.get_all_open_positions().unwrap().is_empty());
+        assert_eq!(
+            portfolio
+                .get_exited_positions(portfolio.engine_id)
+                .unwrap()
+                .len(),
+            2
+        );
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(balance.total, 1000.0 + 10.0 + 20.0);
+        assert_eq!(balance.available, balance.total);
+    }
+
+    #[test]
+    fn update_from_fill_exiting_attributed_position_updates_strategy_statistics() {
+        let fill = fill_event();
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                fill.exchange.clone(),
+                fill.instrument.clone(),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        // Enter long Position attributed to the "rsi" strategy
+        let mut enter_fill = fill_event();
+        enter_fill.decision = Decision::Long;
+        enter_fill.strategy_id = Some(StrategyId::from("rsi"));
+        portfolio.update_from_fill(&enter_fill).unwrap();
+
+        let open_positions = portfolio.get_all_open_positions().unwrap();
+        assert_eq!(open_positions[0].strategy_id, enter_fill.strategy_id);
+
+        // Exit the Position in profit
+        let mut exit_fill = enter_fill.clone();
+        exit_fill.signal_id = Uuid::new_v4();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+        exit_fill.position_signal_id = Some(enter_fill.signal_id);
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let market_id = MarketId::new(&fill.exchange, &fill.instrument);
+        let strategy_stats = portfolio
+            .get_statistics(&determine_strategy_market_id(
+                &market_id,
+                &StrategyId::from("rsi"),
+            ))
+            .unwrap();
+        let market_stats = portfolio.get_statistics(&market_id).unwrap();
+        assert_eq!(strategy_stats.total.count, 1);
+        assert_eq!(strategy_stats.total, market_stats.total);
+
+        // Unattributed strategies have no statistics
+        assert!(matches!(
+            portfolio.get_statistics(&determine_strategy_market_id(
+                &market_id,
+                &StrategyId::from("macd"),
+            )),
+            Err(RepositoryError::ExpectedDataNotPresentError)
+        ));
+    }
+
+    #[test]
+    fn update_from_fill_exiting_ensemble_position_updates_member_statistics() {
+        let fill = fill_event();
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                fill.exchange.clone(),
+                fill.instrument.clone(),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        // Enter long Position from a combined Signal the "sma" member voted against
+        let member = |strategy: &str, weight: f64, suggest: Suggest| SignalAttribution {
+            strategy: strategy.to_owned(),
+            weight,
+            suggest,
+        };
+        let mut enter_fill = fill_event();
+        enter_fill.decision = Decision::Long;
+        enter_fill.attribution = vec![
+            member(
+                "rsi",
+                3.0,
+                Suggest::new_long(SuggestInfo::new_only_strength(1.0)),
+            ),
+            member(
+                "macd",
+                1.0,
+                Suggest::new_long(SuggestInfo::new_only_strength(1.0)),
+            ),
+            member(
+                "sma",
+                1.0,
+                Suggest::new_short(SuggestInfo::new_only_strength(1.0)),
+            ),
+        ];
+        portfolio.update_from_fill(&enter_fill).unwrap();
+
+        let open_positions = portfolio.get_all_open_positions().unwrap();
+        assert_eq!(open_positions[0].attribution, enter_fill.attribution);
+
+        // Exit the Position in profit
+        let mut exit_fill = enter_fill.clone();
+        exit_fill.signal_id = Uuid::new_v4();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+        exit_fill.position_signal_id = Some(enter_fill.signal_id);
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let market_id = MarketId::new(&fill.exchange, &fill.instrument);
+        let market_stats = portfolio.get_statistics(&market_id).unwrap();
+        for strategy in ["rsi", "macd"] {
+            let member_stats = portfolio
+                .get_statistics(&determine_strategy_market_id(
+                    &market_id,
+                    &StrategyId::from(strategy),
+                ))
+                .unwrap();
+            assert_eq!(member_stats.total.count, 1);
+            assert_eq!(member_stats.total, market_stats.total);
+        }
+
+        // Members voting against the Position are not attributed it
+        assert!(matches!(
+            portfolio.get_statistics(&determine_strategy_market_id(
+                &market_id,
+                &StrategyId::from("sma"),
+            )),
+            Err(RepositoryError::ExpectedDataNotPresentError)
+        ));
+    }
+
+    #[test]
+    fn parse_signal_decisions_in_netting_mode_to_single_flipping_entry() {
+        let mut position = position();
+        position.side = Side::Sell;
+        let position = vec![position];
+
+        let suggest = Suggest::new_long(SuggestInfo::new(1.0, None, None, false, false));
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::Netting, &position, &suggest);
+        assert_eq!(close_signal, None);
+        assert_eq!(actual.unwrap().0, Decision::Long);
+
+        let (close_signal, actual) =
+            parse_signal_suggest(PositionMode::PerSignal, &position, &suggest);
+        assert_eq!(close_signal.unwrap().0, Decision::CloseShort);
+        assert_eq!(actual.unwrap().0, Decision::Long);
+    }
 }
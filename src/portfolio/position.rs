@@ -1,7 +1,11 @@
 use crate::{
-    execution::{FeeAmount, Fees, FillEvent},
-    portfolio::{error::PortfolioError, Balance},
-    strategy::{Decision, SignalExtra, SignalPositionExit},
+    execution::{order::OrderId, FeeAmount, Fees, FillEvent},
+    portfolio::{
+        error::PortfolioError,
+        protection::{PriceRange, ProtectionState},
+        Balance,
+    },
+    strategy::{Decision, SignalAttribution, SignalExtra, SignalPositionExit, StrategyId, Suggest},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{Exchange, Instrument, Side};
@@ -23,10 +27,18 @@ pub trait PositionUpdater {
     fn update(&mut self, market: &MarketEvent<DataKind>) -> Option<PositionUpdate>;
 }
 
+/// Scales into an open [`Position`].
+pub trait PositionScaler {
+    /// Increases an open [`Position`] using an entry [`FillEvent`] on the same [`Side`],
+    /// returning a [`PositionUpdate`] that communicates the open [`Position`]'s change in state.
+    fn scale_in(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError>;
+}
+
 /// Exits an open [`Position`].
 pub trait PositionExiter {
-    /// Exits an open [`Position`], given the input Portfolio equity & the [`FillEvent`] returned
-    /// from an Execution handler.
+    /// Exits all, or part, of an open [`Position`], given the input Portfolio equity & the
+    /// [`FillEvent`] returned from an Execution handler. Realised P&L is booked pro rata to the
+    /// quantity exited.
     fn exit(&mut self, balance: Balance, fill: &FillEvent) -> Result<PositionExit, PortfolioError>;
 }
 
@@ -58,6 +70,31 @@ pub fn determine_position_id(
     )
 }
 
+/// Determines how open [`Position`]s are tracked for each [`Instrument`].
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum PositionMode {
+    /// Each signal opens & exits it's own [`Position`], allowing many open [`Position`]s per
+    /// [`Instrument`] (hedge style).
+    #[default]
+    PerSignal,
+    /// A single net [`Position`] is held per [`Instrument`]. Entry fills on the opposite [`Side`]
+    /// reduce, close, or flip the net [`Position`].
+    Netting,
+}
+
+impl PositionMode {
+    /// Returns the key an open [`Position`] entered by the provided signal_id is stored under
+    /// for it's [`Instrument`]. In [`PositionMode::Netting`] every signal shares the same key.
+    pub fn position_key(&self, signal_id: Uuid) -> Uuid {
+        match self {
+            PositionMode::PerSignal => signal_id,
+            PositionMode::Netting => Uuid::nil(),
+        }
+    }
+}
+
 /// Data encapsulating the state of an ongoing or closed [`Position`].
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Position {
@@ -68,6 +105,16 @@ pub struct Position {
     /// Created by which signal
     pub signal_id: Uuid,
 
+    /// Identifier of the strategy that generated the entry [`Signal`](crate::strategy::Signal),
+    /// if attributed.
+    #[serde(default)]
+    pub strategy_id: Option<StrategyId>,
+
+    /// Contribution of each member strategy to the combined entry
+    /// [`Signal`](crate::strategy::Signal), if any.
+    #[serde(default)]
+    pub attribution: Vec<SignalAttribution>,
+
     /// Metadata detailing trace UUIDs, timestamps & equity associated with entering, updating & exiting.
     pub meta: PositionMeta,
 
@@ -84,19 +131,29 @@ pub struct Position {
     /// - Side::Sell considered synonymous with Short.
     pub side: Side,
 
-    /// +ve or -ve quantity of symbol contracts opened.
+    /// +ve or -ve quantity of symbol contracts opened, including any scaled in entries.
     pub quantity: f64,
 
+    /// -ve or +ve quantity of symbol contracts exited so far (opposite sign to quantity).
+    #[serde(default)]
+    pub exit_quantity: f64,
+
     /// All fees types incurred from entering a [`Position`], and their associated [`FeeAmount`].
     pub enter_fees: Fees,
 
     /// Total of enter_fees incurred. Sum of every [`FeeAmount`] in [`Fees`] when entering a [`Position`].
     pub enter_fees_total: FeeAmount,
 
-    /// Enter average price excluding the entry_fees_total.
+    /// Portion of the enter_fees_total already booked against the realised profit & loss of
+    /// exited quantity. The remainder is spread over the quantity still open.
+    #[serde(default)]
+    pub enter_fees_booked: FeeAmount,
+
+    /// Weighted average enter price of the quantity still open, excluding the entry_fees_total.
     pub enter_avg_price_gross: f64,
 
-    /// abs(Quantity) * enter_avg_price_gross.
+    /// Sum of the gross value of every entry fill (abs(Quantity) * enter_avg_price_gross if
+    /// never partially exited).
     pub enter_value_gross: f64,
 
     /// All fees types incurred from exiting a [`Position`], and their associated [`FeeAmount`].
@@ -108,7 +165,7 @@ pub struct Position {
     /// Exit average price excluding the exit_fees_total.
     pub exit_avg_price_gross: f64,
 
-    /// abs(Quantity) * exit_avg_price_gross.
+    /// abs(exit_quantity) * exit_avg_price_gross.
     pub exit_value_gross: f64,
 
     /// Symbol current close price.
@@ -120,11 +177,19 @@ pub struct Position {
     /// Unrealised P&L whilst the [`Position`] is open.
     pub unrealised_profit_loss: f64,
 
-    /// Realised P&L after the [`Position`] has closed.
+    /// Realised P&L booked by any (partial) exits.
     pub realised_profit_loss: f64,
 
+    /// Every [`FillEvent`] applied to this [`Position`], in the order they were applied.
+    #[serde(default)]
+    pub fills: Vec<PositionFill>,
+
     /// Optional take profit and stop loss price by signal.
     pub signal_extra: SignalExtra,
+
+    /// Protective exit state (eg/ trailing stop high-water mark) tracked as the market moves.
+    #[serde(default)]
+    pub protection: ProtectionState,
 }
 
 impl PositionEnterer for Position {
@@ -148,13 +213,17 @@ impl PositionEnterer for Position {
         Ok(Position {
             instrument_id: determine_instrument_id(engine_id, &fill.exchange, &fill.instrument),
             signal_id: fill.signal_id,
+            strategy_id: fill.strategy_id.clone(),
+            attribution: fill.attribution.clone(),
             exchange: fill.exchange.clone(),
             instrument: fill.instrument.clone(),
             meta: metadata,
             side: Position::parse_entry_side(fill)?,
             quantity: fill.quantity,
+            exit_quantity: 0.0,
             enter_fees: fill.fees,
             enter_fees_total,
+            enter_fees_booked: 0.0,
             enter_avg_price_gross,
             enter_value_gross: fill.fill_value_gross,
             exit_fees: Fees::default(),
@@ -165,7 +234,9 @@ impl PositionEnterer for Position {
             current_value_gross: fill.fill_value_gross,
             unrealised_profit_loss,
             realised_profit_loss: 0.0,
+            fills: vec![PositionFill::new(fill, enter_avg_price_gross, 0.0)],
             signal_extra: fill.signal_extra,
+            protection: ProtectionState::new(enter_avg_price_gross),
         })
     }
 }
@@ -186,16 +257,59 @@ impl PositionUpdater for Position {
         self.current_symbol_price = close;
 
         // Market value gross
-        self.current_value_gross = close * self.quantity.abs();
+        self.current_value_gross = close * self.remaining_quantity().abs();
 
         // Unreal profit & loss
         self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
 
+        // Protective exit state (eg/ trailing stop high-water mark)
+        if let Some(range) = PriceRange::from_market(market) {
+            self.protection.update(
+                self.side,
+                range,
+                self.enter_avg_price_gross,
+                &self.signal_extra,
+            );
+        }
+
         // Return a PositionUpdate event that communicates the change in state
         Some(PositionUpdate::from(self))
     }
 }
 
+impl PositionScaler for Position {
+    fn scale_in(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError> {
+        if Position::parse_entry_side(fill)? != self.side {
+            return Err(PortfolioError::ExistingOppositePosition);
+        }
+
+        // Weighted average enter price of the quantity still open
+        let remaining_quantity = self.remaining_quantity().abs();
+        self.enter_avg_price_gross = (self.enter_avg_price_gross * remaining_quantity
+            + fill.fill_value_gross)
+            / (remaining_quantity + fill.quantity.abs());
+
+        // Enter quantity, value & fees
+        self.quantity += fill.quantity;
+        self.enter_value_gross += fill.fill_value_gross;
+        self.enter_fees += fill.fees;
+        self.enter_fees_total += fill.fees.calculate_total_fees();
+
+        // Market value gross & unreal profit & loss
+        self.current_value_gross = self.current_symbol_price * self.remaining_quantity().abs();
+        self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+
+        self.meta.update_time = fill.time;
+        self.fills.push(PositionFill::new(
+            fill,
+            Position::calculate_avg_price_gross(fill),
+            0.0,
+        ));
+
+        Ok(PositionUpdate::from(self))
+    }
+}
+
 impl PositionExiter for Position {
     fn exit(
         &mut self,
@@ -206,27 +320,62 @@ impl PositionExiter for Position {
             return Err(PortfolioError::CannotExitPositionWithEntryFill);
         }
 
+        let exit_quantity = fill.quantity.abs();
+        if exit_quantity > self.remaining_quantity().abs() * (1.0 + QUANTITY_TOLERANCE) {
+            return Err(PortfolioError::ExitQuantityExceedsPosition);
+        }
+
+        // Realised profit & loss of the quantity exited, booking entry fees pro rata
+        let fill_avg_price_gross = Position::calculate_avg_price_gross(fill);
+        let fill_fees_total = fill.fees.calculate_total_fees();
+        let price_profit_loss = match self.side {
+            Side::Buy => fill_avg_price_gross - self.enter_avg_price_gross,
+            Side::Sell => self.enter_avg_price_gross - fill_avg_price_gross,
+        };
+        let enter_fees_booked = self.enter_fees_per_unit() * exit_quantity;
+        let fill_profit_loss =
+            price_profit_loss * exit_quantity - enter_fees_booked - fill_fees_total;
+
+        // Book the entry fees allocated to the quantity exited
+        self.enter_fees_booked += enter_fees_booked;
+
         // Exit fees
-        self.exit_fees = fill.fees;
-        self.exit_fees_total = fill.fees.calculate_total_fees();
+        self.exit_fees += fill.fees;
+        self.exit_fees_total += fill_fees_total;
 
-        // Exit value & price
-        self.exit_value_gross = fill.fill_value_gross;
-        self.exit_avg_price_gross = Position::calculate_avg_price_gross(fill);
+        // Exit quantity, value & price
+        self.exit_quantity += fill.quantity;
+        self.exit_value_gross += fill.fill_value_gross;
+        self.exit_avg_price_gross = (self.exit_value_gross / self.exit_quantity).abs();
 
         // Result profit & loss
-        self.realised_profit_loss = self.calculate_realised_profit_loss();
-        self.unrealised_profit_loss = self.realised_profit_loss;
+        self.realised_profit_loss += fill_profit_loss;
+        if self.is_exited() {
+            self.exit_quantity = -self.quantity;
+            self.unrealised_profit_loss = self.realised_profit_loss;
+        } else {
+            self.current_value_gross = self.current_symbol_price * self.remaining_quantity().abs();
+            self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+        }
 
         // Metadata
-        balance.total += self.realised_profit_loss;
+        balance.total += fill_profit_loss;
         self.meta.update_time = fill.time;
         self.meta.exit_balance = Some(balance);
+        self.fills.push(PositionFill::new(
+            fill,
+            fill_avg_price_gross,
+            fill_profit_loss,
+        ));
 
         PositionExit::try_from(self)
     }
 }
 
+/// Tolerance used when comparing fill quantities to the quantity remaining in a [`Position`], to
+/// absorb floating point error accumulated over many partial fills.
+pub(crate) const QUANTITY_TOLERANCE: f64 = 1e-9;
+
 impl Position {
     /// Returns a [`PositionBuilder`] instance.
     pub fn builder() -> PositionBuilder {
@@ -239,6 +388,31 @@ impl Position {
         (fill.fill_value_gross / fill.quantity).abs()
     }
 
+    /// +ve or -ve quantity of symbol contracts still open.
+    pub fn remaining_quantity(&self) -> f64 {
+        self.quantity + self.exit_quantity
+    }
+
+    /// Determines if the entire quantity of this [`Position`] has been exited.
+    pub fn is_exited(&self) -> bool {
+        self.remaining_quantity().abs() <= self.quantity.abs() * QUANTITY_TOLERANCE
+    }
+
+    /// Calculates the entry value & fees allocated to the input quantity of this [`Position`],
+    /// which are released back to the Portfolio balance when that quantity is exited.
+    pub fn calculate_exit_allocation(&self, quantity: f64) -> f64 {
+        (self.enter_avg_price_gross + self.enter_fees_per_unit()) * quantity.abs()
+    }
+
+    /// Entry fees not yet booked, spread over each symbol contract still open.
+    fn enter_fees_per_unit(&self) -> f64 {
+        let remaining_quantity = self.remaining_quantity().abs();
+        match remaining_quantity == 0.0 {
+            true => 0.0,
+            false => (self.enter_fees_total - self.enter_fees_booked) / remaining_quantity,
+        }
+    }
+
     /// Determine the [`Position`] entry [`Side`] by analysing the input [`FillEvent`].
     pub fn parse_entry_side(fill: &FillEvent) -> Result<Side, PortfolioError> {
         match fill.decision {
@@ -259,14 +433,28 @@ impl Position {
         }
     }
 
-    /// Calculate the approximate [`Position::unrealised_profit_loss`] of a [`Position`].
+    /// Calculate the approximate [`Position::unrealised_profit_loss`] of a [`Position`], including
+    /// any profit & loss already realised by partial exits.
     pub fn calculate_unrealised_profit_loss(&self) -> f64 {
-        let approx_total_fees = self.enter_fees_total * 2.0;
-
-        match self.side {
-            Side::Buy => self.current_value_gross - self.enter_value_gross - approx_total_fees,
-            Side::Sell => self.enter_value_gross - self.current_value_gross - approx_total_fees,
+        // Position never partially exited
+        if self.exit_quantity == 0.0 {
+            let approx_total_fees = self.enter_fees_total * 2.0;
+
+            return match self.side {
+                Side::Buy => self.current_value_gross - self.enter_value_gross - approx_total_fees,
+                Side::Sell => self.enter_value_gross - self.current_value_gross - approx_total_fees,
+            };
         }
+
+        let remaining_quantity = self.remaining_quantity().abs();
+        let open_value_gross = self.enter_avg_price_gross * remaining_quantity;
+        let approx_open_fees = self.enter_fees_per_unit() * remaining_quantity * 2.0;
+
+        self.realised_profit_loss
+            + match self.side {
+                Side::Buy => self.current_value_gross - open_value_gross - approx_open_fees,
+                Side::Sell => open_value_gross - self.current_value_gross - approx_open_fees,
+            }
     }
 
     /// Calculate the exact [`Position::realised_profit_loss`] of a [`Position`].
@@ -284,6 +472,67 @@ impl Position {
     pub fn calculate_profit_loss_return(&self) -> f64 {
         self.realised_profit_loss / self.enter_value_gross
     }
+
+    /// Returns the share of this [`Position`] attributed to each member strategy that voted for
+    /// it's [`Side`] in the combined entry [`Signal`](crate::strategy::Signal), in proportion to
+    /// the member weight.
+    pub fn attributed_shares(&self) -> Vec<(StrategyId, Position)> {
+        let agreeing = self
+            .attribution
+            .iter()
+            .filter(|member| {
+                matches!(
+                    (member.suggest, self.side),
+                    (Suggest::SuggestLong(_), Side::Buy) | (Suggest::SuggestShort(_), Side::Sell)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let total_weight = agreeing.iter().map(|member| member.weight).sum::<f64>();
+        if total_weight <= 0.0 {
+            return vec![];
+        }
+
+        agreeing
+            .into_iter()
+            .map(|member| {
+                (
+                    StrategyId::from(member.strategy.as_str()),
+                    self.scale(member.weight / total_weight),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns this [`Position`] with every quantity, value, fee & profit and loss multiplied by
+    /// the provided ratio. Prices & returns are unchanged.
+    pub fn scale(&self, ratio: f64) -> Position {
+        Position {
+            quantity: self.quantity * ratio,
+            exit_quantity: self.exit_quantity * ratio,
+            enter_fees: self.enter_fees.scale(ratio),
+            enter_fees_total: self.enter_fees_total * ratio,
+            enter_fees_booked: self.enter_fees_booked * ratio,
+            enter_value_gross: self.enter_value_gross * ratio,
+            exit_fees: self.exit_fees.scale(ratio),
+            exit_fees_total: self.exit_fees_total * ratio,
+            exit_value_gross: self.exit_value_gross * ratio,
+            current_value_gross: self.current_value_gross * ratio,
+            unrealised_profit_loss: self.unrealised_profit_loss * ratio,
+            realised_profit_loss: self.realised_profit_loss * ratio,
+            fills: self
+                .fills
+                .iter()
+                .map(|fill| PositionFill {
+                    quantity: fill.quantity * ratio,
+                    fees: fill.fees.scale(ratio),
+                    realised_profit_loss: fill.realised_profit_loss * ratio,
+                    ..*fill
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
 }
 
 /// Builder to construct [`Position`] instances.
@@ -291,13 +540,17 @@ impl Position {
 pub struct PositionBuilder {
     pub instrument_id: Option<InstrumentId>,
     pub signal_id: Option<Uuid>,
+    pub strategy_id: Option<StrategyId>,
+    pub attribution: Option<Vec<SignalAttribution>>,
     pub exchange: Option<Exchange>,
     pub instrument: Option<Instrument>,
     pub meta: Option<PositionMeta>,
     pub side: Option<Side>,
     pub quantity: Option<f64>,
+    pub exit_quantity: Option<f64>,
     pub enter_fees: Option<Fees>,
     pub enter_fees_total: Option<FeeAmount>,
+    pub enter_fees_booked: Option<FeeAmount>,
     pub enter_avg_price_gross: Option<f64>,
     pub enter_value_gross: Option<f64>,
     pub exit_fees: Option<Fees>,
@@ -308,7 +561,9 @@ pub struct PositionBuilder {
     pub current_value_gross: Option<f64>,
     pub unrealised_profit_loss: Option<f64>,
     pub realised_profit_loss: Option<f64>,
+    pub fills: Option<Vec<PositionFill>>,
     pub signal_extra: Option<SignalExtra>,
+    pub protection: Option<ProtectionState>,
 }
 
 impl PositionBuilder {
@@ -330,6 +585,20 @@ impl PositionBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
+    pub fn attribution(self, value: Vec<SignalAttribution>) -> Self {
+        Self {
+            attribution: Some(value),
+            ..self
+        }
+    }
+
     pub fn exchange(self, value: Exchange) -> Self {
         Self {
             exchange: Some(value),
@@ -365,6 +634,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn exit_quantity(self, value: f64) -> Self {
+        Self {
+            exit_quantity: Some(value),
+            ..self
+        }
+    }
+
     pub fn enter_fees(self, value: Fees) -> Self {
         Self {
             enter_fees: Some(value),
@@ -379,6 +655,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn enter_fees_booked(self, value: FeeAmount) -> Self {
+        Self {
+            enter_fees_booked: Some(value),
+            ..self
+        }
+    }
+
     pub fn enter_avg_price_gross(self, value: f64) -> Self {
         Self {
             enter_avg_price_gross: Some(value),
@@ -449,6 +732,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn fills(self, value: Vec<PositionFill>) -> Self {
+        Self {
+            fills: Some(value),
+            ..self
+        }
+    }
+
     pub fn signal_extra(self, value: SignalExtra) -> Self {
         Self {
             signal_extra: Some(value),
@@ -456,6 +746,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn protection(self, value: ProtectionState) -> Self {
+        Self {
+            protection: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Position, PortfolioError> {
         Ok(Position {
             instrument_id: self
@@ -464,6 +761,8 @@ impl PositionBuilder {
             signal_id: self
                 .signal_id
                 .ok_or(PortfolioError::BuilderIncomplete("signal_id"))?,
+            strategy_id: self.strategy_id,
+            attribution: self.attribution.unwrap_or_default(),
             exchange: self
                 .exchange
                 .ok_or(PortfolioError::BuilderIncomplete("exchange"))?,
@@ -475,12 +774,14 @@ impl PositionBuilder {
             quantity: self
                 .quantity
                 .ok_or(PortfolioError::BuilderIncomplete("quantity"))?,
+            exit_quantity: self.exit_quantity.unwrap_or_default(),
             enter_fees: self
                 .enter_fees
                 .ok_or(PortfolioError::BuilderIncomplete("enter_fees"))?,
             enter_fees_total: self
                 .enter_fees_total
                 .ok_or(PortfolioError::BuilderIncomplete("enter_fees_total"))?,
+            enter_fees_booked: self.enter_fees_booked.unwrap_or_default(),
             enter_avg_price_gross: self
                 .enter_avg_price_gross
                 .ok_or(PortfolioError::BuilderIncomplete("enter_avg_price_gross"))?,
@@ -511,9 +812,11 @@ impl PositionBuilder {
             realised_profit_loss: self
                 .realised_profit_loss
                 .ok_or(PortfolioError::BuilderIncomplete("realised_profit_loss"))?,
+            fills: self.fills.unwrap_or_default(),
             signal_extra: self
                 .signal_extra
                 .ok_or(PortfolioError::BuilderIncomplete("signal_extra"))?,
+            protection: self.protection.unwrap_or_default(),
         })
     }
 }
@@ -578,7 +881,48 @@ impl From<&mut Position> for PositionUpdate {
     }
 }
 
-/// [`Position`] exit event. Occurs as a result of a [`FillEvent`] that exits a [`Position`].
+/// Record of a [`FillEvent`] applied to a [`Position`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PositionFill {
+    /// Identifier of the order the [`FillEvent`] (partially) filled.
+    pub order_id: OrderId,
+
+    /// [`FillEvent`] timestamp.
+    pub time: DateTime<Utc>,
+
+    /// LONG, CloseLong, SHORT or CloseShort
+    pub decision: Decision,
+
+    /// +ve or -ve quantity filled.
+    pub quantity: f64,
+
+    /// Fill average price excluding fees.
+    pub avg_price_gross: f64,
+
+    /// All fee types incurred by the fill.
+    pub fees: Fees,
+
+    /// Realised P&L booked by the fill (0.0 for entry fills).
+    pub realised_profit_loss: f64,
+}
+
+impl PositionFill {
+    /// Constructs a new [`PositionFill`] from the input [`FillEvent`].
+    pub fn new(fill: &FillEvent, avg_price_gross: f64, realised_profit_loss: f64) -> Self {
+        Self {
+            order_id: fill.order_id,
+            time: fill.time,
+            decision: fill.decision,
+            quantity: fill.quantity,
+            avg_price_gross,
+            fees: fill.fees,
+            realised_profit_loss,
+        }
+    }
+}
+
+/// [`Position`] exit event. Occurs as a result of a [`FillEvent`] that exits all, or part, of a
+/// [`Position`].
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct PositionExit {
     /// Instrument identifier for a [`Position`], generated from an exchange, symbol, and enter_time.
@@ -602,8 +946,14 @@ pub struct PositionExit {
     /// abs(Quantity) * exit_avg_price_gross.
     pub exit_value_gross: f64,
 
-    /// Realised P&L after the [`Position`] has closed.
+    /// Realised P&L after the [`Position`] has closed, or booked so far if partially exited.
     pub realised_profit_loss: f64,
+
+    /// +ve or -ve quantity of the [`Position`] still open (0.0 once fully exited).
+    pub remaining_quantity: f64,
+
+    /// [`PositionFill`] of the [`FillEvent`] that triggered this exit.
+    pub fill: Option<PositionFill>,
 }
 
 impl TryFrom<&mut Position> for PositionExit {
@@ -622,6 +972,11 @@ impl TryFrom<&mut Position> for PositionExit {
             exit_avg_price_gross: exited_position.exit_avg_price_gross,
             exit_value_gross: exited_position.exit_value_gross,
             realised_profit_loss: exited_position.realised_profit_loss,
+            remaining_quantity: match exited_position.is_exited() {
+                true => 0.0,
+                false => exited_position.remaining_quantity(),
+            },
+            fill: exited_position.fills.last().copied(),
         })
     }
 }
@@ -629,6 +984,7 @@ impl TryFrom<&mut Position> for PositionExit {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::strategy::SuggestInfo;
     use crate::test_util::{fill_event, market_event_trade, position};
     use barter_integration::model::Side;
 
@@ -1696,4 +2052,223 @@ mod tests {
 
         assert!(PositionExit::try_from(&mut exited_position).is_err());
     }
+
+    #[test]
+    fn position_protection_state_survives_serde_round_trip() {
+        let mut position = position();
+        position.protection = ProtectionState {
+            high_water_mark: Some(150.0),
+            break_even_armed: true,
+        };
+
+        let serialised = serde_json::to_string(&position).unwrap();
+        let actual = serde_json::from_str::<Position>(&serialised).unwrap();
+
+        assert_eq!(actual.protection, position.protection);
+    }
+
+    #[test]
+    fn position_without_protection_state_deserialises_with_default() {
+        let position = position();
+        let mut serialised = serde_json::to_value(&position).unwrap();
+        serialised.as_object_mut().unwrap().remove("protection");
+
+        let actual = serde_json::from_value::<Position>(serialised).unwrap();
+
+        assert_eq!(actual.protection, ProtectionState::default());
+    }
+
+    #[test]
+    fn partial_exit_long_position_books_pro_rata_real_pnl() {
+        let mut position = position();
+        position.quantity = 2.0;
+        position.enter_value_gross = 200.0;
+        position.enter_fees_total = 2.0;
+        position.enter_fees = Fees {
+            exchange: 2.0,
+            slippage: 0.0,
+            network: 0.0,
+        };
+
+        let current_balance = Balance {
+            time: Utc::now(),
+            total: 10000.0,
+            available: 10000.0,
+        };
+
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 150.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 0.0,
+            network: 0.0,
+        };
+
+        let exit = position.exit(current_balance, &input_fill).unwrap();
+
+        // (exit price - enter price - enter fees per unit) * quantity - exit fees
+        let expected_profit_loss = (150.0 - 100.0 - 1.0) * 1.0 - 1.0;
+        assert!(!position.is_exited());
+        assert_eq!(position.remaining_quantity(), 1.0);
+        assert_eq!(position.realised_profit_loss, expected_profit_loss);
+        assert_eq!(exit.remaining_quantity, 1.0);
+        assert_eq!(
+            exit.fill.unwrap().realised_profit_loss,
+            expected_profit_loss
+        );
+        assert_eq!(
+            position.meta.exit_balance.unwrap().total,
+            current_balance.total + expected_profit_loss
+        );
+
+        // Exiting the remaining quantity fully exits the Position
+        input_fill.fill_value_gross = 90.0;
+        let exit = position.exit(current_balance, &input_fill).unwrap();
+
+        assert!(position.is_exited());
+        assert_eq!(exit.remaining_quantity, 0.0);
+        assert_eq!(
+            position.realised_profit_loss,
+            expected_profit_loss + (90.0 - 100.0 - 1.0) * 1.0 - 1.0
+        );
+        assert_eq!(position.exit_avg_price_gross, 120.0);
+    }
+
+    #[test]
+    fn scale_in_after_partial_exit_books_every_enter_fee_exactly_once() {
+        // Enter 1 @ 100 with 10 entry fees
+        let mut position = position();
+        position.enter_fees_total = 10.0;
+        position.enter_fees = Fees {
+            exchange: 10.0,
+            slippage: 0.0,
+            network: 0.0,
+        };
+
+        // Exit 0.5 @ 100, booking 5 of the entry fees
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -0.5;
+        exit_fill.fill_value_gross = 50.0;
+        exit_fill.fees = Fees::default();
+        position.exit(Balance::default(), &exit_fill).unwrap();
+
+        assert_eq!(position.enter_fees_booked, 5.0);
+        assert_eq!(position.realised_profit_loss, -5.0);
+
+        // Scale in 1 @ 100 with no entry fees
+        let mut enter_fill = fill_event();
+        enter_fill.decision = Decision::Long;
+        enter_fill.quantity = 1.0;
+        enter_fill.fill_value_gross = 100.0;
+        enter_fill.fees = Fees::default();
+        position.scale_in(&enter_fill).unwrap();
+
+        // Exit the remaining 1.5 @ 100, booking the remaining 5 of the entry fees
+        exit_fill.quantity = -1.5;
+        exit_fill.fill_value_gross = 150.0;
+        position.exit(Balance::default(), &exit_fill).unwrap();
+
+        assert!(position.is_exited());
+        assert_eq!(position.enter_fees_booked, 10.0);
+        assert_eq!(position.realised_profit_loss, -10.0);
+        assert_eq!(
+            position.realised_profit_loss,
+            position.calculate_realised_profit_loss()
+        );
+    }
+
+    #[test]
+    fn exit_more_than_remaining_quantity_is_rejected() {
+        let mut position = position();
+
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.5;
+        input_fill.fill_value_gross = 150.0;
+
+        let actual = position.exit(Balance::default(), &input_fill);
+
+        assert!(matches!(
+            actual,
+            Err(PortfolioError::ExitQuantityExceedsPosition)
+        ));
+        assert_eq!(position.remaining_quantity(), 1.0);
+    }
+
+    #[test]
+    fn scale_in_long_position_weights_enter_avg_price_gross() {
+        let mut position = position();
+
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        input_fill.quantity = 3.0;
+        input_fill.fill_value_gross = 360.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 1.0,
+            network: 1.0,
+        };
+
+        position.scale_in(&input_fill).unwrap();
+
+        assert_eq!(position.quantity, 4.0);
+        assert_eq!(position.enter_value_gross, 460.0);
+        assert_eq!(position.enter_avg_price_gross, 115.0);
+        assert_eq!(position.enter_fees_total, 3.0);
+        assert_eq!(position.fills.len(), 1);
+
+        // Scaling in with a fill on the opposite side is rejected
+        input_fill.decision = Decision::Short;
+        input_fill.quantity = -1.0;
+        assert!(matches!(
+            position.scale_in(&input_fill),
+            Err(PortfolioError::ExistingOppositePosition)
+        ));
+    }
+
+    #[test]
+    fn attributed_shares_weights_position_between_agreeing_members() {
+        let member = |strategy: &str, weight: f64, suggest: Suggest| SignalAttribution {
+            strategy: strategy.to_owned(),
+            weight,
+            suggest,
+        };
+        let long = Suggest::new_long(SuggestInfo::new_only_strength(1.0));
+        let short = Suggest::new_short(SuggestInfo::new_only_strength(1.0));
+
+        let mut position = position();
+        position.quantity = 4.0;
+        position.enter_value_gross = 400.0;
+        position.realised_profit_loss = 40.0;
+        position.attribution = vec![
+            member("rsi", 3.0, long),
+            member("macd", 1.0, long),
+            member("sma", 2.0, short),
+        ];
+
+        let shares = position.attributed_shares();
+        assert_eq!(shares.len(), 2);
+
+        let (strategy_id, share) = &shares[0];
+        assert_eq!(strategy_id, &StrategyId::from("rsi"));
+        assert_eq!(share.quantity, 3.0);
+        assert_eq!(share.enter_value_gross, 300.0);
+        assert_eq!(share.realised_profit_loss, 30.0);
+        assert_eq!(
+            share.calculate_profit_loss_return(),
+            position.calculate_profit_loss_return()
+        );
+
+        let (strategy_id, share) = &shares[1];
+        assert_eq!(strategy_id, &StrategyId::from("macd"));
+        assert_eq!(share.quantity, 1.0);
+        assert_eq!(share.realised_profit_loss, 10.0);
+
+        // Positions not entered by a combined Signal have no member shares
+        position.attribution.clear();
+        assert!(position.attributed_shares().is_empty());
+    }
 }
@@ -0,0 +1,483 @@
+use crate::{
+    portfolio::position::Position,
+    strategy::{ExitTrigger, SignalExtra},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::Side;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Evaluates the protective exit levels (eg/ take profit & stop loss) of an open [`Position`]
+/// against the latest [`MarketEvent`].
+pub trait ProtectiveExitEvaluator {
+    /// Returns a [`ProtectiveExit`] if the input [`MarketEvent`] touched one of the open
+    /// [`Position`]'s protective exit levels.
+    fn evaluate_protective_exit(&self, market: &MarketEvent<DataKind>) -> Option<ProtectiveExit>;
+}
+
+/// Protective exit level touched by a [`MarketEvent`], and the price the exit is expected to be
+/// executed at.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct ProtectiveExit {
+    /// Protective exit level that was touched.
+    pub trigger: ExitTrigger,
+    /// Expected execution price of the exit. This is the touched level, unless the market gapped
+    /// through the level, in which case it is the first price seen beyond it.
+    pub price: f64,
+}
+
+/// Price range traded during a [`MarketEvent`]. Used to detect intra-bar touches of protective
+/// exit levels that the close price alone would miss.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PriceRange {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl PriceRange {
+    /// Construct a [`PriceRange`] where every price is the same (eg/ a single trade).
+    pub fn point(price: f64) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+
+    /// Determine the [`PriceRange`] of the input [`MarketEvent`], if it contains price data.
+    pub fn from_market(market: &MarketEvent<DataKind>) -> Option<Self> {
+        match &market.kind {
+            DataKind::Trade(trade) => Some(Self::point(trade.price)),
+            DataKind::Candle(candle) => Some(Self {
+                open: candle.open,
+                high: candle.high,
+                low: candle.low,
+                close: candle.close,
+            }),
+            DataKind::OrderBookL1(book_l1) => Some(Self::point(book_l1.volume_weighed_mid_price())),
+            DataKind::OrderBook(book) => book.volume_weighed_mid_price().map(Self::point),
+            DataKind::Liquidation(_) => None,
+        }
+    }
+
+    /// Most favourable price traded for a [`Position`] of the provided [`Side`].
+    pub fn favourable(&self, side: Side) -> f64 {
+        match side {
+            Side::Buy => self.high,
+            Side::Sell => self.low,
+        }
+    }
+
+    /// Determines if a price level at or above `level` was traded.
+    fn touched_above(&self, level: f64) -> bool {
+        self.high >= level
+    }
+
+    /// Determines if a price level at or below `level` was traded.
+    fn touched_below(&self, level: f64) -> bool {
+        self.low <= level
+    }
+}
+
+/// Protective exit state of an open [`Position`] that evolves as the market moves (eg/ the
+/// trailing stop high-water mark). Persisted with the [`Position`] so it survives restarts.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct ProtectionState {
+    /// Most favourable price seen since entering the [`Position`] (highest price for a
+    /// [`Side::Buy`] position, lowest price for a [`Side::Sell`] position).
+    pub high_water_mark: Option<f64>,
+    /// Flags if the market has moved far enough in favour of the [`Position`] for the stop to be
+    /// moved to the entry price.
+    pub break_even_armed: bool,
+}
+
+impl ProtectionState {
+    /// Constructs a new [`ProtectionState`] for a [`Position`] entered at the provided price.
+    pub fn new(enter_price: f64) -> Self {
+        Self {
+            high_water_mark: Some(enter_price),
+            break_even_armed: false,
+        }
+    }
+
+    /// Updates the [`ProtectionState`] using the [`PriceRange`] traded during the latest
+    /// [`MarketEvent`].
+    pub fn update(
+        &mut self,
+        side: Side,
+        range: PriceRange,
+        enter_price: f64,
+        signal_extra: &SignalExtra,
+    ) {
+        let favourable = range.favourable(side);
+        let high_water_mark = match (side, self.high_water_mark) {
+            (_, None) => favourable,
+            (Side::Buy, Some(mark)) => mark.max(favourable),
+            (Side::Sell, Some(mark)) => mark.min(favourable),
+        };
+        self.high_water_mark = Some(high_water_mark);
+
+        if let Some(offset) = signal_extra.break_even_after {
+            let favourable_move = match side {
+                Side::Buy => high_water_mark - enter_price,
+                Side::Sell => enter_price - high_water_mark,
+            };
+            if favourable_move >= offset.distance(enter_price) {
+                self.break_even_armed = true;
+            }
+        }
+    }
+}
+
+impl ProtectiveExitEvaluator for Position {
+    fn evaluate_protective_exit(&self, market: &MarketEvent<DataKind>) -> Option<ProtectiveExit> {
+        let range = PriceRange::from_market(market)?;
+        evaluate_take_profit_stop_loss(
+            self.side,
+            range,
+            self.signal_extra.take_profit_price,
+            self.protective_stop(),
+        )
+        .or_else(|| self.evaluate_max_holding_duration(market.exchange_time, range))
+    }
+}
+
+impl Position {
+    /// Determines the tightest active protective stop level of this [`Position`], considering the
+    /// fixed stop loss, trailing stop & break-even stop.
+    pub fn protective_stop(&self) -> Option<ProtectiveExit> {
+        let fixed = self
+            .signal_extra
+            .stop_loss_price
+            .map(|price| ProtectiveExit {
+                trigger: ExitTrigger::StopLoss,
+                price,
+            });
+
+        let trailing = self
+            .signal_extra
+            .trailing_stop
+            .zip(self.protection.high_water_mark)
+            .map(|(offset, mark)| {
+                let distance = offset.distance(mark);
+                ProtectiveExit {
+                    trigger: ExitTrigger::TrailingStop,
+                    price: match self.side {
+                        Side::Buy => mark - distance,
+                        Side::Sell => mark + distance,
+                    },
+                }
+            });
+
+        let break_even = self.protection.break_even_armed.then_some(ProtectiveExit {
+            trigger: ExitTrigger::BreakEven,
+            price: self.enter_avg_price_gross,
+        });
+
+        [fixed, trailing, break_even]
+            .into_iter()
+            .flatten()
+            .reduce(|tightest, stop| {
+                let tighter = match self.side {
+                    Side::Buy => stop.price > tightest.price,
+                    Side::Sell => stop.price < tightest.price,
+                };
+                if tighter {
+                    stop
+                } else {
+                    tightest
+                }
+            })
+    }
+
+    /// Returns a [`ProtectiveExit`] at the close price if this [`Position`] has been open for at
+    /// least the [`SignalExtra::max_holding_duration`].
+    fn evaluate_max_holding_duration(
+        &self,
+        time: DateTime<Utc>,
+        range: PriceRange,
+    ) -> Option<ProtectiveExit> {
+        let max_holding_duration = self.signal_extra.max_holding_duration?;
+
+        (time.signed_duration_since(self.meta.enter_time) >= max_holding_duration).then_some(
+            ProtectiveExit {
+                trigger: ExitTrigger::MaxHoldingDuration,
+                price: range.close,
+            },
+        )
+    }
+}
+
+/// Evaluates a take profit level & a protective stop level against the [`PriceRange`] traded,
+/// taking the [`Side`] of the [`Position`] into account:
+/// - [`Side::Buy`]: take profit is above the entry and stop loss is below it.
+/// - [`Side::Sell`]: take profit is below the entry and stop loss is above it.
+///
+/// If both levels are touched within the same [`PriceRange`] the intra-bar ordering is unknown,
+/// so the stop is conservatively assumed to have been hit first.
+pub fn evaluate_take_profit_stop_loss(
+    side: Side,
+    range: PriceRange,
+    take_profit_price: Option<f64>,
+    stop: Option<ProtectiveExit>,
+) -> Option<ProtectiveExit> {
+    let stop = stop.and_then(|stop| {
+        let touched = match side {
+            Side::Buy => range.touched_below(stop.price),
+            Side::Sell => range.touched_above(stop.price),
+        };
+        touched.then(|| ProtectiveExit {
+            trigger: stop.trigger,
+            price: adverse_execution_price(side, range.open, stop.price),
+        })
+    });
+
+    let take_profit = take_profit_price.and_then(|target| {
+        let touched = match side {
+            Side::Buy => range.touched_above(target),
+            Side::Sell => range.touched_below(target),
+        };
+        touched.then(|| ProtectiveExit {
+            trigger: ExitTrigger::TakeProfit,
+            price: favourable_execution_price(side, range.open, target),
+        })
+    });
+
+    stop.or(take_profit)
+}
+
+/// Execution price of an exit at a level adverse to the [`Position`] (eg/ stop loss). If the market
+/// opened beyond the level it gapped through it, so the exit is executed at the open.
+pub(crate) fn adverse_execution_price(side: Side, open: f64, level: f64) -> f64 {
+    match side {
+        Side::Buy => open.min(level),
+        Side::Sell => open.max(level),
+    }
+}
+
+/// Execution price of an exit at a level favourable to the [`Position`] (eg/ take profit). If the
+/// market opened beyond the level it gapped through it, so the exit is executed at the open.
+pub(crate) fn favourable_execution_price(side: Side, open: f64, level: f64) -> f64 {
+    match side {
+        Side::Buy => open.max(level),
+        Side::Sell => open.min(level),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::position::PositionUpdater;
+    use crate::strategy::PriceOffset;
+    use crate::test_util::{market_event_candle, market_event_trade, position};
+    use chrono::Duration;
+
+    fn candle_market(open: f64, high: f64, low: f64, close: f64) -> MarketEvent<DataKind> {
+        let mut market = market_event_candle();
+        if let DataKind::Candle(ref mut candle) = market.kind {
+            candle.open = open;
+            candle.high = high;
+            candle.low = low;
+            candle.close = close;
+        }
+        market
+    }
+
+    fn trade_market(price: f64) -> MarketEvent<DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        if let DataKind::Trade(ref mut trade) = market.kind {
+            trade.price = price;
+        }
+        market
+    }
+
+    fn position_with(side: Side, take_profit: Option<f64>, stop_loss: Option<f64>) -> Position {
+        let mut position = position();
+        position.side = side;
+        position.signal_extra = SignalExtra {
+            take_profit_price: take_profit,
+            stop_loss_price: stop_loss,
+            ..Default::default()
+        };
+        position
+    }
+
+    #[test]
+    fn long_position_above_stop_loss_does_not_exit() {
+        let position = position_with(Side::Buy, Some(200.0), Some(50.0));
+
+        let actual = position.evaluate_protective_exit(&trade_market(120.0));
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn long_position_exits_on_stop_loss_touched_intra_bar() {
+        let position = position_with(Side::Buy, Some(200.0), Some(90.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(100.0, 110.0, 85.0, 105.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::StopLoss,
+                price: 90.0
+            })
+        );
+    }
+
+    #[test]
+    fn long_position_exits_on_take_profit_touched_intra_bar() {
+        let position = position_with(Side::Buy, Some(150.0), Some(50.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(100.0, 160.0, 95.0, 120.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::TakeProfit,
+                price: 150.0
+            })
+        );
+    }
+
+    #[test]
+    fn long_position_stop_loss_gap_executes_at_open() {
+        let position = position_with(Side::Buy, None, Some(90.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(80.0, 85.0, 70.0, 75.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::StopLoss,
+                price: 80.0
+            })
+        );
+    }
+
+    #[test]
+    fn short_position_exits_on_stop_loss_above_entry() {
+        let position = position_with(Side::Sell, Some(50.0), Some(120.0));
+
+        let actual = position.evaluate_protective_exit(&trade_market(125.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::StopLoss,
+                price: 125.0
+            })
+        );
+    }
+
+    #[test]
+    fn short_position_exits_on_take_profit_below_entry() {
+        let position = position_with(Side::Sell, Some(80.0), Some(120.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(100.0, 105.0, 75.0, 90.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::TakeProfit,
+                price: 80.0
+            })
+        );
+    }
+
+    #[test]
+    fn long_position_trailing_stop_follows_high_water_mark() {
+        let mut position = position_with(Side::Buy, None, Some(80.0));
+        position.signal_extra.trailing_stop = Some(PriceOffset::Percent(0.1));
+        position.protection = ProtectionState::new(100.0);
+
+        // Market rallies to 150, so the trailing stop moves up to 135
+        position.update(&candle_market(100.0, 150.0, 100.0, 140.0));
+        assert_eq!(position.protection.high_water_mark, Some(150.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(140.0, 142.0, 130.0, 131.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::TrailingStop,
+                price: 135.0
+            })
+        );
+    }
+
+    #[test]
+    fn short_position_trailing_stop_follows_low_water_mark() {
+        let mut position = position_with(Side::Sell, None, None);
+        position.signal_extra.trailing_stop = Some(PriceOffset::Absolute(10.0));
+        position.protection = ProtectionState::new(100.0);
+
+        position.update(&trade_market(70.0));
+        assert_eq!(position.protection.high_water_mark, Some(70.0));
+
+        assert_eq!(position.evaluate_protective_exit(&trade_market(79.0)), None);
+        assert_eq!(
+            position.evaluate_protective_exit(&trade_market(81.0)),
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::TrailingStop,
+                price: 81.0
+            })
+        );
+    }
+
+    #[test]
+    fn long_position_stop_moves_to_entry_after_break_even_offset_reached() {
+        let mut position = position_with(Side::Buy, None, Some(90.0));
+        position.enter_avg_price_gross = 100.0;
+        position.signal_extra.break_even_after = Some(PriceOffset::Absolute(20.0));
+        position.protection = ProtectionState::new(100.0);
+
+        position.update(&trade_market(115.0));
+        assert!(!position.protection.break_even_armed);
+
+        position.update(&trade_market(121.0));
+        assert!(position.protection.break_even_armed);
+
+        let actual = position.evaluate_protective_exit(&trade_market(99.0));
+
+        assert_eq!(
+            actual,
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::BreakEven,
+                price: 99.0
+            })
+        );
+    }
+
+    #[test]
+    fn position_exits_after_max_holding_duration() {
+        let mut position = position_with(Side::Buy, None, None);
+        position.signal_extra.max_holding_duration = Some(Duration::hours(4));
+
+        let mut market = trade_market(105.0);
+        market.exchange_time = position.meta.enter_time + Duration::hours(3);
+        assert_eq!(position.evaluate_protective_exit(&market), None);
+
+        market.exchange_time = position.meta.enter_time + Duration::hours(4);
+        assert_eq!(
+            position.evaluate_protective_exit(&market),
+            Some(ProtectiveExit {
+                trigger: ExitTrigger::MaxHoldingDuration,
+                price: 105.0
+            })
+        );
+    }
+
+    #[test]
+    fn stop_loss_takes_priority_when_both_levels_touched_in_same_bar() {
+        let position = position_with(Side::Buy, Some(110.0), Some(90.0));
+
+        let actual = position.evaluate_protective_exit(&candle_market(100.0, 115.0, 85.0, 100.0));
+
+        assert_eq!(actual.map(|exit| exit.trigger), Some(ExitTrigger::StopLoss));
+    }
+}
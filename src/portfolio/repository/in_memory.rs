@@ -1,6 +1,6 @@
 use crate::{
     portfolio::{
-        position::{determine_instrument_id, InstrumentId, Position},
+        position::{determine_instrument_id, InstrumentId, Position, PositionMode},
         repository::{
             determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
             StatisticHandler,
@@ -19,6 +19,7 @@ use uuid::Uuid;
 /// **Careful in production - no fault tolerant guarantees!**
 #[derive(Debug, Default)]
 pub struct InMemoryRepository<Statistic: PositionSummariser> {
+    position_mode: PositionMode,
     open_positions: HashMap<InstrumentId, HashMap<Uuid, Position>>,
     closed_positions: HashMap<String, Vec<Position>>,
     current_balances: HashMap<BalanceId, Balance>,
@@ -26,9 +27,13 @@ pub struct InMemoryRepository<Statistic: PositionSummariser> {
 }
 
 impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Statistic> {
+    fn position_mode(&self) -> PositionMode {
+        self.position_mode
+    }
+
     fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
         let instrument_id = position.instrument_id.clone();
-        let signal_id = position.signal_id;
+        let signal_id = self.position_mode.position_key(position.signal_id);
 
         if let Some(positions) = self.open_positions.get_mut(&instrument_id) {
             positions.insert(signal_id, position);
@@ -84,7 +89,9 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         Ok(self
             .open_positions
             .get(instrument_id)
-            .and_then(|instrument_position| instrument_position.get(signal_id))
+            .and_then(|instrument_position| {
+                instrument_position.get(&self.position_mode.position_key(*signal_id))
+            })
             .cloned())
     }
 
@@ -96,7 +103,9 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         let p = self
             .open_positions
             .get_mut(instrument_id)
-            .and_then(|instrument_positions| instrument_positions.remove(signal_id))
+            .and_then(|instrument_positions| {
+                instrument_positions.remove(&self.position_mode.position_key(*signal_id))
+            })
             .ok_or(RepositoryError::DeleteError)?;
 
         Ok(Some(p))
@@ -164,7 +173,14 @@ impl<Statistic: PositionSummariser> StatisticHandler<Statistic> for InMemoryRepo
 impl<Statistic: PositionSummariser> InMemoryRepository<Statistic> {
     /// Constructs a new [`InMemoryRepository`] component.
     pub fn new() -> Self {
+        Self::with_position_mode(PositionMode::PerSignal)
+    }
+
+    /// Constructs a new [`InMemoryRepository`] component that keys open [`Position`]s using the
+    /// provided [`PositionMode`].
+    pub fn with_position_mode(position_mode: PositionMode) -> Self {
         Self {
+            position_mode,
             open_positions: HashMap::new(),
             closed_positions: HashMap::new(),
             current_balances: HashMap::new(),
@@ -294,4 +310,57 @@ mod tests {
         let position = position.unwrap();
         assert_eq!(position.signal_id, btc2.signal_id)
     }
+
+    #[test]
+    fn protection_state_persisted_with_open_position() {
+        let engine_id = Uuid::new_v4();
+        let mut repo: InMemoryRepository<TradingSummary> = InMemoryRepository::new();
+        let (mut btc1, _btc2, _eth1) = positions(engine_id);
+        btc1.protection.high_water_mark = Some(250.0);
+        btc1.protection.break_even_armed = true;
+
+        repo.set_open_position(btc1.clone()).unwrap();
+
+        let position = repo
+            .get_open_position(&btc1.instrument_id, &btc1.signal_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.protection, btc1.protection);
+    }
+
+    #[test]
+    fn netting_mode_holds_single_position_per_instrument() {
+        let engine_id = Uuid::new_v4();
+        let mut repo: InMemoryRepository<TradingSummary> =
+            InMemoryRepository::with_position_mode(PositionMode::Netting);
+        let (btc1, btc2, eth1) = positions(engine_id);
+
+        repo.set_open_position(btc1.clone()).unwrap();
+        repo.set_open_position(btc2.clone()).unwrap();
+        repo.set_open_position(eth1.clone()).unwrap();
+
+        let positions = repo
+            .get_open_instrument_positions(&btc1.instrument_id)
+            .unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].signal_id, btc2.signal_id);
+        assert_eq!(repo.get_all_open_positions().unwrap().len(), 2);
+
+        // Net Position is retrieved & removed regardless of the signal_id provided
+        let position = repo
+            .get_open_position(&btc1.instrument_id, &btc1.signal_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.signal_id, btc2.signal_id);
+
+        let position = repo
+            .remove_position(&btc1.instrument_id, &Uuid::new_v4())
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.signal_id, btc2.signal_id);
+        assert!(repo
+            .get_open_instrument_positions(&btc1.instrument_id)
+            .unwrap()
+            .is_empty());
+    }
 }
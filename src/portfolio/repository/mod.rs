@@ -1,8 +1,9 @@
 use crate::portfolio::{
-    position::{InstrumentId, Position},
+    position::{InstrumentId, Position, PositionMode},
     repository::error::RepositoryError,
     Balance,
 };
+use crate::strategy::StrategyId;
 use barter_integration::model::{Market, MarketId};
 use uuid::Uuid;
 
@@ -17,6 +18,14 @@ pub mod redis;
 
 /// Handles the reading & writing of a [`Position`] to/from the persistence layer.
 pub trait PositionHandler {
+    /// Returns the [`PositionMode`] open [`Position`]s are keyed by. In
+    /// [`PositionMode::Netting`] the signal_id provided to [`PositionHandler::get_open_position`]
+    /// & [`PositionHandler::remove_position`] is ignored, since only one [`Position`] is held
+    /// per [`InstrumentId`].
+    fn position_mode(&self) -> PositionMode {
+        PositionMode::PerSignal
+    }
+
     /// Upsert the open [`Position`] using it's [`InstrumentId`].
     fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError>;
 
@@ -70,7 +79,8 @@ pub trait BalanceHandler {
 }
 
 /// Handles the reading & writing of a Portfolio's statistics for each of it's
-/// markets, where each market is represented by a [`MarketId`].
+/// markets, where each market is represented by a [`MarketId`]. Statistics per (market, strategy)
+/// are stored at the [`MarketId`] returned by [`determine_strategy_market_id`].
 pub trait StatisticHandler<Statistic> {
     /// Upsert the market statistics at the [`MarketId`] provided.
     fn set_statistics(
@@ -90,3 +100,9 @@ pub type ExitedPositionsId = String;
 pub fn determine_exited_positions_id(engine_id: Uuid) -> ExitedPositionsId {
     format!("positions_exited_{}", engine_id)
 }
+
+/// Returns the [`MarketId`] a Portfolio's statistics for the strategy trading a market are stored
+/// at, given the market's [`MarketId`] & the [`StrategyId`].
+pub fn determine_strategy_market_id(market_id: &MarketId, strategy_id: &StrategyId) -> MarketId {
+    MarketId(format!("{}_strategy_{}", market_id.0, strategy_id))
+}
@@ -1,7 +1,7 @@
 use crate::{
     portfolio::{
         error::PortfolioError,
-        position::{determine_instrument_id, InstrumentId, Position},
+        position::{determine_instrument_id, InstrumentId, Position, PositionMode},
         repository::{
             determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
             StatisticHandler,
@@ -37,6 +37,7 @@ where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
     pool: Pool<RedisConnectionManager>,
+    position_mode: PositionMode,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -44,12 +45,16 @@ impl<Statistic> PositionHandler for RedisRepository<Statistic>
 where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
+    fn position_mode(&self) -> PositionMode {
+        self.position_mode
+    }
+
     fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
         let position_string = serde_json::to_string(&position)?;
 
         let mut conn = self.pool.get().unwrap();
         conn.set(
-            format!("{}_{}", position.instrument_id, position.signal_id),
+            self.open_position_key(&position.instrument_id, &position.signal_id),
             position_string,
         )
         .map_err(|_| RepositoryError::WriteError)
@@ -111,7 +116,7 @@ where
         signal_id: &Uuid,
     ) -> Result<Option<Position>, RepositoryError> {
         let mut conn = self.conn();
-        let key = format!("{}_{}", instrument_id, signal_id);
+        let key = self.open_position_key(instrument_id, signal_id);
         let position_value: String = conn.get(key).map_err(|_| RepositoryError::ReadError)?;
         let p = serde_json::from_str::<Position>(&position_value)?;
         Ok(Some(p))
@@ -123,7 +128,7 @@ where
         signal_id: &Uuid,
     ) -> Result<Option<Position>, RepositoryError> {
         let mut conn = self.conn();
-        let key = format!("{}_{}", instrument_id, signal_id);
+        let key = self.open_position_key(instrument_id, signal_id);
         let position_value: String = conn.get(&key).map_err(|_| RepositoryError::ReadError)?;
         let p = serde_json::from_str::<Position>(&position_value)?;
 
@@ -197,10 +202,11 @@ where
 
     fn get_statistics(&self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
         let mut conn = self.conn();
-        let statistics: String = conn
+        let statistics: Option<String> = conn
             .get(&market_id.0)
             .map_err(|_| RepositoryError::ReadError)?;
 
+        let statistics = statistics.ok_or(RepositoryError::ExpectedDataNotPresentError)?;
         serde_json::from_str(&statistics).map_err(RepositoryError::JsonSerDeError)
     }
 }
@@ -222,10 +228,21 @@ where
     pub fn new(pool: Pool<RedisConnectionManager>) -> Self {
         Self {
             pool,
+            position_mode: PositionMode::default(),
             _statistic_marker: PhantomData::<Statistic>::default(),
         }
     }
 
+    /// Determines the key an open [`Position`] is stored at, given the [`PositionMode`] of this
+    /// [`RedisRepository`].
+    fn open_position_key(&self, instrument_id: &InstrumentId, signal_id: &Uuid) -> String {
+        format!(
+            "{}_{}",
+            instrument_id,
+            self.position_mode.position_key(*signal_id)
+        )
+    }
+
     pub fn conn(&self) -> PooledConnection<RedisConnectionManager> {
         self.pool.get().expect("Failed get a connection from pool")
     }
@@ -251,6 +268,7 @@ where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
     conn: Option<Pool<RedisConnectionManager>>,
+    position_mode: Option<PositionMode>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -261,6 +279,7 @@ where
     pub fn new() -> Self {
         Self {
             conn: None,
+            position_mode: None,
             _statistic_marker: PhantomData::<Statistic>::default(),
         }
     }
@@ -272,9 +291,17 @@ where
         }
     }
 
+    pub fn position_mode(self, value: PositionMode) -> Self {
+        Self {
+            position_mode: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<RedisRepository<Statistic>, PortfolioError> {
         Ok(RedisRepository {
             pool: self.conn.ok_or(PortfolioError::BuilderIncomplete("conn"))?,
+            position_mode: self.position_mode.unwrap_or_default(),
             _statistic_marker: PhantomData::<Statistic>::default(),
         })
     }
@@ -287,6 +314,7 @@ where
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RedisRepositoryBuilder")
             .field("conn", &"Option<redis::Connection>")
+            .field("position_mode", &self.position_mode)
             .field("_statistic_market", &self._statistic_marker)
             .finish()
     }
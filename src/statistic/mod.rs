@@ -23,3 +23,26 @@ where
     let seconds: i64 = Deserialize::deserialize(deserializer)?;
     Ok(Duration::seconds(seconds))
 }
+
+/// Serialize an optional [`Duration`] into an optional `u64` representing the associated seconds.
+pub fn se_option_duration_as_secs<S>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match duration {
+        Some(duration) => serializer.serialize_some(&duration.num_seconds()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize an optional number representing seconds into an optional [`Duration`]
+pub fn de_option_duration_from_secs<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let seconds: Option<i64> = Deserialize::deserialize(deserializer)?;
+    Ok(seconds.map(Duration::seconds))
+}
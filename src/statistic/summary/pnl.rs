@@ -124,8 +124,13 @@ impl PnLReturnSummary {
     }
 
     pub fn update_trades_per_day(&mut self) {
-        self.trades_per_day = self.total.count as f64
-            / (self.duration.num_seconds() as f64 / PnLReturnSummary::SECONDS_IN_DAY)
+        // Trades per day is undefined until the trading session has a duration
+        self.trades_per_day = match self.duration.num_seconds() {
+            0 => 0.0,
+            seconds => {
+                self.total.count as f64 / (seconds as f64 / PnLReturnSummary::SECONDS_IN_DAY)
+            }
+        }
     }
 }
 
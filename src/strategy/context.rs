@@ -0,0 +1,456 @@
+use super::{error::StrategyError, Signal, SignalGenerator, SignalIntent};
+use crate::{
+    data::{
+        bar::{bucket, bucket_start},
+        MarketMeta,
+    },
+    portfolio::{
+        position::Position,
+        repository::{BalanceHandler, PositionHandler},
+        Balance,
+    },
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::Market;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+/// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`]
+/// alongside a read-only [`StrategyContext`]. Used via the [`Contextual`] [`SignalGenerator`]
+/// adapter.
+pub trait ContextualSignalGenerator {
+    /// Optionally return a [`Signal`] given input [`MarketEvent`] & [`StrategyContext`].
+    fn generate_signal(
+        &mut self,
+        market: &MarketEvent<DataKind>,
+        context: &StrategyContext<'_>,
+    ) -> Option<Signal>;
+
+    /// Return every [`SignalIntent`] given input [`MarketEvent`] & [`StrategyContext`].
+    /// Defaults to the [`Signal`] returned by generate_signal().
+    fn generate_intents(
+        &mut self,
+        market: &MarketEvent<DataKind>,
+        context: &StrategyContext<'_>,
+    ) -> Vec<SignalIntent> {
+        self.generate_signal(market, context)
+            .map(SignalIntent::Signal)
+            .into_iter()
+            .collect()
+    }
+
+    /// Updates the parameters of the strategy at runtime (eg/ via a remote
+    /// [`Command`](crate::engine::Command)). Strategies do not support this by default.
+    fn update_params(&mut self, _params: serde_json::Value) -> Result<(), StrategyError> {
+        Err(StrategyError::ParamsUpdateNotSupported)
+    }
+}
+
+/// Read-only view of the trading state available to a [`ContextualSignalGenerator`], enabling
+/// pairs trading, higher timeframe trend filters & position aware entries.
+#[derive(Debug)]
+pub struct StrategyContext<'a> {
+    /// [`Market`] the strategy is trading.
+    pub market: &'a Market,
+    /// Recent bars of the strategy [`Market`] across each configured [`Timeframe`].
+    pub timeframes: &'a [Timeframe],
+    /// Latest [`MarketMeta`] of every [`Market`] publishing to the shared [`MarketPrices`].
+    pub prices: &'a HashMap<Market, MarketMeta>,
+    /// Open [`Position`]s in the strategy [`Market`].
+    pub positions: &'a [Position],
+    /// Current Portfolio [`Balance`], if available.
+    pub balance: Option<Balance>,
+}
+
+impl StrategyContext<'_> {
+    /// Returns the [`Timeframe`] with the provided interval, if configured.
+    pub fn timeframe(&self, interval: Duration) -> Option<&Timeframe> {
+        self.timeframes
+            .iter()
+            .find(|timeframe| timeframe.interval == interval)
+    }
+
+    /// Returns the latest close price of the provided [`Market`], if published.
+    pub fn price(&self, market: &Market) -> Option<f64> {
+        self.prices.get(market).map(|meta| meta.close)
+    }
+}
+
+/// Candles of a [`Market`] aggregated over a fixed interval, aligned to multiples of the
+/// interval since the Unix epoch.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Timeframe {
+    pub interval: Duration,
+    /// Most recently completed bars, oldest first.
+    pub completed: VecDeque<Candle>,
+    /// Bar of the current interval, containing the latest [`MarketEvent`]. It's close_time is
+    /// the end of the interval.
+    pub forming: Option<Candle>,
+    /// Maximum number of completed bars retained.
+    history: usize,
+    bucket: i64,
+}
+
+impl Timeframe {
+    /// Constructs a new [`Timeframe`] of the provided interval, retaining up to `history`
+    /// completed bars.
+    pub fn new(interval: Duration, history: usize) -> Self {
+        Self {
+            interval,
+            completed: VecDeque::with_capacity(history),
+            forming: None,
+            history,
+            bucket: 0,
+        }
+    }
+
+    /// Aggregates the trade or candle [`MarketEvent`] into the forming bar.
+    fn update(&mut self, market: &MarketEvent<DataKind>) {
+        let (time, open, high, low, close, volume, trade_count) = match &market.kind {
+            DataKind::Trade(trade) => (
+                market.exchange_time,
+                trade.price,
+                trade.price,
+                trade.price,
+                trade.price,
+                trade.amount,
+                1,
+            ),
+            // Candles closing exactly on an interval boundary belong to the interval they close
+            DataKind::Candle(candle) => (
+                candle.close_time - chrono::Duration::nanoseconds(1),
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+                candle.trade_count,
+            ),
+            _ => return,
+        };
+
+        let bucket = bucket(time, self.interval);
+        if self.forming.is_some() && bucket != self.bucket {
+            if let Some(completed) = self.forming.take() {
+                if self.completed.len() == self.history {
+                    self.completed.pop_front();
+                }
+                if self.history > 0 {
+                    self.completed.push_back(completed);
+                }
+            }
+        }
+
+        match &mut self.forming {
+            Some(bar) => {
+                bar.high = bar.high.max(high);
+                bar.low = bar.low.min(low);
+                bar.close = close;
+                bar.volume += volume;
+                bar.trade_count += trade_count;
+            }
+            None => {
+                self.bucket = bucket;
+                self.forming = Some(Candle {
+                    close_time: bucket_start(bucket + 1, self.interval),
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                    trade_count,
+                });
+            }
+        }
+    }
+}
+
+/// Latest [`MarketMeta`] of many [`Market`]s, shared between the [`Contextual`] strategies of
+/// each [`Trader`](crate::engine::trader::Trader) so they can observe each others markets.
+#[derive(Clone, Debug, Default)]
+pub struct MarketPrices(Arc<RwLock<HashMap<Market, MarketMeta>>>);
+
+impl MarketPrices {
+    /// Returns the latest [`MarketMeta`] of the provided [`Market`], if published.
+    pub fn latest(&self, market: &Market) -> Option<MarketMeta> {
+        self.0.read().get(market).copied()
+    }
+
+    /// Publishes the latest [`MarketMeta`] of the provided [`Market`].
+    pub fn publish(&self, market: Market, meta: MarketMeta) {
+        self.0.write().insert(market, meta);
+    }
+}
+
+/// [`SignalGenerator`] adapter that provides a [`ContextualSignalGenerator`] with a read-only
+/// [`StrategyContext`], so it can be used as the strategy of a
+/// [`Trader`](crate::engine::trader::Trader).
+///
+/// Every [`MarketEvent`] updates the configured [`Timeframe`]s & publishes the latest price to
+/// the shared [`MarketPrices`], before the open [`Position`]s & [`Balance`] are read from the
+/// Portfolio.
+#[derive(Debug)]
+pub struct Contextual<Strategy, Portfolio> {
+    strategy: Strategy,
+    engine_id: Uuid,
+    market: Market,
+    portfolio: Arc<Mutex<Portfolio>>,
+    prices: MarketPrices,
+    timeframes: Vec<Timeframe>,
+}
+
+impl<Strategy, Portfolio> Contextual<Strategy, Portfolio>
+where
+    Strategy: ContextualSignalGenerator,
+    Portfolio: PositionHandler + BalanceHandler,
+{
+    /// Constructs a new [`Contextual`] adapter for the provided strategy trading the provided
+    /// [`Market`], without any [`Timeframe`]s.
+    pub fn new(
+        strategy: Strategy,
+        engine_id: Uuid,
+        market: Market,
+        portfolio: Arc<Mutex<Portfolio>>,
+        prices: MarketPrices,
+    ) -> Self {
+        Self {
+            strategy,
+            engine_id,
+            market,
+            portfolio,
+            prices,
+            timeframes: Vec::new(),
+        }
+    }
+
+    /// Adds a [`Timeframe`] of the provided interval, retaining up to `history` completed bars.
+    pub fn with_timeframe(mut self, interval: Duration, history: usize) -> Self {
+        self.timeframes.push(Timeframe::new(interval, history));
+        self
+    }
+
+    /// Updates the [`Timeframe`]s & [`MarketPrices`] with the [`MarketEvent`], before calling
+    /// the provided closure with the strategy & it's [`StrategyContext`].
+    fn with_context<Generate, Output>(
+        &mut self,
+        market: &MarketEvent<DataKind>,
+        generate: Generate,
+    ) -> Output
+    where
+        Generate: FnOnce(&mut Strategy, &StrategyContext<'_>) -> Output,
+    {
+        for timeframe in &mut self.timeframes {
+            timeframe.update(market);
+        }
+
+        let close = match &market.kind {
+            DataKind::Trade(trade) => Some(trade.price),
+            DataKind::Candle(candle) => Some(candle.close),
+            _ => None,
+        };
+        if let Some(close) = close {
+            self.prices.publish(
+                self.market.clone(),
+                MarketMeta {
+                    close,
+                    time: market.exchange_time,
+                },
+            );
+        }
+
+        let (positions, balance) = self.portfolio_state();
+        let prices = self.prices.0.read();
+
+        generate(
+            &mut self.strategy,
+            &StrategyContext {
+                market: &self.market,
+                timeframes: &self.timeframes,
+                prices: &prices,
+                positions: &positions,
+                balance,
+            },
+        )
+    }
+
+    /// Reads the open [`Position`]s in the strategy [`Market`] & the current [`Balance`] from
+    /// the Portfolio.
+    fn portfolio_state(&self) -> (Vec<Position>, Option<Balance>) {
+        let portfolio = self.portfolio.lock();
+
+        let positions = portfolio
+            .get_open_markets_positions(self.engine_id, std::iter::once(&self.market))
+            .unwrap_or_else(|error| {
+                warn!(?error, market = ?self.market, "failed to read open Positions");
+                Vec::new()
+            });
+
+        (positions, portfolio.get_balance(self.engine_id).ok())
+    }
+}
+
+impl<Strategy, Portfolio> SignalGenerator for Contextual<Strategy, Portfolio>
+where
+    Strategy: ContextualSignalGenerator,
+    Portfolio: PositionHandler + BalanceHandler,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        self.with_context(market, |strategy, context| {
+            strategy.generate_signal(market, context)
+        })
+    }
+
+    fn generate_intents(&mut self, market: &MarketEvent<DataKind>) -> Vec<SignalIntent> {
+        self.with_context(market, |strategy, context| {
+            strategy.generate_intents(market, context)
+        })
+    }
+
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), StrategyError> {
+        self.strategy.update_params(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::{position::determine_instrument_id, repository::in_memory::InMemoryRepository},
+        statistic::summary::pnl::PnLReturnSummary,
+        test_util::{market_event_candle, market_event_trade, position},
+    };
+    use barter_integration::model::{InstrumentKind, Side};
+    use chrono::{TimeZone, Utc};
+
+    /// Records a summary of each [`StrategyContext`] it receives.
+    #[derive(Default)]
+    struct Recorder {
+        seen: Vec<(Vec<Candle>, Option<f64>, usize, Option<f64>)>,
+    }
+
+    impl ContextualSignalGenerator for Recorder {
+        fn generate_signal(
+            &mut self,
+            _: &MarketEvent<DataKind>,
+            context: &StrategyContext<'_>,
+        ) -> Option<Signal> {
+            let eth = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+            self.seen.push((
+                context
+                    .timeframe(Duration::from_secs(120))
+                    .map(|timeframe| timeframe.completed.iter().copied().collect())
+                    .unwrap_or_default(),
+                context.price(&eth),
+                context.positions.len(),
+                context.balance.map(|balance| balance.total),
+            ));
+            None
+        }
+    }
+
+    fn candle(minute: u32, close: f64) -> MarketEvent<DataKind> {
+        let close_time = Utc.with_ymd_and_hms(2022, 4, 5, 20, minute, 0).unwrap();
+        MarketEvent {
+            exchange_time: close_time,
+            kind: DataKind::Candle(Candle {
+                close_time,
+                open: close - 1.0,
+                high: close + 1.0,
+                low: close - 2.0,
+                close,
+                volume: 1.0,
+                trade_count: 1,
+            }),
+            ..market_event_candle()
+        }
+    }
+
+    #[test]
+    fn should_provide_timeframes_other_market_prices_and_portfolio_state() {
+        let engine_id = Uuid::new_v4();
+        let btc = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let eth = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+
+        let mut repository = InMemoryRepository::<PnLReturnSummary>::new();
+        repository
+            .set_balance(
+                engine_id,
+                Balance {
+                    time: Utc::now(),
+                    total: 5000.0,
+                    available: 4000.0,
+                },
+            )
+            .unwrap();
+        repository
+            .set_open_position(Position {
+                instrument_id: determine_instrument_id(engine_id, &btc.exchange, &btc.instrument),
+                exchange: btc.exchange.clone(),
+                instrument: btc.instrument.clone(),
+                ..position()
+            })
+            .unwrap();
+        let portfolio = Arc::new(Mutex::new(repository));
+        let prices = MarketPrices::default();
+
+        let mut btc_strategy = Contextual::new(
+            Recorder::default(),
+            engine_id,
+            btc.clone(),
+            Arc::clone(&portfolio),
+            prices.clone(),
+        )
+        .with_timeframe(Duration::from_secs(120), 1);
+        let mut eth_strategy = Contextual::new(
+            Recorder::default(),
+            engine_id,
+            eth.clone(),
+            portfolio,
+            prices.clone(),
+        );
+
+        btc_strategy.generate_signal(&candle(1, 100.0));
+        eth_strategy.generate_signal(&MarketEvent {
+            exchange: eth.exchange.clone(),
+            instrument: eth.instrument.clone(),
+            ..market_event_trade(Side::Buy)
+        });
+        btc_strategy.generate_signal(&candle(2, 110.0));
+        btc_strategy.generate_signal(&candle(3, 90.0));
+        btc_strategy.generate_signal(&candle(5, 95.0));
+
+        let seen = &btc_strategy.strategy.seen;
+        assert_eq!(seen.len(), 4);
+        assert_eq!(seen[0].1, None);
+        assert_eq!(seen[1].1, Some(1000.0));
+        assert!(seen[1].0.is_empty());
+        assert_eq!(
+            seen[2].0,
+            vec![Candle {
+                close_time: Utc.with_ymd_and_hms(2022, 4, 5, 20, 2, 0).unwrap(),
+                open: 99.0,
+                high: 111.0,
+                low: 98.0,
+                close: 110.0,
+                volume: 2.0,
+                trade_count: 2,
+            }]
+        );
+        assert_eq!(seen[3].0.len(), 1);
+        assert_eq!(seen[3].0[0].close, 90.0);
+        assert!(seen
+            .iter()
+            .all(|(_, _, positions, balance)| { *positions == 1 && *balance == Some(5000.0) }));
+        assert_eq!(eth_strategy.strategy.seen[0].2, 0);
+        assert_eq!(prices.latest(&btc).map(|meta| meta.close), Some(95.0));
+    }
+}
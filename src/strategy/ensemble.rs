@@ -0,0 +1,482 @@
+use super::{
+    error::StrategyError, Signal, SignalAttribution, SignalGenerator, SignalIntent, Suggest,
+    SuggestInfo,
+};
+use barter_data::event::{DataKind, MarketEvent};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Determines how an [`Ensemble`] combines the [`Suggest`]s of it's members. Members that do not
+/// generate a [`Signal`] abstain, but still count towards the total member weight.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Vote {
+    /// Direction suggested by members holding more than half of the total member weight. The
+    /// combined strength is the weighted mean strength of those members.
+    Majority,
+    /// Direction of the weighted net strength (long positive, short negative) across every
+    /// member, if it's magnitude is at least the provided threshold. The combined strength is
+    /// that magnitude.
+    WeightedStrength { threshold: f64 },
+    /// Direction suggested by every member. The combined strength is the weighted mean strength
+    /// of every member.
+    Unanimous,
+}
+
+/// Named member strategy of an [`Ensemble`].
+struct Member {
+    name: String,
+    weight: f64,
+    strategy: Box<dyn SignalGenerator + Send>,
+}
+
+/// [`SignalGenerator`] combinator that aggregates the [`Suggest`]s of many member strategies
+/// trading the same market into a single [`Signal`], according to it's [`Vote`]. Plugs into
+/// [`Trader::builder().strategy(...)`](crate::engine::trader::TraderBuilder::strategy)
+/// unchanged.
+///
+/// Every member processes every [`MarketEvent`], so indicators stay up to date. The combined
+/// [`Signal`] takes it's `fail_price` & `target_price` from the weighted mean of the agreeing
+/// members that provide them, it's [`SignalExtra`](super::SignalExtra) from the first agreeing
+/// member, and attributes the [`Suggest`] of every member that voted.
+///
+/// When driven via [`SignalGenerator::generate_intents`], the first [`SignalIntent::Signal`] of
+/// each member is it's vote, and every member [`SignalIntent::Exit`] & [`SignalIntent::Amend`]
+/// is passed through unchanged.
+pub struct Ensemble {
+    vote: Vote,
+    members: Vec<Member>,
+}
+
+impl std::fmt::Debug for Ensemble {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ensemble")
+            .field("vote", &self.vote)
+            .field(
+                "members",
+                &self
+                    .members
+                    .iter()
+                    .map(|member| (&member.name, member.weight))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Ensemble {
+    /// Constructs a new [`Ensemble`] without any members, combining their [`Suggest`]s using the
+    /// provided [`Vote`].
+    pub fn new(vote: Vote) -> Self {
+        Self {
+            vote,
+            members: Vec::new(),
+        }
+    }
+
+    /// Adds a named member strategy with the provided voting weight.
+    pub fn member<Strategy>(
+        mut self,
+        name: impl Into<String>,
+        weight: f64,
+        strategy: Strategy,
+    ) -> Self
+    where
+        Strategy: SignalGenerator + Send + 'static,
+    {
+        self.members.push(Member {
+            name: name.into(),
+            weight,
+            strategy: Box::new(strategy),
+        });
+        self
+    }
+
+    /// Determines the winning direction (true if long) & combined strength of the votes.
+    fn tally(&self, votes: &[(&Member, Signal)]) -> Option<(bool, f64)> {
+        let total_weight = self.members.iter().map(|member| member.weight).sum::<f64>();
+        if votes.is_empty() || total_weight <= 0.0 {
+            return None;
+        }
+
+        let weight_of = |long: bool| {
+            votes
+                .iter()
+                .filter(|(_, signal)| is_long(&signal.suggest) == long)
+                .map(|(member, _)| member.weight)
+                .sum::<f64>()
+        };
+        let mean_strength = |long: bool| {
+            let (weighted, weight) = votes
+                .iter()
+                .filter(|(_, signal)| is_long(&signal.suggest) == long)
+                .fold((0.0, 0.0), |(weighted, weight), (member, signal)| {
+                    (
+                        weighted + member.weight * info(&signal.suggest).strength,
+                        weight + member.weight,
+                    )
+                });
+            weighted / weight
+        };
+
+        match self.vote {
+            Vote::Majority => [true, false]
+                .into_iter()
+                .find(|long| weight_of(*long) > total_weight / 2.0)
+                .map(|long| (long, mean_strength(long))),
+            Vote::WeightedStrength { threshold } => {
+                let net = votes
+                    .iter()
+                    .map(|(member, signal)| {
+                        let strength = member.weight * info(&signal.suggest).strength;
+                        if is_long(&signal.suggest) {
+                            strength
+                        } else {
+                            -strength
+                        }
+                    })
+                    .sum::<f64>()
+                    / total_weight;
+                (net != 0.0 && net.abs() >= threshold).then_some((net > 0.0, net.abs()))
+            }
+            Vote::Unanimous => {
+                let long = is_long(&votes[0].1.suggest);
+                (votes.len() == self.members.len() && weight_of(long) == total_weight)
+                    .then(|| (long, mean_strength(long)))
+            }
+        }
+    }
+
+    /// Combines the [`Signal`] of each member (in member order), if any, into a single
+    /// [`Signal`] according to the [`Vote`].
+    fn combine(&self, signals: Vec<Option<Signal>>) -> Option<Signal> {
+        let votes = self
+            .members
+            .iter()
+            .zip(signals)
+            .filter_map(|(member, signal)| signal.map(|signal| (member, signal)))
+            .collect::<Vec<_>>();
+
+        let (long, strength) = self.tally(&votes)?;
+        let agreeing = votes
+            .iter()
+            .filter(|(_, signal)| is_long(&signal.suggest) == long)
+            .collect::<Vec<_>>();
+
+        let weighted_price = |price: fn(&SuggestInfo) -> Option<f64>| {
+            let (weighted, weight) = agreeing
+                .iter()
+                .filter_map(|(member, signal)| {
+                    price(info(&signal.suggest)).map(|price| (member.weight, price))
+                })
+                .fold((0.0, 0.0), |(weighted, total), (weight, price)| {
+                    (weighted + weight * price, total + weight)
+                });
+            (weight > 0.0).then(|| weighted / weight)
+        };
+
+        let combined = SuggestInfo::new(
+            strength,
+            weighted_price(|info| info.fail_price),
+            weighted_price(|info| info.target_price),
+            agreeing
+                .iter()
+                .all(|(_, signal)| info(&signal.suggest).only_close_opposite),
+            agreeing
+                .iter()
+                .all(|(_, signal)| info(&signal.suggest).re_enter),
+        );
+
+        let (_, first) = agreeing.first()?;
+        Some(Signal {
+            signal_id: Uuid::new_v4(),
+            time: first.time,
+            exchange: first.exchange.clone(),
+            instrument: first.instrument.clone(),
+            suggest: if long {
+                Suggest::new_long(combined)
+            } else {
+                Suggest::new_short(combined)
+            },
+            market_meta: first.market_meta,
+            extra: first.extra,
+            attribution: votes
+                .iter()
+                .map(|(member, signal)| SignalAttribution {
+                    strategy: member.name.clone(),
+                    weight: member.weight,
+                    suggest: signal.suggest,
+                })
+                .collect(),
+            strategy_id: None,
+        })
+    }
+}
+
+impl SignalGenerator for Ensemble {
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        let signals = self
+            .members
+            .iter_mut()
+            .map(|member| member.strategy.generate_signal(market))
+            .collect::<Vec<_>>();
+
+        self.combine(signals)
+    }
+
+    fn generate_intents(&mut self, market: &MarketEvent<DataKind>) -> Vec<SignalIntent> {
+        let mut intents = Vec::new();
+        let signals = self
+            .members
+            .iter_mut()
+            .map(|member| {
+                let mut signal = None;
+                for intent in member.strategy.generate_intents(market) {
+                    match intent {
+                        SignalIntent::Signal(member_signal) => {
+                            signal.get_or_insert(member_signal);
+                        }
+                        intent => intents.push(intent),
+                    }
+                }
+                signal
+            })
+            .collect::<Vec<_>>();
+
+        intents.extend(self.combine(signals).map(SignalIntent::Signal));
+        intents
+    }
+
+    /// Updates the parameters of members using a JSON object keyed by member name (eg/
+    /// `{"rsi": {"rsi_period": 7}}`).
+    ///
+    /// Members are updated in turn, so the update is not atomic. If a member fails, the members
+    /// already updated keep their new parameters & are reported by
+    /// [`StrategyError::PartialParamsUpdate`], while the remaining members are left unchanged.
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), StrategyError> {
+        let params = match params {
+            serde_json::Value::Object(params) => params,
+            _ => {
+                return Err(StrategyError::InvalidParams(
+                    "expected an object keyed by member name".to_owned(),
+                ))
+            }
+        };
+
+        if let Some(name) = params
+            .keys()
+            .find(|name| !self.members.iter().any(|member| &member.name == *name))
+        {
+            return Err(StrategyError::InvalidParams(format!(
+                "unknown member: {name}"
+            )));
+        }
+
+        let mut applied = Vec::with_capacity(params.len());
+        for (name, params) in params {
+            if let Some(member) = self.members.iter_mut().find(|member| member.name == name) {
+                match member.strategy.update_params(params) {
+                    Ok(()) => applied.push(name),
+                    Err(error) if applied.is_empty() => return Err(error),
+                    Err(error) => {
+                        return Err(StrategyError::PartialParamsUpdate {
+                            applied,
+                            member: name,
+                            error: Box::new(error),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_long(suggest: &Suggest) -> bool {
+    matches!(suggest, Suggest::SuggestLong(_))
+}
+
+fn info(suggest: &Suggest) -> &SuggestInfo {
+    match suggest {
+        Suggest::SuggestLong(info) | Suggest::SuggestShort(info) => info,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        strategy::{
+            example::{Config, RSIStrategy},
+            Decision, ExitTrigger, SignalExtra, SignalPositionExit,
+        },
+        test_util::{market_event_candle, signal},
+    };
+    use chrono::Utc;
+
+    /// Member strategy that always generates the same [`Suggest`], if any.
+    struct Fixed(Option<Suggest>);
+
+    impl SignalGenerator for Fixed {
+        fn generate_signal(&mut self, _: &MarketEvent<DataKind>) -> Option<Signal> {
+            self.0.map(|suggest| Signal {
+                suggest,
+                ..signal()
+            })
+        }
+    }
+
+    /// Member strategy that always generates the same [`SignalIntent`]s.
+    struct Intents(Vec<SignalIntent>);
+
+    impl SignalGenerator for Intents {
+        fn generate_signal(&mut self, _: &MarketEvent<DataKind>) -> Option<Signal> {
+            None
+        }
+
+        fn generate_intents(&mut self, _: &MarketEvent<DataKind>) -> Vec<SignalIntent> {
+            self.0.clone()
+        }
+    }
+
+    fn suggest(decision: Decision, strength: f64, fail_price: Option<f64>) -> Option<Suggest> {
+        Some(Suggest::new(
+            decision, strength, fail_price, None, false, false,
+        ))
+    }
+
+    fn ensemble(vote: Vote) -> Ensemble {
+        Ensemble::new(vote)
+            .member("a", 2.0, Fixed(suggest(Decision::Long, 1.0, Some(90.0))))
+            .member("b", 1.0, Fixed(suggest(Decision::Long, 0.4, Some(96.0))))
+            .member("c", 1.0, Fixed(suggest(Decision::Short, 0.5, None)))
+            .member("d", 1.0, Fixed(None))
+    }
+
+    #[test]
+    fn should_combine_member_suggests_according_to_vote() {
+        let market = market_event_candle();
+
+        let majority = ensemble(Vote::Majority).generate_signal(&market).unwrap();
+        assert!(matches!(
+            majority.suggest,
+            Suggest::SuggestLong(info)
+                if (info.strength - 0.8).abs() < 1e-9 && info.fail_price == Some(92.0)
+        ));
+        assert_eq!(
+            majority
+                .attribution
+                .iter()
+                .map(|attribution| attribution.strategy.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+
+        // Net strength: (2.0 * 1.0 + 1.0 * 0.4 - 1.0 * 0.5) / 5.0 = 0.38
+        let weighted = ensemble(Vote::WeightedStrength { threshold: 0.3 })
+            .generate_signal(&market)
+            .unwrap();
+        assert!(matches!(
+            weighted.suggest,
+            Suggest::SuggestLong(info) if (info.strength - 0.38).abs() < 1e-9
+        ));
+        assert!(ensemble(Vote::WeightedStrength { threshold: 0.5 })
+            .generate_signal(&market)
+            .is_none());
+
+        assert!(ensemble(Vote::Unanimous).generate_signal(&market).is_none());
+        let unanimous = Ensemble::new(Vote::Unanimous)
+            .member("a", 1.0, Fixed(suggest(Decision::Short, 1.0, None)))
+            .member("b", 3.0, Fixed(suggest(Decision::Short, 0.2, None)))
+            .generate_signal(&market)
+            .unwrap();
+        assert!(matches!(
+            unanimous.suggest,
+            Suggest::SuggestShort(info) if (info.strength - 0.4).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn generate_intents_should_vote_on_member_signals_and_pass_through_exits() {
+        let exit = SignalIntent::Exit(SignalPositionExit {
+            signal_id: Uuid::new_v4(),
+            time: Utc::now(),
+            exchange: signal().exchange,
+            instrument: signal().instrument,
+            signal_extra: SignalExtra::default(),
+            trigger: ExitTrigger::Strategy,
+            price: 100.0,
+        });
+        let long = |strength| {
+            SignalIntent::Signal(Signal {
+                suggest: suggest(Decision::Long, strength, None).unwrap(),
+                ..signal()
+            })
+        };
+
+        let mut ensemble = Ensemble::new(Vote::Majority)
+            .member("a", 1.0, Intents(vec![exit.clone(), long(1.0), long(0.0)]))
+            .member("b", 1.0, Intents(vec![long(0.5)]))
+            .member("c", 1.0, Fixed(suggest(Decision::Short, 1.0, None)));
+
+        let intents = ensemble.generate_intents(&market_event_candle());
+
+        assert_eq!(intents.len(), 2);
+        assert_eq!(intents[0], exit);
+        assert!(matches!(
+            &intents[1],
+            SignalIntent::Signal(Signal { suggest: Suggest::SuggestLong(info), attribution, .. })
+                if (info.strength - 0.75).abs() < 1e-9 && attribution.len() == 3
+        ));
+
+        // Member exits are passed through even if the vote yields no Signal
+        let mut ensemble = Ensemble::new(Vote::Unanimous)
+            .member("a", 1.0, Intents(vec![exit.clone(), long(1.0)]))
+            .member("b", 1.0, Fixed(suggest(Decision::Short, 1.0, None)));
+
+        assert_eq!(
+            ensemble.generate_intents(&market_event_candle()),
+            vec![exit]
+        );
+    }
+
+    #[test]
+    fn update_params_rejects_unknown_members() {
+        let mut ensemble = ensemble(Vote::Majority);
+
+        assert!(matches!(
+            ensemble.update_params(serde_json::json!({ "z": {} })),
+            Err(StrategyError::InvalidParams(_))
+        ));
+        assert!(matches!(
+            ensemble.update_params(serde_json::json!({ "a": {} })),
+            Err(StrategyError::ParamsUpdateNotSupported)
+        ));
+    }
+
+    #[test]
+    fn update_params_reports_members_updated_before_a_member_fails() {
+        let rsi = || RSIStrategy::new(Config { rsi_period: 14 });
+        let mut ensemble = Ensemble::new(Vote::Majority)
+            .member("a", 1.0, rsi())
+            .member("b", 1.0, rsi())
+            .member("c", 1.0, rsi());
+
+        match ensemble.update_params(serde_json::json!({
+            "a": { "rsi_period": 7 },
+            "b": { "rsi_period": 0 },
+            "c": { "rsi_period": 7 },
+        })) {
+            Err(StrategyError::PartialParamsUpdate {
+                applied,
+                member,
+                error,
+            }) => {
+                assert_eq!(applied, vec!["a".to_owned()]);
+                assert_eq!(member, "b");
+                assert!(matches!(*error, StrategyError::InvalidParams(_)));
+            }
+            result => panic!("expected a partial params update, got {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+/// All errors generated in the barter::strategy module.
+#[derive(Error, Debug)]
+pub enum StrategyError {
+    #[error("Strategy does not support updating it's parameters at runtime")]
+    ParamsUpdateNotSupported,
+
+    #[error("Invalid strategy parameters: {0}")]
+    InvalidParams(String),
+
+    #[error("Ensemble member {member} failed to update it's parameters after {applied:?} were updated: {error}")]
+    PartialParamsUpdate {
+        applied: Vec<String>,
+        member: String,
+        error: Box<StrategyError>,
+    },
+}
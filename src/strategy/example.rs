@@ -1,8 +1,7 @@
 use super::{Decision, Signal, SignalGenerator};
 use crate::data::MarketMeta;
-use crate::strategy::{SignalExtra, Suggest};
+use crate::strategy::{error::StrategyError, SignalExtra, Suggest};
 use barter_data::event::{DataKind, MarketEvent};
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use ta::{indicators::RelativeStrengthIndex, Next};
 use uuid::Uuid;
@@ -36,7 +35,7 @@ impl SignalGenerator for RSIStrategy {
 
         Some(Signal {
             signal_id: Uuid::new_v4(),
-            time: Utc::now(),
+            time: market.exchange_time,
             exchange: market.exchange.clone(),
             instrument: market.instrument.clone(),
             market_meta: MarketMeta {
@@ -45,8 +44,21 @@ impl SignalGenerator for RSIStrategy {
             },
             suggest,
             extra: SignalExtra::default(),
+            attribution: Vec::new(),
+            strategy_id: None,
         })
     }
+
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), StrategyError> {
+        let config = serde_json::from_value::<Config>(params)
+            .map_err(|error| StrategyError::InvalidParams(error.to_string()))?;
+
+        // Re-initialise the RSI indicator using the updated rsi_period
+        self.rsi = RelativeStrengthIndex::new(config.rsi_period)
+            .map_err(|error| StrategyError::InvalidParams(error.to_string()))?;
+
+        Ok(())
+    }
 }
 
 impl RSIStrategy {
@@ -89,3 +101,24 @@ impl RSIStrategy {
         1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn update_params_reinitialises_rsi_or_rejects_invalid_params() {
+        let mut strategy = RSIStrategy::new(Config { rsi_period: 14 });
+
+        assert!(strategy.update_params(json!({ "rsi_period": 7 })).is_ok());
+        assert!(matches!(
+            strategy.update_params(json!({ "rsi_period": 0 })),
+            Err(StrategyError::InvalidParams(_))
+        ));
+        assert!(matches!(
+            strategy.update_params(json!({ "period": 7 })),
+            Err(StrategyError::InvalidParams(_))
+        ));
+    }
+}
@@ -1,17 +1,53 @@
-use crate::data::MarketMeta;
+use crate::{
+    data::MarketMeta,
+    statistic::{de_option_duration_from_secs, se_option_duration_as_secs},
+};
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{Exchange, Instrument, Market};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use error::StrategyError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Barter strategy module specific errors.
+pub mod error;
+
 /// Barter example RSI strategy [`SignalGenerator`] implementation.
 pub mod example;
 
+/// Configurable rule based [`SignalGenerator`] composing indicators & rule expressions.
+pub mod rule;
+
+/// [`SignalGenerator`] adapter providing strategies with a read-only context of multi-timeframe
+/// bars, other market prices, open positions & balance.
+pub mod context;
+
+/// [`SignalGenerator`] combinator aggregating the [`Suggest`]s of many member strategies by
+/// majority vote, weighted strength or unanimity.
+pub mod ensemble;
+
 /// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`].
 pub trait SignalGenerator {
     /// Optionally return a [`Signal`] given input [`MarketEvent`].
     fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal>;
+
+    /// Return every [`SignalIntent`] given input [`MarketEvent`], each routed by the
+    /// [`Trader`](crate::engine::trader::Trader) through the associated
+    /// [`OrderGenerator`](crate::portfolio::OrderGenerator) path. Strategies managing several
+    /// legs or positions per market override this, in which case generate_signal() may simply
+    /// return None. Defaults to the [`Signal`] returned by generate_signal().
+    fn generate_intents(&mut self, market: &MarketEvent<DataKind>) -> Vec<SignalIntent> {
+        self.generate_signal(market)
+            .map(SignalIntent::Signal)
+            .into_iter()
+            .collect()
+    }
+
+    /// Updates the parameters of the strategy at runtime (eg/ via a remote
+    /// [`Command`](crate::engine::Command)). Strategies do not support this by default.
+    fn update_params(&mut self, _params: serde_json::Value) -> Result<(), StrategyError> {
+        Err(StrategyError::ParamsUpdateNotSupported)
+    }
 }
 
 /// Advisory [`Signal`] for a [`Market`] detailing the [`SuggestInfo`] associated with each
@@ -26,12 +62,102 @@ pub struct Signal {
     /// Metadata propagated from the [`MarketEvent`] that yielded this [`Signal`].
     pub market_meta: MarketMeta,
     pub extra: SignalExtra,
+    /// Contribution of each member strategy if this [`Signal`] combines several (eg/ an
+    /// [`Ensemble`](ensemble::Ensemble)), otherwise empty. Carried through to the resulting
+    /// [`Position`](crate::portfolio::position::Position) so statistics can also be broken down
+    /// per member strategy, weighted by it's share of the vote.
+    #[serde(default)]
+    pub attribution: Vec<SignalAttribution>,
+    /// Identifier of the strategy that generated this [`Signal`], carried through the resulting
+    /// [`OrderEvent`](crate::portfolio::OrderEvent)s, [`FillEvent`](crate::execution::FillEvent)s
+    /// & [`Position`](crate::portfolio::position::Position) so statistics can be broken down per
+    /// strategy.
+    #[serde(default)]
+    pub strategy_id: Option<StrategyId>,
+}
+
+/// Unique identifier of a strategy trading a [`Market`], used to attribute
+/// [`Position`](crate::portfolio::position::Position)s & statistics to it.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct StrategyId(pub String);
+
+impl std::fmt::Display for StrategyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<S> From<S> for StrategyId
+where
+    S: Into<String>,
+{
+    fn from(id: S) -> Self {
+        Self(id.into())
+    }
 }
 
+/// Contribution of a single member strategy to a combined [`Signal`], describing how it voted.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct SignalAttribution {
+    /// Name of the member strategy.
+    pub strategy: String,
+    pub weight: f64,
+    /// [`Suggest`] of the member strategy.
+    pub suggest: Suggest,
+}
+
+/// Typed intent generated by a [`SignalGenerator`] for a single [`MarketEvent`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum SignalIntent {
+    /// Advisory [`Signal`] to enter (or close) positions, interpreted by
+    /// [`OrderGenerator::generate_order`](crate::portfolio::OrderGenerator::generate_order).
+    Signal(Signal),
+    /// Exit of a specific open [`Position`](crate::portfolio::position::Position), typically
+    /// with an [`ExitTrigger::Strategy`] trigger.
+    Exit(SignalPositionExit),
+    /// Amendment of the protective exit levels of a specific open
+    /// [`Position`](crate::portfolio::position::Position).
+    Amend(SignalPositionAmend),
+}
+
+/// Protective exit configuration attached to a [`Signal`] & propagated to the
+/// [`Position`](crate::portfolio::position::Position) it enters.
 #[derive(Clone, Copy, Default, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SignalExtra {
     pub take_profit_price: Option<f64>,
     pub stop_loss_price: Option<f64>,
+    /// Stop that trails the most favourable price seen since entry by the provided offset.
+    #[serde(default)]
+    pub trailing_stop: Option<PriceOffset>,
+    /// Moves the stop to the entry price once the market has moved the provided offset in favour
+    /// of the position.
+    #[serde(default)]
+    pub break_even_after: Option<PriceOffset>,
+    /// Exits the position once it has been open for at least the provided duration.
+    #[serde(
+        default,
+        deserialize_with = "de_option_duration_from_secs",
+        serialize_with = "se_option_duration_as_secs"
+    )]
+    pub max_holding_duration: Option<Duration>,
+}
+
+/// Price distance from a reference price, either as an absolute amount or as a percentage of the
+/// reference price in decimal form (eg/ 0.01 for 1%).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub enum PriceOffset {
+    Absolute(f64),
+    Percent(f64),
+}
+
+impl PriceOffset {
+    /// Calculates the absolute price distance of this [`PriceOffset`] from the reference price.
+    pub fn distance(&self, reference_price: f64) -> f64 {
+        match self {
+            PriceOffset::Absolute(amount) => *amount,
+            PriceOffset::Percent(pct) => reference_price * pct,
+        }
+    }
 }
 
 /// Describes the type of advisory signal the strategy is endorsing.
@@ -72,7 +198,7 @@ impl Decision {
 }
 
 /// Only two possibilities of a [`Signal`]
-#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub enum Suggest {
     SuggestLong(SuggestInfo),
     SuggestShort(SuggestInfo),
@@ -159,11 +285,42 @@ impl SuggestInfo {
 /// Exit a specified position. Take Profit and Stop Loss
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SignalPositionExit {
+    /// Signal identifier of the [`Position`](crate::portfolio::position::Position) to exit.
     pub signal_id: Uuid,
     pub time: DateTime<Utc>,
     pub exchange: Exchange,
     pub instrument: Instrument,
     pub signal_extra: SignalExtra,
+    /// Protective exit level (or remote request) that triggered this [`SignalPositionExit`].
+    pub trigger: ExitTrigger,
+    /// Expected execution price of the exit.
+    pub price: f64,
+}
+
+/// Replaces the [`SignalExtra`] protective exit levels (eg/ stop loss & take profit) of a
+/// specific open [`Position`](crate::portfolio::position::Position).
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct SignalPositionAmend {
+    /// Signal identifier of the [`Position`](crate::portfolio::position::Position) to amend.
+    pub signal_id: Uuid,
+    pub time: DateTime<Utc>,
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub signal_extra: SignalExtra,
+}
+
+/// Protective exit levels, or remote requests, that can trigger a [`SignalPositionExit`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum ExitTrigger {
+    TakeProfit,
+    StopLoss,
+    TrailingStop,
+    BreakEven,
+    MaxHoldingDuration,
+    /// Exit requested remotely (eg/ via a [`Command`](crate::engine::Command)).
+    Manual,
+    /// Exit requested by a [`SignalGenerator`] via a [`SignalIntent::Exit`].
+    Strategy,
 }
 
 /// use this Signal to Exit all positions of a instrument.
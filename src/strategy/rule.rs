@@ -0,0 +1,735 @@
+use super::{
+    error::StrategyError, Decision, PriceOffset, Signal, SignalExtra, SignalGenerator, Suggest,
+};
+use crate::data::MarketMeta;
+use barter_data::event::{DataKind, MarketEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ta::{
+    indicators::{
+        EfficiencyRatio, ExponentialMovingAverage, Maximum, MeanAbsoluteDeviation, Minimum,
+        RateOfChange, RelativeStrengthIndex, SimpleMovingAverage, StandardDeviation,
+    },
+    Next,
+};
+use uuid::Uuid;
+
+/// Boxed indicator consuming one input value per [`MarketEvent`].
+type BoxedIndicator = Box<dyn Next<f64, Output = f64> + Send>;
+
+/// Configuration for constructing a [`RuleStrategy`] via the new() constructor method, or for
+/// updating it's parameters at runtime.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    pub indicators: Vec<IndicatorConfig>,
+    /// [`SignalRule`]s evaluated in order, the first satisfied of which generates the [`Signal`].
+    pub rules: Vec<SignalRule>,
+}
+
+/// Named built-in indicator of a [`RuleStrategy`], referenced by [`Operand::Indicator`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct IndicatorConfig {
+    pub name: String,
+    pub source: Source,
+    pub kind: IndicatorKind,
+}
+
+/// Built-in [`ta`] indicators, each parameterised by their period. Indicators are considered
+/// warmed-up once they have consumed period inputs.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum IndicatorKind {
+    SimpleMovingAverage(usize),
+    ExponentialMovingAverage(usize),
+    RelativeStrengthIndex(usize),
+    StandardDeviation(usize),
+    MeanAbsoluteDeviation(usize),
+    Maximum(usize),
+    Minimum(usize),
+    RateOfChange(usize),
+    EfficiencyRatio(usize),
+}
+
+impl IndicatorKind {
+    /// Number of inputs required before the indicator output is meaningful.
+    fn warm_up(&self) -> usize {
+        match *self {
+            IndicatorKind::SimpleMovingAverage(period)
+            | IndicatorKind::ExponentialMovingAverage(period)
+            | IndicatorKind::RelativeStrengthIndex(period)
+            | IndicatorKind::StandardDeviation(period)
+            | IndicatorKind::MeanAbsoluteDeviation(period)
+            | IndicatorKind::Maximum(period)
+            | IndicatorKind::Minimum(period)
+            | IndicatorKind::RateOfChange(period)
+            | IndicatorKind::EfficiencyRatio(period) => period,
+        }
+    }
+
+    /// Constructs the [`ta`] indicator of this [`IndicatorKind`].
+    fn build(&self) -> Result<BoxedIndicator, ta::errors::TaError> {
+        Ok(match *self {
+            IndicatorKind::SimpleMovingAverage(period) => {
+                Box::new(SimpleMovingAverage::new(period)?)
+            }
+            IndicatorKind::ExponentialMovingAverage(period) => {
+                Box::new(ExponentialMovingAverage::new(period)?)
+            }
+            IndicatorKind::RelativeStrengthIndex(period) => {
+                Box::new(RelativeStrengthIndex::new(period)?)
+            }
+            IndicatorKind::StandardDeviation(period) => Box::new(StandardDeviation::new(period)?),
+            IndicatorKind::MeanAbsoluteDeviation(period) => {
+                Box::new(MeanAbsoluteDeviation::new(period)?)
+            }
+            IndicatorKind::Maximum(period) => Box::new(Maximum::new(period)?),
+            IndicatorKind::Minimum(period) => Box::new(Minimum::new(period)?),
+            IndicatorKind::RateOfChange(period) => Box::new(RateOfChange::new(period)?),
+            IndicatorKind::EfficiencyRatio(period) => Box::new(EfficiencyRatio::new(period)?),
+        })
+    }
+}
+
+/// Value of a [`MarketEvent`] consumed by an indicator. Trades use their price for every
+/// price [`Source`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum Source {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    Volume,
+    /// (high + low + close) / 3
+    Typical,
+}
+
+/// Value referenced by a [`Condition`], [`Strength`] or [`PriceLevel`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Operand {
+    /// Latest output of the named indicator.
+    Indicator(String),
+    /// Close price of the latest candle, or price of the latest trade.
+    Price,
+    Constant(f64),
+}
+
+/// Rule expression evaluated against the latest (& previous) indicator values.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Condition {
+    Above(Operand, Operand),
+    Below(Operand, Operand),
+    /// First [`Operand`] moved from at or below, to above the second [`Operand`].
+    CrossesAbove(Operand, Operand),
+    /// First [`Operand`] moved from at or above, to below the second [`Operand`].
+    CrossesBelow(Operand, Operand),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+/// Strength of the [`Suggest`] generated by a [`SignalRule`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Strength {
+    Constant(f64),
+    /// Linearly maps the [`Operand`] from `from` (strength 0.0) to `to` (strength 1.0), clamped
+    /// between 0.0 & 1.0 (eg/ an RSI from 60 to 100). `from` & `to` must differ.
+    Scaled {
+        operand: Operand,
+        from: f64,
+        to: f64,
+    },
+}
+
+/// Determines the `fail_price` or `target_price` of the [`Suggest`] generated by a
+/// [`SignalRule`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum PriceLevel {
+    /// Distance from the current price, against the suggested direction for a `fail_price`, and
+    /// in favour of it for a `target_price`.
+    Offset(PriceOffset),
+    /// Value of the [`Operand`] (eg/ a [`IndicatorKind::Minimum`] as a long `fail_price`).
+    Operand(Operand),
+}
+
+/// Generates a [`Suggest`] of the provided [`Decision`] when it's [`Condition`] is satisfied.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SignalRule {
+    pub decision: Decision,
+    pub condition: Condition,
+    pub strength: Strength,
+    #[serde(default)]
+    pub fail_price: Option<PriceLevel>,
+    #[serde(default)]
+    pub target_price: Option<PriceLevel>,
+    pub only_close_opposite: bool,
+    #[serde(default)]
+    pub re_enter: bool,
+}
+
+impl SignalRule {
+    /// Constructs a new [`SignalRule`] with a constant strength of 1.0, no `fail_price` or
+    /// `target_price`, that only closes opposite positions & does not re-enter.
+    pub fn new(decision: Decision, condition: Condition) -> Self {
+        Self {
+            decision,
+            condition,
+            strength: Strength::Constant(1.0),
+            fail_price: None,
+            target_price: None,
+            only_close_opposite: true,
+            re_enter: false,
+        }
+    }
+}
+
+/// Named indicator of a [`RuleStrategy`].
+struct Indicator {
+    name: String,
+    source: Source,
+    warm_up: usize,
+    /// Built-in [`IndicatorKind`], or None for custom indicators.
+    kind: Option<IndicatorKind>,
+    indicator: BoxedIndicator,
+}
+
+/// Price & indicator values after a [`MarketEvent`] was processed.
+#[derive(Clone, PartialEq, Debug)]
+struct Snapshot {
+    price: f64,
+    values: Vec<f64>,
+}
+
+/// Configurable [`SignalGenerator`] composing indicators (anything implementing [`ta::Next`])
+/// & [`SignalRule`] expressions, so new strategies become configuration rather than bespoke
+/// [`SignalGenerator`] implementations.
+///
+/// Every indicator consumes each candle or trade [`MarketEvent`], but [`SignalRule`]s are only
+/// evaluated once every indicator is warmed-up.
+pub struct RuleStrategy {
+    indicators: Vec<Indicator>,
+    names: HashMap<String, usize>,
+    rules: Vec<SignalRule>,
+    updates: usize,
+    previous: Option<Snapshot>,
+}
+
+impl std::fmt::Debug for RuleStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleStrategy")
+            .field(
+                "indicators",
+                &self
+                    .indicators
+                    .iter()
+                    .map(|indicator| (&indicator.name, indicator.source, indicator.kind))
+                    .collect::<Vec<_>>(),
+            )
+            .field("rules", &self.rules)
+            .field("updates", &self.updates)
+            .finish()
+    }
+}
+
+impl SignalGenerator for RuleStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        let current = self.update(&market.kind)?;
+        let previous = self.previous.replace(current.clone());
+
+        let warmed_up = self
+            .indicators
+            .iter()
+            .all(|indicator| self.updates >= indicator.warm_up);
+        if !warmed_up {
+            return None;
+        }
+
+        let suggest = self.rules.iter().find_map(|rule| {
+            self.evaluate(&rule.condition, &current, previous.as_ref())
+                .then(|| self.suggest(rule, &current))
+        })?;
+
+        Some(Signal {
+            signal_id: Uuid::new_v4(),
+            time: market.exchange_time,
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: current.price,
+                time: market.exchange_time,
+            },
+            suggest,
+            extra: SignalExtra::default(),
+            attribution: Vec::new(),
+            strategy_id: None,
+        })
+    }
+
+    /// Replaces the built-in indicators & [`SignalRule`]s with those of the provided [`Config`],
+    /// retaining any custom indicators. The warm-up period restarts for every indicator, but
+    /// custom indicators keep their internal state rather than being reset.
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), StrategyError> {
+        let config = serde_json::from_value::<Config>(params)
+            .map_err(|error| StrategyError::InvalidParams(error.to_string()))?;
+
+        self.configure(config)
+    }
+}
+
+impl RuleStrategy {
+    /// Constructs a new [`RuleStrategy`] component using the provided configuration struct.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        let mut strategy = Self {
+            indicators: Vec::new(),
+            names: HashMap::new(),
+            rules: Vec::new(),
+            updates: 0,
+            previous: None,
+        };
+        strategy.configure(config)?;
+        Ok(strategy)
+    }
+
+    /// Adds a named custom indicator consuming the provided [`Source`], considered warmed-up
+    /// once it has consumed `warm_up` inputs. Indicators with multiple outputs (eg/
+    /// [`ta::indicators::BollingerBands`]) can be wrapped to output a single value.
+    pub fn with_indicator<I>(
+        mut self,
+        name: impl Into<String>,
+        source: Source,
+        warm_up: usize,
+        indicator: I,
+    ) -> Result<Self, StrategyError>
+    where
+        I: Next<f64, Output = f64> + Send + 'static,
+    {
+        let name = name.into();
+        if self.names.contains_key(&name) {
+            return Err(StrategyError::InvalidParams(format!(
+                "duplicate indicator name: {name}"
+            )));
+        }
+
+        self.names.insert(name.clone(), self.indicators.len());
+        self.indicators.push(Indicator {
+            name,
+            source,
+            warm_up,
+            kind: None,
+            indicator: Box::new(indicator),
+        });
+        Ok(self)
+    }
+
+    /// Adds a [`SignalRule`], evaluated after those already added. Rules may only reference
+    /// indicators that have already been added.
+    pub fn with_rule(mut self, rule: SignalRule) -> Result<Self, StrategyError> {
+        validate(&self.names, &rule)?;
+        self.rules.push(rule);
+        Ok(self)
+    }
+
+    /// Replaces the built-in indicators & [`SignalRule`]s with those of the provided [`Config`],
+    /// retaining any custom indicators & resetting the warm-up. Leaves the [`RuleStrategy`]
+    /// unchanged if the [`Config`] is invalid.
+    fn configure(&mut self, config: Config) -> Result<(), StrategyError> {
+        let mut indicators = config
+            .indicators
+            .into_iter()
+            .map(|config| {
+                let indicator = config
+                    .kind
+                    .build()
+                    .map_err(|error| StrategyError::InvalidParams(format!("{error:?}")))?;
+                Ok(Indicator {
+                    name: config.name,
+                    source: config.source,
+                    warm_up: config.kind.warm_up(),
+                    kind: Some(config.kind),
+                    indicator,
+                })
+            })
+            .collect::<Result<Vec<_>, StrategyError>>()?;
+
+        let custom = self
+            .indicators
+            .iter()
+            .filter(|indicator| indicator.kind.is_none());
+        let names = indicators
+            .iter()
+            .chain(custom)
+            .enumerate()
+            .map(|(index, indicator)| (indicator.name.clone(), index))
+            .collect::<HashMap<_, _>>();
+
+        if names.len() != indicators.len() + self.custom_count() {
+            return Err(StrategyError::InvalidParams(
+                "duplicate indicator name".to_owned(),
+            ));
+        }
+        for rule in &config.rules {
+            validate(&names, rule)?;
+        }
+
+        indicators.extend(
+            std::mem::take(&mut self.indicators)
+                .into_iter()
+                .filter(|indicator| indicator.kind.is_none()),
+        );
+        self.indicators = indicators;
+        self.names = names;
+        self.rules = config.rules;
+        self.updates = 0;
+        self.previous = None;
+        Ok(())
+    }
+
+    fn custom_count(&self) -> usize {
+        self.indicators
+            .iter()
+            .filter(|indicator| indicator.kind.is_none())
+            .count()
+    }
+
+    /// Updates every indicator with the candle or trade [`DataKind`], returning the resulting
+    /// [`Snapshot`]. Returns None for other [`DataKind`]s.
+    fn update(&mut self, kind: &DataKind) -> Option<Snapshot> {
+        let (open, high, low, close, volume) = match kind {
+            DataKind::Candle(candle) => (
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+            ),
+            DataKind::Trade(trade) => (
+                trade.price,
+                trade.price,
+                trade.price,
+                trade.price,
+                trade.amount,
+            ),
+            _ => return None,
+        };
+
+        let values = self
+            .indicators
+            .iter_mut()
+            .map(|indicator| {
+                indicator.indicator.next(match indicator.source {
+                    Source::Open => open,
+                    Source::High => high,
+                    Source::Low => low,
+                    Source::Close => close,
+                    Source::Volume => volume,
+                    Source::Typical => (high + low + close) / 3.0,
+                })
+            })
+            .collect();
+        self.updates += 1;
+
+        Some(Snapshot {
+            price: close,
+            values,
+        })
+    }
+
+    fn value(&self, operand: &Operand, snapshot: &Snapshot) -> f64 {
+        match operand {
+            Operand::Indicator(name) => self
+                .names
+                .get(name)
+                .and_then(|index| snapshot.values.get(*index))
+                .copied()
+                .unwrap_or(f64::NAN),
+            Operand::Price => snapshot.price,
+            Operand::Constant(value) => *value,
+        }
+    }
+
+    fn evaluate(
+        &self,
+        condition: &Condition,
+        current: &Snapshot,
+        previous: Option<&Snapshot>,
+    ) -> bool {
+        let crosses = |a: &Operand, b: &Operand, above: bool| {
+            previous.is_some_and(|previous| {
+                let (prev_a, prev_b) = (self.value(a, previous), self.value(b, previous));
+                let (a, b) = (self.value(a, current), self.value(b, current));
+                match above {
+                    true => prev_a <= prev_b && a > b,
+                    false => prev_a >= prev_b && a < b,
+                }
+            })
+        };
+
+        match condition {
+            Condition::Above(a, b) => self.value(a, current) > self.value(b, current),
+            Condition::Below(a, b) => self.value(a, current) < self.value(b, current),
+            Condition::CrossesAbove(a, b) => crosses(a, b, true),
+            Condition::CrossesBelow(a, b) => crosses(a, b, false),
+            Condition::All(conditions) => conditions
+                .iter()
+                .all(|condition| self.evaluate(condition, current, previous)),
+            Condition::Any(conditions) => conditions
+                .iter()
+                .any(|condition| self.evaluate(condition, current, previous)),
+            Condition::Not(condition) => !self.evaluate(condition, current, previous),
+        }
+    }
+
+    /// Constructs the [`Suggest`] of a satisfied [`SignalRule`].
+    fn suggest(&self, rule: &SignalRule, current: &Snapshot) -> Suggest {
+        let strength = match &rule.strength {
+            Strength::Constant(strength) => *strength,
+            Strength::Scaled { operand, from, to } => {
+                ((self.value(operand, current) - from) / (to - from)).clamp(0.0, 1.0)
+            }
+        };
+
+        // Long suggestions fail below & target above the current price, and vice versa
+        let direction = match rule.decision {
+            Decision::Long | Decision::CloseShort => 1.0,
+            Decision::Short | Decision::CloseLong => -1.0,
+        };
+        let level = |level: &PriceLevel, sign: f64| match level {
+            PriceLevel::Offset(offset) => {
+                current.price + sign * direction * offset.distance(current.price)
+            }
+            PriceLevel::Operand(operand) => self.value(operand, current),
+        };
+
+        Suggest::new(
+            rule.decision,
+            strength,
+            rule.fail_price.as_ref().map(|fail| level(fail, -1.0)),
+            rule.target_price.as_ref().map(|target| level(target, 1.0)),
+            rule.only_close_opposite,
+            rule.re_enter,
+        )
+    }
+}
+
+/// Validates that every [`Operand::Indicator`] referenced by the [`SignalRule`] exists.
+fn validate(names: &HashMap<String, usize>, rule: &SignalRule) -> Result<(), StrategyError> {
+    let mut operands = Vec::new();
+    collect_operands(&rule.condition, &mut operands);
+    if let Strength::Scaled { operand, from, to } = &rule.strength {
+        if from == to {
+            return Err(StrategyError::InvalidParams(format!(
+                "scaled strength requires distinct from & to: {from}"
+            )));
+        }
+        operands.push(operand);
+    }
+    for level in [&rule.fail_price, &rule.target_price].into_iter().flatten() {
+        if let PriceLevel::Operand(operand) = level {
+            operands.push(operand);
+        }
+    }
+
+    match operands.into_iter().find_map(|operand| match operand {
+        Operand::Indicator(name) if !names.contains_key(name) => Some(name),
+        _ => None,
+    }) {
+        Some(name) => Err(StrategyError::InvalidParams(format!(
+            "unknown indicator: {name}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Collects every [`Operand`] referenced by the [`Condition`].
+fn collect_operands<'a>(condition: &'a Condition, operands: &mut Vec<&'a Operand>) {
+    match condition {
+        Condition::Above(a, b)
+        | Condition::Below(a, b)
+        | Condition::CrossesAbove(a, b)
+        | Condition::CrossesBelow(a, b) => operands.extend([a, b]),
+        Condition::All(conditions) | Condition::Any(conditions) => conditions
+            .iter()
+            .for_each(|condition| collect_operands(condition, operands)),
+        Condition::Not(condition) => collect_operands(condition, operands),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+    use barter_data::subscription::candle::Candle;
+    use serde_json::json;
+
+    fn candle(close: f64) -> MarketEvent<DataKind> {
+        let event = market_event_candle();
+        MarketEvent {
+            kind: DataKind::Candle(Candle {
+                close_time: event.exchange_time,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+                trade_count: 1,
+            }),
+            ..event
+        }
+    }
+
+    fn indicator(name: &str, kind: IndicatorKind) -> IndicatorConfig {
+        IndicatorConfig {
+            name: name.to_owned(),
+            source: Source::Close,
+            kind,
+        }
+    }
+
+    fn named(name: &str) -> Operand {
+        Operand::Indicator(name.to_owned())
+    }
+
+    #[test]
+    fn should_generate_signals_on_moving_average_crossovers_after_warm_up() {
+        let mut strategy = RuleStrategy::new(Config {
+            indicators: vec![
+                indicator("fast", IndicatorKind::SimpleMovingAverage(2)),
+                indicator("slow", IndicatorKind::SimpleMovingAverage(4)),
+            ],
+            rules: vec![
+                SignalRule::new(
+                    Decision::Long,
+                    Condition::CrossesAbove(named("fast"), named("slow")),
+                ),
+                SignalRule::new(
+                    Decision::Short,
+                    Condition::CrossesBelow(named("fast"), named("slow")),
+                ),
+            ],
+        })
+        .unwrap();
+
+        let decisions = [10.0, 9.0, 8.0, 7.0, 6.0, 12.0, 14.0, 15.0, 9.0, 5.0]
+            .map(|close| {
+                strategy
+                    .generate_signal(&candle(close))
+                    .map(|signal| signal.suggest)
+            })
+            .map(|suggest| match suggest {
+                Some(Suggest::SuggestLong(_)) => Some(Decision::Long),
+                Some(Suggest::SuggestShort(_)) => Some(Decision::Short),
+                None => None,
+            });
+
+        assert_eq!(
+            decisions,
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(Decision::Long),
+                None,
+                None,
+                Some(Decision::Short),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_compute_strength_and_price_levels_using_custom_indicators() {
+        let mut strategy = RuleStrategy::new(Config::default())
+            .unwrap()
+            .with_indicator("min", Source::Low, 1, Minimum::new(3).unwrap())
+            .unwrap()
+            .with_rule(SignalRule {
+                strength: Strength::Scaled {
+                    operand: Operand::Price,
+                    from: 100.0,
+                    to: 200.0,
+                },
+                fail_price: Some(PriceLevel::Operand(named("min"))),
+                target_price: Some(PriceLevel::Offset(PriceOffset::Percent(0.1))),
+                ..SignalRule::new(
+                    Decision::Long,
+                    Condition::All(vec![
+                        Condition::Above(Operand::Price, Operand::Constant(100.0)),
+                        Condition::Not(Box::new(Condition::Below(Operand::Price, named("min")))),
+                    ]),
+                )
+            })
+            .unwrap();
+
+        assert!(strategy.generate_signal(&candle(90.0)).is_none());
+        let signal = strategy.generate_signal(&candle(125.0)).unwrap();
+
+        assert_eq!(
+            signal.suggest,
+            Suggest::new(Decision::Long, 0.25, Some(90.0), Some(137.5), true, false)
+        );
+        assert_eq!(signal.market_meta.close, 125.0);
+    }
+
+    #[test]
+    fn should_reject_invalid_rules_and_retain_custom_indicators_on_update_params() {
+        let unknown = Config {
+            rules: vec![SignalRule::new(
+                Decision::Long,
+                Condition::Above(named("missing"), Operand::Price),
+            )],
+            ..Config::default()
+        };
+        assert!(matches!(
+            RuleStrategy::new(unknown),
+            Err(StrategyError::InvalidParams(_))
+        ));
+
+        let degenerate = Config {
+            rules: vec![SignalRule {
+                strength: Strength::Scaled {
+                    operand: Operand::Price,
+                    from: 100.0,
+                    to: 100.0,
+                },
+                ..SignalRule::new(
+                    Decision::Long,
+                    Condition::Above(Operand::Price, Operand::Constant(0.0)),
+                )
+            }],
+            ..Config::default()
+        };
+        assert!(matches!(
+            RuleStrategy::new(degenerate),
+            Err(StrategyError::InvalidParams(_))
+        ));
+
+        let mut strategy = RuleStrategy::new(Config::default())
+            .unwrap()
+            .with_indicator("max", Source::High, 1, Maximum::new(2).unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            strategy.update_params(json!({
+                "indicators": [{ "name": "max", "source": "Close", "kind": { "Minimum": 2 } }],
+                "rules": [],
+            })),
+            Err(StrategyError::InvalidParams(_))
+        ));
+
+        strategy
+            .update_params(json!({
+                "indicators": [{ "name": "rsi", "source": "Close", "kind": { "RelativeStrengthIndex": 2 } }],
+                "rules": [{
+                    "decision": "Short",
+                    "condition": { "Below": [{ "Indicator": "max" }, { "Indicator": "rsi" }] },
+                    "strength": { "Constant": 0.5 },
+                    "only_close_opposite": false,
+                }],
+            }))
+            .unwrap();
+
+        assert_eq!(strategy.indicators.len(), 2);
+        assert_eq!(strategy.rules[0].decision, Decision::Short);
+    }
+}
@@ -1,26 +1,47 @@
+use async_trait::async_trait;
 use barter::{
-    data::historical,
-    engine::{trader::Trader, Engine},
-    event::EventTx,
+    clock::SharedClock,
+    data::{historical, live, MarketGenerator, MarketMeta},
+    engine::{
+        error::EngineError,
+        shutdown::ShutdownReason,
+        trader::{Trader, TraderBuilder},
+        Command, Engine,
+    },
+    event::{Event, EventTx},
     execution::{
+        error::ExecutionError,
+        order::{ExecutionReport, OrderId, OrderState, OrderUpdate},
         simulated::{Config as ExecutionConfig, SimulatedExecution},
-        Fees,
+        AsyncExecutionClient, ExecutionClient, Fees, FillEvent,
     },
     portfolio::{
         allocator::DefaultAllocator, portfolio::MetaPortfolio,
-        repository::in_memory::InMemoryRepository, risk::DefaultRisk,
+        repository::in_memory::InMemoryRepository, risk::DefaultRisk, OrderEvent,
     },
     statistic::summary::{
         trading::{Config as StatisticConfig, TradingSummary},
         Initialiser,
     },
-    strategy::example::{Config as StrategyConfig, RSIStrategy},
-    test_util::market_event_trade,
+    strategy::{
+        example::{Config as StrategyConfig, RSIStrategy},
+        Decision, Signal, SignalGenerator, Suggest,
+    },
+    test_util::{market_event_trade, signal},
 };
+use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{InstrumentKind, Market, Side};
+use chrono::Utc;
 use parking_lot::Mutex;
-use std::{collections::HashMap, sync::Arc, time::Duration};
-use tokio::sync::mpsc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 #[tokio::test]
@@ -38,6 +59,9 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
     // Create the Market(s) to be traded on (1-to-1 relationship with a Trader)
     let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
 
+    // Create Clock driven by MarketEvent exchange_time
+    let clock = SharedClock::historical();
+
     // Build global shared-state MetaPortfolio (1-to-1 relationship with an Engine)
     let portfolio = Arc::new(Mutex::new(
         MetaPortfolio::builder()
@@ -54,6 +78,7 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
                 trading_days_per_year: 365,
                 risk_free_return: 0.0,
             })
+            .clock(clock.clone())
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
     ));
@@ -75,13 +100,18 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
                 [market_event_trade(Side::Buy)].into_iter(),
             ))
             .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
-            .execution(SimulatedExecution::new(ExecutionConfig {
-                simulated_fees_pct: Fees {
-                    exchange: 0.1,
-                    slippage: 0.05,
-                    network: 0.0,
-                },
-            }))
+            .execution(
+                SimulatedExecution::new(ExecutionConfig {
+                    simulated_fees_pct: Fees {
+                        exchange: 0.1,
+                        slippage: 0.05,
+                        network: 0.0,
+                    },
+                    partial_fills: false,
+                })
+                .with_clock(clock.clone()),
+            )
+            .clock(clock.clone())
             .build()
             .expect("failed to build trader"),
     );
@@ -101,6 +131,7 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             trading_days_per_year: 365,
             risk_free_return: 0.0,
         }))
+        .clock(clock)
         .build()
         .expect("failed to build engine");
 
@@ -116,3 +147,583 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
         "failed because Engine's command_rx.await is blocking the Engine from stopping"
     )
 }
+
+/// Shared-state [`MetaPortfolio`] used by the [`Trader`]s under test.
+type Portfolio = MetaPortfolio<
+    InMemoryRepository<TradingSummary>,
+    DefaultAllocator,
+    DefaultRisk,
+    TradingSummary,
+>;
+
+/// Builds a [`MetaPortfolio`] trading the provided [`Market`].
+fn portfolio(engine_id: Uuid, market: &Market, clock: SharedClock) -> Arc<Mutex<Portfolio>> {
+    Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .clock(clock)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ))
+}
+
+/// Constructs a [`TraderBuilder`] for the provided [`Market`], leaving the data, strategy &
+/// execution to each test.
+fn trader<Data, Strategy, Execution>(
+    engine_id: Uuid,
+    market: Market,
+    command_rx: mpsc::Receiver<Command>,
+    event_tx: EventTx,
+    portfolio: Arc<Mutex<Portfolio>>,
+    clock: SharedClock,
+) -> TraderBuilder<EventTx, TradingSummary, Portfolio, Data, Strategy, Execution>
+where
+    Data: MarketGenerator<MarketEvent<DataKind>> + Send,
+    Strategy: SignalGenerator + Send,
+    Execution: ExecutionClient + Send,
+{
+    Trader::builder()
+        .engine_id(engine_id)
+        .market(market)
+        .command_rx(command_rx)
+        .event_tx(event_tx)
+        .portfolio(portfolio)
+        .clock(clock)
+}
+
+#[tokio::test]
+async fn engine_with_async_traders_stops_after_candles_finished() {
+    // Create channel to distribute Commands to the Engine & it's Traders (eg/ Command::Terminate)
+    let (_command_tx, command_rx) = mpsc::channel(20);
+
+    // Create Event channel to listen to all Engine Events in real-time
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+
+    // Generate unique identifier to associate an Engine's components
+    let engine_id = Uuid::new_v4();
+
+    // Create the Market(s) to be traded on (1-to-1 relationship with a Trader)
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    // Create Clock driven by MarketEvent exchange_time
+    let clock = SharedClock::historical();
+
+    // Build global shared-state MetaPortfolio (1-to-1 relationship with an Engine)
+    let portfolio = portfolio(engine_id, &market, clock.clone());
+
+    // Create channel for each Trader so the Engine can distribute Commands to it
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+
+    let traders = vec![trader(
+        engine_id,
+        market.clone(),
+        trader_command_rx,
+        event_tx,
+        Arc::clone(&portfolio),
+        clock.clone(),
+    )
+    .data(historical::MarketFeed::new([market_event_trade(Side::Buy)]))
+    .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+    .execution(
+        SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.1,
+                slippage: 0.05,
+                network: 0.0,
+            },
+            partial_fills: false,
+        })
+        .with_clock(clock.clone()),
+    )
+    .build()
+    .expect("failed to build trader")];
+
+    // Build Engine (1-to-many relationship with Traders)
+    // Create HashMap<Market, trader_command_tx> so Engine can route Commands to Traders
+    let trader_command_txs = HashMap::from_iter([(market, trader_command_tx)]);
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(traders)
+        .trader_command_txs(trader_command_txs)
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }))
+        .clock(clock)
+        .build()
+        .expect("failed to build engine");
+
+    // Run Engine with it's Traders as asynchronous tasks, which must also stop once the
+    // Traders have no more historical data to process
+    let timeout = Duration::from_millis(10);
+    let actual = tokio::time::timeout(timeout, engine.run_async()).await;
+
+    assert!(
+        actual.is_ok(),
+        "failed because Engine's command_rx.await is blocking the Engine from stopping"
+    );
+    assert_eq!(actual.unwrap().reason, ShutdownReason::TradersStopped);
+}
+
+/// [`ExecutionClient`] that receives [`ExecutionReport`]s asynchronously via a channel.
+struct ChannelExecution {
+    report_rx: mpsc::UnboundedReceiver<ExecutionReport>,
+}
+
+impl ExecutionClient for ChannelExecution {
+    fn submit_order(&mut self, _: &OrderEvent) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Ok(vec![])
+    }
+}
+
+#[async_trait]
+impl AsyncExecutionClient for ChannelExecution {
+    async fn next_report(&mut self) -> Option<ExecutionReport> {
+        self.report_rx.recv().await
+    }
+}
+
+#[tokio::test]
+async fn async_trader_waits_on_execution_reports_and_commands() {
+    let (command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let (report_tx, report_rx) = mpsc::unbounded_channel();
+    let (_market_tx, market_rx) = mpsc::unbounded_channel();
+
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    // Live MarketFeed that never yields, so only execution reports & Commands drive the Trader
+    let trader = trader(
+        engine_id,
+        market.clone(),
+        command_rx,
+        EventTx::new(event_tx),
+        portfolio(engine_id, &market, SharedClock::default()),
+        SharedClock::default(),
+    )
+    .data(live::MarketFeed::new(market_rx))
+    .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+    .execution(ChannelExecution { report_rx })
+    .build()
+    .expect("failed to build trader");
+
+    let trader_handle = tokio::spawn(trader.run_async());
+
+    let update = OrderUpdate {
+        order_id: OrderId::new(),
+        signal_id: Uuid::new_v4(),
+        time: Utc::now(),
+        exchange: market.exchange.clone(),
+        instrument: market.instrument.clone(),
+        state: OrderState::Acknowledged,
+        filled_quantity: 0.0,
+        remaining_quantity: 1.0,
+        reason: None,
+    };
+    report_tx
+        .send(ExecutionReport::Update(update.clone()))
+        .unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(1), event_rx.recv())
+        .await
+        .expect("Trader did not forward the asynchronous ExecutionReport");
+    assert_eq!(event, Some(Event::OrderUpdate(update)));
+
+    let (ack_tx, ack_rx) = oneshot::channel();
+    command_tx
+        .send(Command::Terminate("test finished".to_owned(), ack_tx))
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(1), trader_handle)
+        .await
+        .expect("Trader did not action Command::Terminate")
+        .unwrap();
+    assert!(matches!(ack_rx.await, Ok(Ok(()))));
+}
+
+/// [`ExecutionClient`] whose asynchronously received [`ExecutionReport`]s can only be polled.
+struct PollingExecution {
+    report_rx: mpsc::UnboundedReceiver<ExecutionReport>,
+}
+
+impl ExecutionClient for PollingExecution {
+    fn submit_order(&mut self, _: &OrderEvent) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        Ok(vec![])
+    }
+
+    fn poll_reports(&mut self) -> Vec<ExecutionReport> {
+        std::iter::from_fn(|| self.report_rx.try_recv().ok()).collect()
+    }
+}
+
+#[async_trait]
+impl AsyncExecutionClient for PollingExecution {
+    async fn next_report(&mut self) -> Option<ExecutionReport> {
+        None
+    }
+}
+
+#[tokio::test]
+async fn async_trader_polls_execution_reports_after_each_wake() {
+    let (command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let (report_tx, report_rx) = mpsc::unbounded_channel();
+    let (_market_tx, market_rx) = mpsc::unbounded_channel();
+
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let trader = trader(
+        engine_id,
+        market.clone(),
+        command_rx,
+        EventTx::new(event_tx),
+        portfolio(engine_id, &market, SharedClock::default()),
+        SharedClock::default(),
+    )
+    .data(live::MarketFeed::new(market_rx))
+    .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+    .execution(PollingExecution { report_rx })
+    .build()
+    .expect("failed to build trader");
+
+    let trader_handle = tokio::spawn(trader.run_async());
+
+    let update = OrderUpdate {
+        order_id: OrderId::new(),
+        signal_id: Uuid::new_v4(),
+        time: Utc::now(),
+        exchange: market.exchange.clone(),
+        instrument: market.instrument.clone(),
+        state: OrderState::Acknowledged,
+        filled_quantity: 0.0,
+        remaining_quantity: 1.0,
+        reason: None,
+    };
+    report_tx
+        .send(ExecutionReport::Update(update.clone()))
+        .unwrap();
+
+    // Wake the Trader with a Command, after which the polled ExecutionReport is forwarded
+    let (ack_tx, ack_rx) = oneshot::channel();
+    command_tx
+        .send(Command::PauseTrader(market, ack_tx))
+        .await
+        .unwrap();
+    assert!(matches!(ack_rx.await, Ok(Ok(()))));
+
+    let event = tokio::time::timeout(Duration::from_secs(1), event_rx.recv())
+        .await
+        .expect("Trader did not forward the polled ExecutionReport");
+    assert_eq!(event, Some(Event::OrderUpdate(update)));
+
+    let (ack_tx, _ack_rx) = oneshot::channel();
+    command_tx
+        .send(Command::Terminate("test finished".to_owned(), ack_tx))
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(1), trader_handle)
+        .await
+        .expect("Trader did not action Command::Terminate")
+        .unwrap();
+}
+
+/// [`ExecutionClient`] that fills entry orders at the order close price, but reports every exit
+/// order unfilled with the configured [`OrderState`] (eg/ `Cancelled` for an order book without
+/// any depth, or `Acknowledged` for an exit left resting on the book).
+struct ExitsNeverFillExecution {
+    exit_state: OrderState,
+}
+
+impl ExecutionClient for ExitsNeverFillExecution {
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Vec<ExecutionReport>, ExecutionError> {
+        if order.decision.is_exit() {
+            return Ok(vec![ExecutionReport::Update(OrderUpdate {
+                order_id: order.order_id,
+                signal_id: order.signal_id,
+                time: order.time,
+                exchange: order.exchange.clone(),
+                instrument: order.instrument.clone(),
+                state: self.exit_state,
+                filled_quantity: 0.0,
+                remaining_quantity: order.quantity,
+                reason: None,
+            })]);
+        }
+
+        Ok(vec![ExecutionReport::Fill(FillEvent {
+            order_id: order.order_id,
+            signal_id: order.signal_id,
+            time: order.time,
+            exchange: order.exchange.clone(),
+            instrument: order.instrument.clone(),
+            market_meta: order.market_meta,
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross: order.quantity.abs() * order.market_meta.close,
+            fees: Fees::default(),
+            signal_extra: order.signal_extra,
+            position_signal_id: order.position_signal_id,
+            strategy_id: order.strategy_id.clone(),
+            attribution: order.attribution.clone(),
+        })])
+    }
+}
+
+/// [`SignalGenerator`] suggesting Long for the first [`MarketEvent`], then Short for every
+/// following [`MarketEvent`].
+#[derive(Default)]
+struct LongThenShortStrategy {
+    markets: usize,
+}
+
+impl SignalGenerator for LongThenShortStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        let decision = match self.markets {
+            0 => Decision::Long,
+            _ => Decision::Short,
+        };
+        self.markets += 1;
+
+        Some(Signal {
+            time: market.exchange_time,
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            suggest: Suggest::new(decision, 1.0, None, None, false, false),
+            market_meta: MarketMeta {
+                close: 1000.0,
+                time: market.exchange_time,
+            },
+            ..signal()
+        })
+    }
+}
+
+#[tokio::test]
+async fn trader_drops_reversing_entry_when_opposite_exit_is_not_filled() {
+    let (_command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let trader = trader(
+        engine_id,
+        market.clone(),
+        command_rx,
+        EventTx::new(event_tx),
+        portfolio(engine_id, &market, SharedClock::default()),
+        SharedClock::default(),
+    )
+    .data(historical::MarketFeed::new(
+        [
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Sell),
+            market_event_trade(Side::Sell),
+        ]
+        .into_iter(),
+    ))
+    .strategy(LongThenShortStrategy::default())
+    .execution(ExitsNeverFillExecution {
+        exit_state: OrderState::Cancelled,
+    })
+    .build()
+    .expect("failed to build trader");
+
+    // Trader must stop once the MarketFeed is finished, rather than re-submitting exit orders
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        tokio::task::spawn_blocking(move || trader.run()),
+    )
+    .await
+    .expect("Trader kept re-queueing the entry Signal of an unfilled exit")
+    .unwrap();
+
+    let mut exit_orders = 0;
+    let mut positions_entered = 0;
+    while let Ok(event) = event_rx.try_recv() {
+        match event {
+            Event::OrderNew(order) if order.decision.is_exit() => exit_orders += 1,
+            Event::PositionNew(_) => positions_entered += 1,
+            _ => {}
+        }
+    }
+
+    // One exit attempt per Short Signal, with neither Short entry following the unfilled exit
+    assert_eq!(exit_orders, 2);
+    assert_eq!(positions_entered, 1);
+}
+
+/// [`SignalGenerator`] suggesting Long for every [`MarketEvent`], counting the number it has
+/// analysed.
+struct CountingStrategy {
+    markets: Arc<AtomicUsize>,
+}
+
+impl SignalGenerator for CountingStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        self.markets.fetch_add(1, Ordering::SeqCst);
+
+        Some(Signal {
+            time: market.exchange_time,
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            suggest: Suggest::new(Decision::Long, 1.0, None, None, false, false),
+            market_meta: MarketMeta {
+                close: 1000.0,
+                time: market.exchange_time,
+            },
+            ..signal()
+        })
+    }
+}
+
+#[tokio::test]
+async fn paused_trader_keeps_strategy_updated_but_discards_signals() {
+    let (command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let markets = Arc::new(AtomicUsize::new(0));
+    let trader = trader(
+        engine_id,
+        market.clone(),
+        command_rx,
+        EventTx::new(event_tx),
+        portfolio(engine_id, &market, SharedClock::default()),
+        SharedClock::default(),
+    )
+    .data(historical::MarketFeed::new(
+        [
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Buy),
+        ]
+        .into_iter(),
+    ))
+    .strategy(CountingStrategy {
+        markets: Arc::clone(&markets),
+    })
+    .execution(ExitsNeverFillExecution {
+        exit_state: OrderState::Cancelled,
+    })
+    .build()
+    .expect("failed to build trader");
+
+    let (pause_ack_tx, pause_ack_rx) = oneshot::channel();
+    let (exit_ack_tx, exit_ack_rx) = oneshot::channel();
+    command_tx
+        .send(Command::PauseTrader(market.clone(), pause_ack_tx))
+        .await
+        .unwrap();
+    command_tx
+        .send(Command::ExitPosition(market, exit_ack_tx))
+        .await
+        .unwrap();
+
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        tokio::task::spawn_blocking(move || trader.run()),
+    )
+    .await
+    .expect("Trader did not stop after the MarketFeed finished")
+    .unwrap();
+
+    assert!(matches!(pause_ack_rx.await, Ok(Ok(()))));
+    assert!(matches!(exit_ack_rx.await, Ok(Ok(()))));
+    assert_eq!(markets.load(Ordering::SeqCst), 3);
+    while let Ok(event) = event_rx.try_recv() {
+        assert!(
+            !matches!(event, Event::Signal(_)),
+            "paused Trader sent a Signal"
+        );
+    }
+}
+
+#[tokio::test]
+async fn trader_acks_superseded_exit_request_with_error() {
+    let (command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let (market_tx, market_rx) = mpsc::unbounded_channel();
+
+    let engine_id = Uuid::new_v4();
+    let trade = market_event_trade(Side::Buy);
+    let market = Market::new(trade.exchange.clone(), trade.instrument.clone());
+
+    let trader = trader(
+        engine_id,
+        market.clone(),
+        command_rx,
+        EventTx::new(event_tx),
+        portfolio(engine_id, &market, SharedClock::default()),
+        SharedClock::default(),
+    )
+    .data(live::MarketFeed::new(market_rx))
+    .strategy(CountingStrategy {
+        markets: Arc::new(AtomicUsize::new(0)),
+    })
+    .execution(ExitsNeverFillExecution {
+        exit_state: OrderState::Acknowledged,
+    })
+    .build()
+    .expect("failed to build trader");
+
+    let trader_handle = tokio::task::spawn_blocking(move || trader.run());
+
+    // Open a Position to exit
+    market_tx.send(trade.clone()).unwrap();
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !matches!(event_rx.recv().await, Some(Event::PositionNew(_))) {}
+    })
+    .await
+    .expect("Trader did not open a Position");
+
+    // Request the exit twice while the first request's exit orders are still resting
+    let (first_ack_tx, first_ack_rx) = oneshot::channel();
+    let (second_ack_tx, _second_ack_rx) = oneshot::channel();
+    command_tx
+        .send(Command::ExitPositionsAndConfirm(
+            market.clone(),
+            first_ack_tx,
+        ))
+        .await
+        .unwrap();
+    command_tx
+        .send(Command::ExitPositionsAndConfirm(market, second_ack_tx))
+        .await
+        .unwrap();
+
+    // Wake the Trader so it actions the Commands, then finish the MarketFeed
+    market_tx.send(trade).unwrap();
+    drop(market_tx);
+
+    tokio::time::timeout(Duration::from_secs(1), trader_handle)
+        .await
+        .expect("Trader did not stop after the MarketFeed finished")
+        .unwrap();
+
+    assert!(matches!(
+        first_ack_rx.await,
+        Ok(Err(EngineError::ExitSuperseded))
+    ));
+}